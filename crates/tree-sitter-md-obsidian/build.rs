@@ -3,7 +3,7 @@ fn main() {
     let inline_dir = std::path::Path::new("src/inline");
 
     let mut c_config = cc::Build::new();
-    c_config.std("c11").include(&block_dir).include(&inline_dir);
+    c_config.std("c11").include(block_dir).include(inline_dir);
 
     #[cfg(target_env = "msvc")]
     c_config.flag("-utf-8");
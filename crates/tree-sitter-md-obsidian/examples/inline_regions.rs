@@ -107,7 +107,7 @@ Regular paragraph after list with #important tag.
 }
 
 /// Recursively find all nodes with kind="inline" in the tree
-fn find_inline_nodes(tree: &Tree) -> Vec<Node> {
+fn find_inline_nodes(tree: &Tree) -> Vec<Node<'_>> {
     let mut nodes = Vec::new();
     walk_for_inline(&tree.root_node(), &mut nodes);
     nodes
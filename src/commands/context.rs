@@ -0,0 +1,51 @@
+use crate::context::{self, Bundle};
+use crate::vault::Vault;
+use anyhow::{Result, bail};
+
+pub fn handle_context(vault: &Vault, target: String, max_tokens: usize, json: bool) -> Result<()> {
+    let Some(link_index) = vault.load_link_index()? else {
+        bail!("Link index not found. Run 'kbase index --only links' first.");
+    };
+
+    let bundle = context::build(vault, &link_index, &target, max_tokens)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&bundle)?);
+    } else {
+        print_markdown(&bundle);
+    }
+
+    Ok(())
+}
+
+/// Print the bundle as markdown, each piece wrapped in boundary comments
+/// carrying its provenance.
+fn print_markdown(bundle: &Bundle) {
+    println!(
+        "<!-- kbase context: {} (query \"{}\", ~{} of {} tokens, {} sections omitted) -->",
+        bundle.seed.display(),
+        bundle.query,
+        bundle.tokens,
+        bundle.max_tokens,
+        bundle.omitted
+    );
+
+    for piece in &bundle.pieces {
+        let location = format!(
+            "{}:{}-{}",
+            piece.path.display(),
+            piece.start_line,
+            piece.end_line
+        );
+        println!();
+        println!(
+            "<!-- kbase: {} ({}, score {:.2}{}) -->",
+            location,
+            piece.source.label(),
+            piece.score,
+            if piece.truncated { ", truncated" } else { "" }
+        );
+        println!("{}", piece.content);
+        println!("<!-- kbase: end {} -->", location);
+    }
+}
@@ -20,7 +20,7 @@ pub fn handle_domains(vault: &Vault, sort: SortBy) -> Result<()> {
 
     // Sort
     match sort {
-        SortBy::Count => sorted.sort_by(|a, b| b.1.cmp(&a.1)),
+        SortBy::Count => sorted.sort_by_key(|d| std::cmp::Reverse(d.1)),
         SortBy::Name => {} // Already sorted by name from vault.domains()
     }
 
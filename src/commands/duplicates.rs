@@ -0,0 +1,92 @@
+use crate::duplicates::{self, Pair, Side};
+use crate::vault::Vault;
+use anyhow::{Result, bail};
+
+pub fn handle_duplicates(
+    vault: &Vault,
+    threshold: f64,
+    domain: Option<String>,
+    json: bool,
+) -> Result<()> {
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        bail!("--threshold must be greater than 0 and at most 1");
+    }
+    if let Some(domain) = &domain
+        && !vault.root.join(domain).is_dir()
+    {
+        bail!("Domain '{}' does not exist", domain);
+    }
+
+    let domain = domain.as_deref();
+    let notes = duplicates::find_pairs(&duplicates::note_units(vault, domain)?, threshold);
+    let sections = duplicates::find_pairs(&duplicates::section_units(vault, domain)?, threshold);
+
+    if json {
+        let result = serde_json::json!({
+            "threshold": threshold,
+            "notes": notes,
+            "sections": sections,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    if notes.is_empty() && sections.is_empty() {
+        println!("No duplicates found (threshold {:.2}).", threshold);
+        return Ok(());
+    }
+
+    if !notes.is_empty() {
+        println!("Similar notes (>= {:.2}):\n", threshold);
+        for pair in &notes {
+            print_pair(pair, note_label);
+        }
+    }
+    if !sections.is_empty() {
+        if !notes.is_empty() {
+            println!();
+        }
+        println!("Similar sections (>= {:.2}):\n", threshold);
+        for pair in &sections {
+            print_pair(pair, section_label);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_pair(pair: &Pair, label: fn(&Side) -> String) {
+    println!("  {:.2}  {}", pair.similarity, label(&pair.a));
+    println!("        {}", label(&pair.b));
+}
+
+/// `lucene/a.md (overlap: 3-12, 20)`
+fn note_label(side: &Side) -> String {
+    let ranges: Vec<String> = side
+        .overlap
+        .iter()
+        .map(|&(start, end)| format_range(start, end))
+        .collect();
+    format!("{} (overlap: {})", side.path.display(), ranges.join(", "))
+}
+
+/// `lucene/a.md:3-12 (Setup)`
+fn section_label(side: &Side) -> String {
+    let location = format!(
+        "{}:{}",
+        side.path.display(),
+        format_range(side.start_line, side.end_line)
+    );
+    match &side.heading {
+        Some(heading) => format!("{} ({})", location, heading),
+        None => location,
+    }
+}
+
+fn format_range(start: usize, end: usize) -> String {
+    if start == end {
+        start.to_string()
+    } else {
+        format!("{}-{}", start, end)
+    }
+}
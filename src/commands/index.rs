@@ -1,4 +1,7 @@
-use crate::{IndexType, links::LinkIndex, tags::TagIndex, vault::Vault};
+use crate::{
+    IndexType, links::LinkIndex, metadata::MetadataIndex, search::semantic::SemanticIndex,
+    tags::TagIndex, tasks::TaskIndex, vault::Vault,
+};
 use anyhow::Result;
 
 pub fn handle_index(vault: &Vault, only: Vec<IndexType>) -> Result<()> {
@@ -17,7 +20,7 @@ pub fn handle_index(vault: &Vault, only: Vec<IndexType>) -> Result<()> {
     if only.is_empty() || only.contains(&IndexType::Links) {
         println!("Building link index...");
         let (link_index, unresolved_count) = LinkIndex::build_from_vault(vault)?;
-        link_index.save_to_json(&index_dir)?;
+        vault.save_link_index(&link_index)?;
 
         println!("Built link index:");
         println!("  Saved to {}/links-forward.json", index_dir.display());
@@ -27,9 +30,62 @@ pub fn handle_index(vault: &Vault, only: Vec<IndexType>) -> Result<()> {
         }
     }
 
+    if only.is_empty() || only.contains(&IndexType::Metadata) {
+        println!("Building metadata index...");
+        let metadata_index = MetadataIndex::build_from_vault(vault)?;
+        vault.save_metadata_index(&metadata_index)?;
+
+        println!(
+            "Built metadata index: {} notes with metadata",
+            metadata_index.note_count()
+        );
+        println!("Saved to {}", index_dir.join("metadata.json").display());
+    }
+
+    if only.is_empty() || only.contains(&IndexType::Tasks) {
+        println!("Building task index...");
+        let task_index = TaskIndex::build_from_vault(vault)?;
+        vault.save_task_index(&task_index)?;
+
+        println!("Built task index: {} tasks", task_index.task_count());
+        println!("Saved to {}", index_dir.join("tasks.json").display());
+    }
+
     if only.is_empty() || only.contains(&IndexType::Search) {
-        println!("Search index not yet implemented");
+        println!("Building search index...");
+        let search_index = SemanticIndex::build_from_vault(vault)?;
+        vault.save_search_index(&search_index)?;
+
+        println!(
+            "Built search index: {} sections, {} terms",
+            search_index.sections.len(),
+            search_index.terms.len()
+        );
+        println!("Saved to {}", index_dir.join("search.json").display());
     }
 
     Ok(())
 }
+
+/// Rebuild the indexes that already exist on disk.
+/// Called after commands that rewrite notes, so later queries don't see stale paths.
+pub fn refresh_indexes(vault: &Vault) -> Result<()> {
+    let index_dir = vault.index_dir()?;
+    if index_dir.join("tags.json").exists() {
+        vault.save_tag_index(&TagIndex::build_from_vault(vault)?)?;
+    }
+    if index_dir.join("links-forward.json").exists() {
+        let (link_index, _) = LinkIndex::build_from_vault(vault)?;
+        vault.save_link_index(&link_index)?;
+    }
+    if index_dir.join("metadata.json").exists() {
+        vault.save_metadata_index(&MetadataIndex::build_from_vault(vault)?)?;
+    }
+    if index_dir.join("tasks.json").exists() {
+        vault.save_task_index(&TaskIndex::build_from_vault(vault)?)?;
+    }
+    if index_dir.join("search.json").exists() {
+        vault.save_search_index(&SemanticIndex::build_from_vault(vault)?)?;
+    }
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use crate::links::LinkIndex;
 use crate::vault::Vault;
-use anyhow::{bail, Context, Result};
-use std::path::PathBuf;
+use anyhow::{Result, bail};
+use std::path::Path;
 
 pub fn handle_links(
     vault: &Vault,
@@ -11,21 +11,16 @@ pub fn handle_links(
     json: bool,
 ) -> Result<()> {
     // Load the link index
-    let index_dir = vault.index_dir()?;
-    
-    if !index_dir.join("links-forward.json").exists() {
+    let Some(link_index) = vault.load_link_index()? else {
         bail!("Link index not found. Run 'kbase index --only links' first.");
-    }
-
-    let link_index = LinkIndex::load_from_json(&index_dir)
-        .context("Failed to load link index")?;
+    };
 
-    // Normalize note path
-    let note_path = PathBuf::from(&note);
+    let note_path = vault.resolve_note(&note)?;
+    let note = note_path.to_string_lossy().into_owned();
 
     // Determine what to show based on flags
-    let show_forward = forward || (!forward && !backward); // default to both if neither flag
-    let show_backward = backward || (!forward && !backward);
+    let show_forward = forward || !backward; // default to both if neither flag
+    let show_backward = backward || !forward;
 
     if json {
         output_json(&link_index, &note_path, show_forward, show_backward)?;
@@ -38,7 +33,7 @@ pub fn handle_links(
 
 fn output_text(
     index: &LinkIndex,
-    note_path: &PathBuf,
+    note_path: &Path,
     note_display: &str,
     show_forward: bool,
     show_backward: bool,
@@ -84,7 +79,7 @@ fn output_text(
 
 fn output_json(
     index: &LinkIndex,
-    note_path: &PathBuf,
+    note_path: &Path,
     show_forward: bool,
     show_backward: bool,
 ) -> Result<()> {
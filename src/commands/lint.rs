@@ -0,0 +1,124 @@
+use crate::commands::index::refresh_indexes;
+use crate::lint::{self, Diagnostic, LintContext, Severity, rules};
+use crate::vault::Vault;
+use anyhow::{Result, bail};
+
+/// `fix` is None to only report, or Some(dry_run) to apply fixable problems.
+pub fn handle_lint(
+    vault: &Vault,
+    only: Vec<String>,
+    strict: bool,
+    fix: Option<bool>,
+    json: bool,
+) -> Result<()> {
+    lint::validate_config(&vault.config.lint)?;
+
+    let mut rules = rules::all();
+    if !only.is_empty() {
+        let known: Vec<&str> = rules.iter().map(|r| r.name()).collect();
+        if let Some(name) = only.iter().find(|name| !known.contains(&name.as_str())) {
+            bail!("Unknown lint rule '{}'. Rules: {}", name, known.join(", "));
+        }
+        rules.retain(|r| only.iter().any(|name| name == r.name()));
+    }
+
+    let ctx = LintContext::build(vault)?;
+    let mut diagnostics = lint::run(&ctx, &rules, &vault.config.lint);
+
+    let mut fixed = 0;
+    if let Some(dry_run) = fix {
+        let changes = lint::fixes(&ctx, &diagnostics)?;
+        if dry_run {
+            print!("{}", changes.diff());
+            return Ok(());
+        }
+        if !changes.changes().is_empty() {
+            changes.commit(&vault.root)?;
+            refresh_indexes(vault)?;
+        }
+        fixed = diagnostics.iter().filter(|d| d.fixable).count();
+        diagnostics.retain(|d| !d.fixable);
+        if !json && fixed > 0 {
+            println!(
+                "Fixed {} {} in {} {}\n",
+                fixed,
+                plural(fixed, "problem"),
+                changes.changes().len(),
+                plural(changes.changes().len(), "note")
+            );
+        }
+    }
+
+    let errors = count(&diagnostics, Severity::Error);
+    let warnings = count(&diagnostics, Severity::Warning);
+
+    if json {
+        let result = serde_json::json!({
+            "errors": errors,
+            "warnings": warnings,
+            "fixed": fixed,
+            "diagnostics": diagnostics,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if diagnostics.is_empty() {
+        println!("No problems found.");
+    } else {
+        for d in &diagnostics {
+            println!(
+                "{}:{}:{}: {}[{}] {}",
+                d.path.display(),
+                d.line,
+                d.column,
+                d.severity,
+                d.rule,
+                d.message
+            );
+        }
+        let fixable = diagnostics.iter().filter(|d| d.fixable).count();
+        let hint = if fixable > 0 {
+            format!(" ({} fixable with --fix)", fixable)
+        } else {
+            String::new()
+        };
+        println!(
+            "\n{} {}, {} {}{}",
+            errors,
+            plural(errors, "error"),
+            warnings,
+            plural(warnings, "warning"),
+            hint
+        );
+    }
+
+    if errors > 0 || (strict && warnings > 0) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Print every rule with its effective severity for this vault.
+pub fn list_rules(vault: &Vault) -> Result<()> {
+    lint::validate_config(&vault.config.lint)?;
+
+    for rule in rules::all() {
+        let level = lint::severity(rule.as_ref(), &vault.config.lint)
+            .map_or("off".to_string(), |s| s.to_string());
+        println!("{:<16} {:<8} {}", rule.name(), level, rule.description());
+    }
+    Ok(())
+}
+
+fn count(diagnostics: &[Diagnostic], severity: Severity) -> usize {
+    diagnostics
+        .iter()
+        .filter(|d| d.severity == severity)
+        .count()
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        word.to_string()
+    } else {
+        format!("{}s", word)
+    }
+}
@@ -1,14 +1,26 @@
 // Command handlers
 
 pub mod config;
+pub mod context;
 pub mod domains;
+pub mod duplicates;
 pub mod index;
 pub mod links;
+pub mod lint;
+pub mod mv;
 pub mod notes;
+pub mod query;
 pub mod read;
+pub mod related;
+pub mod rename_heading;
+pub mod search;
+pub mod tag;
+pub mod task;
 pub mod tags;
+pub mod tasks;
 
 use crate::config::Config;
+use crate::tags::TagFilter;
 use crate::vault::Vault;
 use anyhow::Result;
 use std::env;
@@ -28,25 +40,152 @@ pub fn handle_command(command: Command) -> Result<()> {
             domains::handle_domains(&vault, sort)
         }
         Command::Notes {
+            mut domains,
             domain,
             term,
-            tag,
+            tags,
+            any_tags,
+            not_tags,
+            filters,
             files,
         } => {
             let vault = open_vault()?;
-            notes::handle_notes(&vault, domain, term, tag, files)
+            domains.extend(domain);
+            let tags = TagFilter {
+                all: tags,
+                any: any_tags,
+                none: not_tags,
+            };
+            notes::handle_notes(&vault, domains, term, tags, filters, files)
         }
         Command::Read {
             path,
             outline,
+            section,
+            expand_links,
+            depth,
             line_numbers,
         } => {
             let vault = open_vault()?;
-            read::handle_read(&vault, path, outline, line_numbers)
+            let expand_depth = expand_links.then_some(depth);
+            read::handle_read(&vault, path, outline, section, expand_depth, line_numbers)
         }
-        Command::Tags { sort } => {
+        Command::Task { action } => {
             let vault = open_vault()?;
-            tags::handle_tags(&vault, sort)
+            task::handle_task(&vault, action)
+        }
+        Command::Context {
+            target,
+            max_tokens,
+            json,
+        } => {
+            let vault = open_vault()?;
+            context::handle_context(&vault, target, max_tokens, json)
+        }
+        Command::Search {
+            query,
+            semantic,
+            blend,
+            domain,
+            logs,
+            limit,
+            json,
+        } => {
+            let mut vault = open_vault()?;
+            if logs {
+                vault.include_logs()?;
+            }
+            search::handle_search(&vault, query, semantic, blend, domain, limit, json)
+        }
+        Command::Duplicates {
+            threshold,
+            domain,
+            json,
+        } => {
+            let vault = open_vault()?;
+            duplicates::handle_duplicates(&vault, threshold, domain, json)
+        }
+        Command::Lint {
+            rules,
+            strict,
+            fix,
+            dry_run,
+            list_rules,
+            json,
+        } => {
+            let vault = open_vault()?;
+            if list_rules {
+                return lint::list_rules(&vault);
+            }
+            lint::handle_lint(&vault, rules, strict, fix.then_some(dry_run), json)
+        }
+        Command::Related {
+            note,
+            limit,
+            unlinked,
+            json,
+        } => {
+            let vault = open_vault()?;
+            related::handle_related(&vault, note, limit, unlinked, json)
+        }
+        Command::Query { query, json } => {
+            let vault = open_vault()?;
+            query::handle_query(&vault, query, json)
+        }
+        Command::Tasks {
+            done,
+            all,
+            domain,
+            file,
+            due,
+            priority,
+            sort,
+            verbose,
+            total,
+        } => {
+            let vault = open_vault()?;
+            let filter = tasks::TaskFilter {
+                done,
+                all,
+                domain,
+                file,
+                due,
+                priority: priority.map(Into::into),
+            };
+            tasks::handle_tasks(&vault, filter, sort, verbose, total)
+        }
+        Command::Tag {
+            name,
+            verbose,
+            action,
+        } => {
+            let vault = open_vault()?;
+            match action {
+                Some(action) => tag::handle_tag(&vault, action),
+                None => tag::list_tagged(&vault, name.unwrap_or_default(), verbose),
+            }
+        }
+        Command::Tags {
+            sort,
+            tree,
+            domains,
+            by_domain,
+            cooccur,
+            cooccur_matrix,
+        } => {
+            let vault = open_vault()?;
+            let view = if let Some(tag) = cooccur {
+                tags::TagsView::Cooccur(tag)
+            } else if cooccur_matrix {
+                tags::TagsView::CooccurMatrix
+            } else if by_domain {
+                tags::TagsView::ByDomain
+            } else if tree {
+                tags::TagsView::Tree
+            } else {
+                tags::TagsView::List
+            };
+            tags::handle_tags(&vault, sort, domains, view)
         }
         Command::Links {
             note,
@@ -57,6 +196,19 @@ pub fn handle_command(command: Command) -> Result<()> {
             let vault = open_vault()?;
             links::handle_links(&vault, note, forward, backward, json)
         }
+        Command::Mv { old, new, dry_run } => {
+            let vault = open_vault()?;
+            mv::handle_mv(&vault, old, new, dry_run)
+        }
+        Command::RenameHeading {
+            note,
+            old,
+            new,
+            dry_run,
+        } => {
+            let vault = open_vault()?;
+            rename_heading::handle_rename_heading(&vault, note, old, new, dry_run)
+        }
         Command::Index { only } => {
             let vault = open_vault()?;
             index::handle_index(&vault, only)
@@ -71,7 +223,7 @@ fn open_vault() -> Result<Vault> {
     // Check KBASE_VAULT environment variable first (vault name)
     if let Ok(vault_name) = env::var("KBASE_VAULT") {
         if let Some(vault_config) = config.vaults.get(&vault_name) {
-            return Vault::open(vault_config.path.clone(), vault_name);
+            return Vault::from_config(vault_name, vault_config.clone());
         } else {
             let available: Vec<_> = config.vaults.keys().map(|s| s.as_str()).collect();
             anyhow::bail!(
@@ -84,5 +236,5 @@ fn open_vault() -> Result<Vault> {
 
     // Fall back to active_vault from config
     let (vault_name, vault_config) = config.get_active_vault()?;
-    Vault::open(vault_config.path, vault_name)
+    Vault::from_config(vault_name, vault_config)
 }
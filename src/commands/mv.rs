@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+
+use crate::commands::index::refresh_indexes;
+use crate::edit::{ChangeSet, FileChange, TextEdit, apply_edits};
+use crate::links::index::should_index_wikilink;
+use crate::links::resolve::resolve_target;
+use crate::links::rewrite::{shortest_link, target_range};
+use crate::parser::{MarkdownParser, TreeSitterParser};
+use crate::vault::Vault;
+
+pub fn handle_mv(vault: &Vault, old: String, new: String, dry_run: bool) -> Result<()> {
+    let old_path = vault.resolve_note(&old)?;
+    let old = old_path.to_string_lossy().into_owned();
+
+    let new_path = destination_path(vault, &old_path, &new);
+    if vault.root.join(&new_path).exists() {
+        bail!("destination already exists: {}", new_path.display());
+    }
+
+    let Some(link_index) = vault.load_link_index()? else {
+        bail!("Link index not found. Run 'kbase index --only links' first.");
+    };
+
+    // Note sets before and after the move, for resolving links on both sides
+    let before: HashSet<PathBuf> = vault.all_notes()?.into_iter().map(|n| n.path).collect();
+    let mut after = before.clone();
+    after.remove(&old_path);
+    after.insert(new_path.clone());
+
+    let relink = Relink {
+        old: &old_path,
+        new: &new_path,
+        before: &before,
+        after: &after,
+    };
+
+    let mut parser = TreeSitterParser::new()?;
+    let mut changes = ChangeSet::new();
+    let mut link_count = 0;
+
+    // The moved note itself: its relative links may need rewriting from the new location
+    let content = vault.read_note(&old)?;
+    let edits = relink.edits(&mut parser, &content, &old_path, &new_path)?;
+    link_count += edits.len();
+    changes.push(FileChange {
+        from: old_path.clone(),
+        to: new_path.clone(),
+        updated: apply_edits(&content, &edits)?,
+        original: content,
+    });
+
+    // Every note linking to the moved note
+    let sources = link_index.get_backward(&old_path).unwrap_or_default();
+    for source in sources.iter().filter(|s| **s != old_path) {
+        let content = vault.read_note(&source.to_string_lossy())?;
+        let edits = relink.edits(&mut parser, &content, source, source)?;
+        link_count += edits.len();
+        let updated = apply_edits(&content, &edits)?;
+        changes.push(FileChange::modify(source.clone(), content, updated));
+    }
+
+    if dry_run {
+        print!("{}", changes.diff());
+        return Ok(());
+    }
+
+    changes.commit(&vault.root)?;
+    refresh_indexes(vault)?;
+
+    let note_count = changes.changes().iter().filter(|c| !c.is_move()).count();
+    println!("Moved {} → {}", old_path.display(), new_path.display());
+    println!("Updated {} links in {} notes", link_count, note_count);
+
+    Ok(())
+}
+
+/// Resolve the destination argument to a vault-relative note path.
+/// Accepts an existing folder (keeps the filename) and adds `.md` when missing.
+fn destination_path(vault: &Vault, old_path: &Path, new: &str) -> PathBuf {
+    let new_path = PathBuf::from(new);
+    if new.ends_with('/') || vault.root.join(&new_path).is_dir() {
+        return new_path.join(old_path.file_name().unwrap_or_default());
+    }
+    if new_path.extension().and_then(|e| e.to_str()) == Some("md") {
+        new_path
+    } else {
+        PathBuf::from(format!("{}.md", new))
+    }
+}
+
+/// Link rewriting context for a single move.
+struct Relink<'a> {
+    old: &'a Path,
+    new: &'a Path,
+    before: &'a HashSet<PathBuf>,
+    after: &'a HashSet<PathBuf>,
+}
+
+impl Relink<'_> {
+    /// Compute edits for every wikilink in `content` whose resolution changes
+    /// because of the move. `source_before`/`source_after` are the note's own
+    /// path before and after (they differ only for the moved note).
+    fn edits(
+        &self,
+        parser: &mut TreeSitterParser,
+        content: &str,
+        source_before: &Path,
+        source_after: &Path,
+    ) -> Result<Vec<TextEdit>> {
+        let parsed = parser.parse(content)?;
+        let mut edits = Vec::new();
+
+        for link in &parsed.wikilinks {
+            if !should_index_wikilink(&link.target) {
+                continue;
+            }
+            let Some(resolved) = resolve_target(&link.target, source_before, self.before) else {
+                continue;
+            };
+
+            let expected = if resolved == self.old {
+                self.new.to_path_buf()
+            } else {
+                resolved
+            };
+            if resolve_target(&link.target, source_after, self.after).as_ref() == Some(&expected) {
+                continue;
+            }
+
+            let Some(range) = target_range(content, link) else {
+                eprintln!(
+                    "Warning: could not locate [[{}]] at {}:{}, leaving it unchanged",
+                    link.target,
+                    source_before.display(),
+                    link.line
+                );
+                continue;
+            };
+            edits.push(TextEdit::new(
+                range,
+                shortest_link(&expected, source_after, self.after),
+            ));
+        }
+
+        Ok(edits)
+    }
+}
@@ -1,14 +1,18 @@
 use crate::{
+    metadata::FieldFilter,
     output,
-    vault::{Note, Vault},
+    tags::TagFilter,
+    vault::{Note, Vault, read_first_heading},
 };
 use anyhow::Result;
+use std::collections::{BTreeSet, HashSet};
 
 pub fn handle_notes(
     vault: &Vault,
-    domain: Option<String>,
+    mut domains: Vec<String>,
     term: Option<String>,
-    tag: Option<String>,
+    tags: TagFilter,
+    filters: Vec<String>,
     files: bool,
 ) -> Result<()> {
     if term.is_some() {
@@ -16,22 +20,52 @@ pub fn handle_notes(
         std::process::exit(1);
     }
 
-    let notes = if let Some(ref tag_name) = tag {
+    let filters = filters
+        .iter()
+        .map(|f| f.parse())
+        .collect::<Result<Vec<FieldFilter>>>()?;
+
+    // `kbase notes lucene --domain lucene` lists each note once
+    let mut seen = HashSet::new();
+    domains.retain(|d| seen.insert(d.clone()));
+
+    let mut notes = if !tags.is_empty() {
         // Tag-first filtering approach
-        get_notes_by_tag(vault, tag_name, domain.as_deref())?
+        get_notes_by_tags(vault, &tags, &domains)?
+    } else if domains.is_empty() {
+        vault.all_notes()?
     } else {
-        // Original behavior: domain or all notes
-        match &domain {
-            Some(d) => vault.notes_in_domain(d)?,
-            None => vault.all_notes()?,
+        let mut notes = Vec::new();
+        for domain in &domains {
+            notes.extend(vault.notes_in_domain(domain)?);
         }
+        notes
     };
 
+    if !filters.is_empty() {
+        notes = filter_by_metadata(vault, notes, &filters)?;
+    }
+
     if notes.is_empty() {
-        match (&domain, &tag) {
-            (Some(d), Some(t)) => println!("No notes in domain '{}' with tag '{}'.", d, t),
-            (None, Some(t)) => println!("No notes with tag '{}'.", t),
-            (Some(d), None) => println!("No notes in domain '{}'.", d),
+        if !filters.is_empty() {
+            println!("No notes match the given --where filters.");
+            return Ok(());
+        }
+        let domains = match domains.as_slice() {
+            [] => None,
+            [domain] => Some(format!("domain '{}'", domain)),
+            many => Some(format!("domains {}", quoted(many))),
+        };
+        let single_tag = tags.any.is_empty() && tags.none.is_empty();
+        let tags = match tags.all.as_slice() {
+            _ if tags.is_empty() => None,
+            [tag] if single_tag => Some(format!("tag '{}'", tag)),
+            _ => Some("the given tags".to_string()),
+        };
+        match (domains, tags) {
+            (Some(d), Some(t)) => println!("No notes in {} with {}.", d, t),
+            (None, Some(t)) => println!("No notes with {}.", t),
+            (Some(d), None) => println!("No notes in {}.", d),
             (None, None) => println!("No notes found."),
         }
         return Ok(());
@@ -53,28 +87,32 @@ pub fn handle_notes(
     Ok(())
 }
 
-/// Get notes by tag using tag-first filtering approach
-fn get_notes_by_tag(vault: &Vault, tag: &str, domain: Option<&str>) -> Result<Vec<Note>> {
+/// Get notes matching a tag filter using tag-first filtering approach
+fn get_notes_by_tags(vault: &Vault, tags: &TagFilter, domains: &[String]) -> Result<Vec<Note>> {
     // Load tag index
     let Some(tag_index) = vault.load_tag_index()? else {
         eprintln!("No tag index found. Run `kbase index` to build it first.");
         std::process::exit(1);
     };
 
-    // Get paths for the tag
-    let tagged_paths = tag_index.notes_with_tag(tag);
-    if tagged_paths.is_empty() {
-        return Ok(Vec::new()); // No notes with this tag
-    }
+    // Start from every tagged note, or every note if tags are only excluded
+    let candidates: BTreeSet<String> = if tags.all.is_empty() && tags.any.is_empty() {
+        vault
+            .all_notes()?
+            .into_iter()
+            .map(|n| n.path.to_string_lossy().into_owned())
+            .collect()
+    } else {
+        tag_index.tags_by_note().into_keys().collect()
+    };
+    let tagged_paths = tags.apply(&tag_index, candidates);
 
     // Convert paths to Notes, filtering by domain if specified
     let mut notes = Vec::new();
     for path_str in tagged_paths {
         // Apply domain filter if specified
-        if let Some(domain_name) = domain {
-            if !path_in_domain(&path_str, domain_name) {
-                continue;
-            }
+        if !domains.is_empty() && !domains.iter().any(|d| path_in_domain(&path_str, d)) {
+            continue;
         }
 
         // Convert path to Note
@@ -89,6 +127,34 @@ fn get_notes_by_tag(vault: &Vault, tag: &str, domain: Option<&str>) -> Result<Ve
     Ok(notes)
 }
 
+fn quoted(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|n| format!("'{}'", n))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Keep notes whose metadata satisfies every filter
+fn filter_by_metadata(
+    vault: &Vault,
+    notes: Vec<Note>,
+    filters: &[FieldFilter],
+) -> Result<Vec<Note>> {
+    let Some(metadata_index) = vault.load_metadata_index()? else {
+        eprintln!("No metadata index found. Run `kbase index` to build it first.");
+        std::process::exit(1);
+    };
+
+    Ok(notes
+        .into_iter()
+        .filter(|note| {
+            let metadata = metadata_index.get(&note.path.to_string_lossy());
+            filters.iter().all(|f| f.matches(metadata))
+        })
+        .collect())
+}
+
 /// Check if a path belongs to the specified domain
 fn path_in_domain(path: &str, domain: &str) -> bool {
     if let Some(slash_pos) = path.find('/') {
@@ -130,18 +196,3 @@ fn path_to_note(vault: &Vault, path_str: &str) -> Result<Note> {
         title,
     })
 }
-
-/// Read the first level-1 heading (`# Title`) from a file.
-/// Only scans the first 20 lines for performance.
-fn read_first_heading(path: &std::path::Path) -> Option<String> {
-    let content = std::fs::read_to_string(path).ok()?;
-    for line in content.lines().take(20) {
-        if let Some(rest) = line.trim().strip_prefix("# ") {
-            let title = rest.trim().to_string();
-            if !title.is_empty() {
-                return Some(title);
-            }
-        }
-    }
-    None
-}
@@ -0,0 +1,112 @@
+use crate::{
+    output,
+    query::{self, NoteRow, ResultTable, Source},
+    tags::TagIndex,
+    vault::Vault,
+};
+use anyhow::Result;
+
+pub fn handle_query(vault: &Vault, query: String, json: bool) -> Result<()> {
+    let mut query = query::parse_query(&query)?;
+
+    let (Some(metadata_index), Some(tag_index)) =
+        (vault.load_metadata_index()?, vault.load_tag_index()?)
+    else {
+        eprintln!("No metadata or tag index found. Run `kbase index` to build it first.");
+        std::process::exit(1);
+    };
+
+    if let Some(from) = &mut query.from {
+        normalize_tags(from, &tag_index);
+    }
+
+    let mut tags_by_note = tag_index.tags_by_note();
+    let notes = vault
+        .all_notes()?
+        .into_iter()
+        .map(|note| {
+            let path = note.path.to_string_lossy().to_string();
+            NoteRow {
+                tags: tags_by_note.remove(&path).unwrap_or_default(),
+                metadata: metadata_index.get(&path).cloned().unwrap_or_default(),
+                title: note.title,
+                path,
+            }
+        })
+        .collect();
+
+    let table = query::run(&query, notes)?;
+
+    if json {
+        output_json(&table)
+    } else {
+        output_text(&table);
+        Ok(())
+    }
+}
+
+/// Rewrite `FROM #tag` sources to the canonical tags the index stores.
+fn normalize_tags(source: &mut Source, tag_index: &TagIndex) {
+    match source {
+        Source::Tag(tag) => *tag = tag_index.normalize(tag),
+        Source::Folder(_) | Source::Domain(_) => {}
+        Source::Not(inner) => normalize_tags(inner, tag_index),
+        Source::And(a, b) | Source::Or(a, b) => {
+            normalize_tags(a, tag_index);
+            normalize_tags(b, tag_index);
+        }
+    }
+}
+
+fn output_text(table: &ResultTable) {
+    if table.rows.is_empty() {
+        println!("No results.");
+        return;
+    }
+
+    let mut headers: Vec<&str> = Vec::new();
+    if table.show_id {
+        headers.push("File");
+    }
+    headers.extend(table.columns.iter().map(String::as_str));
+
+    let rows: Vec<Vec<String>> = table
+        .rows
+        .iter()
+        .map(|row| {
+            let id = table.show_id.then(|| row.file.clone());
+            id.into_iter()
+                .chain(row.values.iter().map(|v| v.to_string()))
+                .collect()
+        })
+        .collect();
+
+    output::print_table_n(&headers, &rows);
+}
+
+fn output_json(table: &ResultTable) -> Result<()> {
+    use serde_json::json;
+
+    let rows: Vec<_> = table
+        .rows
+        .iter()
+        .map(|row| {
+            let fields: serde_json::Map<_, _> = table
+                .columns
+                .iter()
+                .zip(&row.values)
+                .map(|(name, value)| (name.clone(), json!(value)))
+                .collect();
+            json!({ "file": row.file, "fields": fields })
+        })
+        .collect();
+
+    let result = json!({
+        "columns": table.columns,
+        "total": rows.len(),
+        "rows": rows,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
@@ -1,19 +1,159 @@
-use crate::parser::{MarkdownParser, TreeSitterParser};
+use crate::links::expand::{Expanded, SkipReason, expand_links};
+use crate::parser::sections::{find_section, section_lines};
+use crate::parser::{Heading, MarkdownParser, TreeSitterParser};
 use crate::vault::Vault;
-use anyhow::Result;
+use anyhow::{Result, bail};
+use std::ops::RangeInclusive;
+use std::path::Path;
 
-pub fn handle_read(vault: &Vault, path: String, outline: bool, line_numbers: bool) -> Result<()> {
+pub fn handle_read(
+    vault: &Vault,
+    path: String,
+    outline: bool,
+    section: Option<String>,
+    expand_depth: Option<usize>,
+    line_numbers: bool,
+) -> Result<()> {
+    let path = vault.resolve_note(&path)?.to_string_lossy().into_owned();
     let content = vault.read_note(&path)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    let mut parser = TreeSitterParser::new()?;
+    let headings = parser.parse(&content)?.headings;
+
+    // Restrict output to one section, or the whole note
+    let range = match &section {
+        Some(section) => locate_section(&lines, &headings, &path, section)?,
+        None => 1..=lines.len(),
+    };
 
     if outline {
-        print_outline(&content, line_numbers)?;
+        print_outline(&headings, &range, lines.len(), line_numbers);
+    } else if let Some(depth) = expand_depth {
+        print_expanded(vault, &path, &lines, range, depth, line_numbers)?;
+    } else if section.is_none() && !line_numbers {
+        print!("{}", content);
     } else {
-        print_content(&content, line_numbers);
+        print_lines(&lines, range, line_numbers);
+    }
+
+    Ok(())
+}
+
+/// Print the note followed by the content it links to, each linked note or
+/// section wrapped in `<!-- kbase: ... -->` boundary comments.
+fn print_expanded(
+    vault: &Vault,
+    path: &str,
+    lines: &[&str],
+    range: RangeInclusive<usize>,
+    depth: usize,
+    line_numbers: bool,
+) -> Result<()> {
+    let Some(link_index) = vault.load_link_index()? else {
+        bail!("Link index not found. Run 'kbase index --only links' first.");
+    };
+
+    let expanded = expand_links(vault, &link_index, Path::new(path), range.clone(), depth)?;
+    print_lines(lines, range, line_numbers);
+
+    for item in expanded {
+        match item {
+            Expanded::Segment(segment) => {
+                let target = display_target(&segment.path, segment.section.as_deref());
+                println!();
+                println!(
+                    "<!-- kbase: {} (depth {}, linked from {}) -->",
+                    target,
+                    segment.depth,
+                    segment.from.display()
+                );
+                let seg_lines: Vec<&str> = segment.content.lines().collect();
+                print_lines(&seg_lines, segment.lines.clone(), line_numbers);
+                println!("<!-- kbase: end {} -->", target);
+            }
+            Expanded::Skipped {
+                path,
+                section,
+                from,
+                reason,
+            } => {
+                let reason = match reason {
+                    SkipReason::Duplicate => "already included",
+                    SkipReason::Cycle => "cycle",
+                    SkipReason::MissingSection => "section not found",
+                    SkipReason::AmbiguousSection => "section is ambiguous",
+                };
+                println!();
+                println!(
+                    "<!-- kbase: skipped {} ({}, linked from {}) -->",
+                    display_target(&path, section.as_deref()),
+                    reason,
+                    from.display()
+                );
+            }
+        }
     }
 
     Ok(())
 }
 
+fn display_target(path: &Path, section: Option<&str>) -> String {
+    match section {
+        Some(section) => format!("{}#{}", path.display(), section),
+        None => path.display().to_string(),
+    }
+}
+
+/// Resolve an `A/B` (or `A#B`) heading path to the section's line range.
+/// With `#` in the path, `/` is part of the heading text (`IO#Read/Write`); a
+/// `/` path that matches nothing is retried as one heading.
+fn locate_section(
+    lines: &[&str],
+    headings: &[Heading],
+    path: &str,
+    section: &str,
+) -> Result<RangeInclusive<usize>> {
+    let separator = if section.contains('#') { '#' } else { '/' };
+    let components: Vec<&str> = section.split(separator).collect();
+    let mut matches = find_section(headings, &components);
+    if matches.is_empty() && components.len() > 1 && separator == '/' {
+        matches = find_section(headings, &[section]);
+    }
+
+    match matches.as_slice() {
+        [] => bail!(
+            "section not found in {}: {} (use --outline to list headings)",
+            path,
+            section
+        ),
+        [index] => Ok(section_lines(lines, headings, *index)),
+        many => {
+            let candidates: Vec<String> = many
+                .iter()
+                .map(|&i| {
+                    let h = &headings[i];
+                    format!(
+                        "  {} {} (line {})",
+                        "#".repeat(h.level as usize),
+                        h.text,
+                        h.line
+                    )
+                })
+                .collect();
+            let separator = if section.contains('/') { '#' } else { '/' };
+            bail!(
+                "section '{}' is ambiguous in {}; add a parent heading, e.g. \"Parent{}{}\":\n{}",
+                section,
+                path,
+                separator,
+                section,
+                candidates.join("\n")
+            )
+        }
+    }
+}
+
 /// Calculate width needed for line numbers (minimum 6 chars)
 fn line_number_width(line_count: usize) -> usize {
     format!("{}", line_count).len().max(6)
@@ -26,18 +166,20 @@ fn print_line_numbered(line_num: usize, text: impl std::fmt::Display, width: usi
 
 /// Print heading outline, indented by level.
 /// Optionally shows line numbers where each heading appears.
-fn print_outline(content: &str, line_numbers: bool) -> Result<()> {
-    let mut parser = TreeSitterParser::new()?;
-    let parsed = parser.parse(content)?;
-
+fn print_outline(
+    headings: &[Heading],
+    range: &RangeInclusive<usize>,
+    line_count: usize,
+    line_numbers: bool,
+) {
     let width = if line_numbers {
-        line_number_width(content.lines().count())
+        line_number_width(line_count)
     } else {
         0
     };
 
     // Print each heading with indentation and markdown markers
-    for heading in parsed.headings {
+    for heading in headings.iter().filter(|h| range.contains(&h.line)) {
         let indent = "  ".repeat((heading.level - 1) as usize);
         let markers = "#".repeat(heading.level as usize);
         let text = format!("{}{} {}", indent, markers, heading.text);
@@ -48,23 +190,22 @@ fn print_outline(content: &str, line_numbers: bool) -> Result<()> {
             println!("{}", text);
         }
     }
-
-    Ok(())
 }
 
-/// Print full content, optionally with line numbers (cat -n style).
+/// Print a range of lines, optionally with their original line numbers (cat -n style).
 ///
 /// If `line_numbers` is true, numbers all lines including blank lines,
-/// right-aligned with tab separator.
-fn print_content(content: &str, line_numbers: bool) {
-    if line_numbers {
-        let lines: Vec<&str> = content.lines().collect();
-        let width = line_number_width(lines.len());
-
-        for (i, line) in lines.iter().enumerate() {
-            print_line_numbered(i + 1, line, width);
+/// right-aligned with tab separator. The width fits the whole note, so a
+/// section lines up with a full `read -n`.
+fn print_lines(lines: &[&str], range: RangeInclusive<usize>, line_numbers: bool) {
+    let width = line_number_width(lines.len());
+
+    for line_num in range {
+        let line = lines[line_num - 1];
+        if line_numbers {
+            print_line_numbered(line_num, line, width);
+        } else {
+            println!("{}", line);
         }
-    } else {
-        print!("{}", content);
     }
 }
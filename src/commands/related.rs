@@ -0,0 +1,96 @@
+use crate::related::{self, Related};
+use crate::vault::Vault;
+use anyhow::Result;
+use std::path::PathBuf;
+
+pub fn handle_related(
+    vault: &Vault,
+    note: String,
+    limit: usize,
+    unlinked: bool,
+    json: bool,
+) -> Result<()> {
+    let seed = vault.resolve_note(&note)?;
+
+    let (Some(tag_index), Some(link_index)) = (vault.load_tag_index()?, vault.load_link_index()?)
+    else {
+        eprintln!("No tag or link index found. Run `kbase index` to build it first.");
+        std::process::exit(1);
+    };
+
+    let mut results = related::related(vault, &tag_index, &link_index, &seed)?;
+    if unlinked {
+        results.retain(|r| !r.linked);
+    }
+    results.truncate(limit);
+
+    if json {
+        let result = serde_json::json!({
+            "note": seed,
+            "total": results.len(),
+            "related": results,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if results.is_empty() {
+        println!("No related notes found for {}", seed.display());
+    } else {
+        println!("Related to {}\n", seed.display());
+        for result in &results {
+            print_result(result);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print one result: score, path and title, then a line per signal that fired.
+fn print_result(result: &Related) {
+    let linked = if result.linked {
+        "  (already linked)"
+    } else {
+        ""
+    };
+    println!(
+        "{:.2}  {}  {}{}",
+        result.score,
+        result.path.display(),
+        result.title,
+        linked
+    );
+
+    let indent = "      ";
+    if !result.shared_tags.is_empty() {
+        let tags: Vec<String> = result
+            .shared_tags
+            .iter()
+            .map(|t| format!("#{}", t))
+            .collect();
+        println!("{}shared tags: {}", indent, tags.join(", "));
+    }
+    if !result.co_cited_by.is_empty() {
+        println!("{}co-cited by: {}", indent, join_paths(&result.co_cited_by));
+    }
+    if !result.both_link_to.is_empty() {
+        println!(
+            "{}both link to: {}",
+            indent,
+            join_paths(&result.both_link_to)
+        );
+    }
+    if result.signals.text > 0.0 {
+        println!(
+            "{}similar text ({:.2}): {}",
+            indent,
+            result.signals.text,
+            result.shared_terms.join(", ")
+        );
+    }
+}
+
+fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,188 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+
+use crate::commands::index::refresh_indexes;
+use crate::edit::{ChangeSet, FileChange, TextEdit, apply_edits, line_range};
+use crate::links::resolve::{AliasMap, resolve_with_aliases};
+use crate::links::rewrite::section_range;
+use crate::parser::{Heading, MarkdownParser, ParsedMarkdown, TreeSitterParser};
+use crate::vault::Vault;
+
+pub fn handle_rename_heading(
+    vault: &Vault,
+    note: String,
+    old: String,
+    new: String,
+    dry_run: bool,
+) -> Result<()> {
+    let new = new.trim().to_string();
+    if new.is_empty() {
+        bail!("New heading text cannot be empty");
+    }
+
+    let note_path = vault.resolve_note(&note)?;
+    let note = note_path.to_string_lossy().into_owned();
+    let content = vault.read_note(&note)?;
+
+    let mut parser = TreeSitterParser::new()?;
+    let parsed = parser.parse(&content)?;
+    let heading = find_heading(&parsed, &note, &old)?;
+    if heading.text.trim() == new {
+        bail!("heading is already named '{}' in {}", new, note);
+    }
+    let taken = parsed
+        .headings
+        .iter()
+        .any(|h| h.line != heading.line && section_matches(&h.text, &new));
+    if taken {
+        bail!("heading '{}' already exists in {}", new, note);
+    }
+
+    let Some(link_index) = vault.load_link_index()? else {
+        bail!("Link index not found. Run 'kbase index --only links' first.");
+    };
+    let all_notes: HashSet<PathBuf> = vault.all_notes()?.into_iter().map(|n| n.path).collect();
+    let aliases = vault.aliases()?;
+    let rename = SectionRename {
+        note: &note_path,
+        old: &old,
+        new: &new,
+        all_notes: &all_notes,
+        aliases: &aliases,
+    };
+
+    let mut changes = ChangeSet::new();
+    let mut link_count = 0;
+    let mut note_count = 0;
+
+    // The heading itself, plus any [[#Old]] links inside the same note
+    let mut edits = vec![heading_edit(&content, heading, &new)?];
+    let self_links = rename.edits(&content, &parsed, &note_path);
+    link_count += self_links.len();
+    edits.extend(self_links);
+    let updated = apply_edits(&content, &edits)?;
+    changes.push(FileChange::modify(note_path.clone(), content, updated));
+
+    // Section links from every note linking here
+    let sources = link_index.get_backward(&note_path).unwrap_or_default();
+    for source in sources.iter().filter(|s| **s != note_path) {
+        let content = vault.read_note(&source.to_string_lossy())?;
+        let parsed = parser.parse(&content)?;
+        let edits = rename.edits(&content, &parsed, source);
+        if edits.is_empty() {
+            continue;
+        }
+        link_count += edits.len();
+        note_count += 1;
+        let updated = apply_edits(&content, &edits)?;
+        changes.push(FileChange::modify(source.clone(), content, updated));
+    }
+
+    if dry_run {
+        print!("{}", changes.diff());
+        return Ok(());
+    }
+
+    changes.commit(&vault.root)?;
+    refresh_indexes(vault)?;
+
+    println!("Renamed heading '{}' → '{}' in {}", old, new, note);
+    println!("Updated {} links in {} notes", link_count, note_count);
+
+    Ok(())
+}
+
+/// Whether a heading (or `#` link segment) names `old`. Case is ignored,
+/// like Obsidian does for section links.
+fn section_matches(text: &str, old: &str) -> bool {
+    text.trim().eq_ignore_ascii_case(old.trim())
+}
+
+/// Find the single heading matching `old`.
+fn find_heading<'a>(parsed: &'a ParsedMarkdown, note: &str, old: &str) -> Result<&'a Heading> {
+    let matches: Vec<&Heading> = parsed
+        .headings
+        .iter()
+        .filter(|h| section_matches(&h.text, old))
+        .collect();
+    match matches.as_slice() {
+        [] => bail!("heading not found in {}: {}", note, old),
+        [heading] => Ok(heading),
+        many => {
+            let lines: Vec<String> = many.iter().map(|h| h.line.to_string()).collect();
+            bail!(
+                "heading '{}' appears {} times in {} (lines {}); rename is ambiguous",
+                old,
+                many.len(),
+                note,
+                lines.join(", ")
+            )
+        }
+    }
+}
+
+/// Replace the heading text on its line, keeping the `#` markers and any trailing text.
+fn heading_edit(content: &str, heading: &Heading, new: &str) -> Result<TextEdit> {
+    let old = heading.text.trim();
+    let range = line_range(content, heading.line)
+        .with_context(|| format!("heading line {} is out of range", heading.line))?;
+    let line = &content[range.clone()];
+
+    let markers_end = line.len() - line.trim_start_matches([' ', '#']).len();
+    let offset = line[markers_end..]
+        .find(old)
+        .map(|i| markers_end + i)
+        .with_context(|| format!("could not find '{}' on line {}", old, heading.line))?;
+
+    let start = range.start + offset;
+    Ok(TextEdit::new(start..start + old.len(), new))
+}
+
+/// Section-link rewriting context for a single heading rename.
+struct SectionRename<'a> {
+    note: &'a Path,
+    old: &'a str,
+    new: &'a str,
+    all_notes: &'a HashSet<PathBuf>,
+    aliases: &'a AliasMap,
+}
+
+impl SectionRename<'_> {
+    /// Edits for every `[[note#old]]` link in `content` (including nested
+    /// `[[note#Parent#old]]` chains and `[[Alias#old]]`). If several segments
+    /// of a chain match, only the last (closest to the linked section) is
+    /// renamed.
+    fn edits(&self, content: &str, parsed: &ParsedMarkdown, source: &Path) -> Vec<TextEdit> {
+        let mut edits = Vec::new();
+
+        for link in &parsed.wikilinks {
+            let Some(section) = &link.section else {
+                continue;
+            };
+
+            let points_here = if link.target.is_empty() {
+                source == self.note
+            } else {
+                resolve_with_aliases(&link.target, source, self.all_notes, self.aliases).as_deref()
+                    == Some(self.note)
+            };
+            if !points_here {
+                continue;
+            }
+
+            let mut parts: Vec<&str> = section.split('#').collect();
+            let Some(i) = parts.iter().rposition(|p| section_matches(p, self.old)) else {
+                continue;
+            };
+            parts[i] = self.new;
+
+            if let Some(range) = section_range(content, link) {
+                edits.push(TextEdit::new(range, parts.join("#")));
+            }
+        }
+
+        edits
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::search::sections::vault_sections;
+use crate::search::{Bm25, tokenize};
+use crate::vault::Vault;
+
+/// Best-matching section of a note.
+#[derive(Debug, Serialize)]
+struct Hit {
+    path: PathBuf,
+    title: String,
+    heading: Option<String>,
+    line: usize,
+    score: f64,
+}
+
+pub fn handle_search(
+    vault: &Vault,
+    query: String,
+    semantic: bool,
+    blend: Option<f64>,
+    domain: Option<String>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    if let Some(domain) = &domain
+        && !vault.root.join(domain).is_dir()
+    {
+        bail!("Domain '{}' does not exist", domain);
+    }
+    if blend.is_some_and(|w| !(0.0..=1.0).contains(&w)) {
+        bail!("--blend must be between 0 and 1");
+    }
+
+    let terms = tokenize(&query);
+    if terms.is_empty() {
+        bail!("No search terms in '{}'", query);
+    }
+
+    let sections = vault_sections(vault)?;
+    let titles: HashMap<&PathBuf, &str> = sections
+        .iter()
+        .map(|s| (&s.path, s.title.as_str()))
+        .collect();
+    let title_of = |path: &PathBuf| titles.get(path).map_or_else(String::new, |t| t.to_string());
+
+    // Section scores keyed by (path, start line), with their headings
+    let mut scores: HashMap<(PathBuf, usize), (Option<String>, f64)> = HashMap::new();
+
+    let bm25 = Bm25::new(sections.iter().map(|s| tokenize(&s.text)));
+    let lexical = bm25.search(&terms);
+    let best = lexical.first().map_or(1.0, |&(_, score)| score);
+
+    // Raw BM25 on its own; scaled to 0..1 and weighted when blended
+    let bm25_weight = if semantic {
+        blend.unwrap_or(0.0) / best
+    } else {
+        1.0
+    };
+    if bm25_weight > 0.0 {
+        for (i, score) in lexical {
+            let section = &sections[i];
+            let key = (section.path.clone(), section.start_line);
+            let entry = scores.entry(key).or_insert((section.heading.clone(), 0.0));
+            entry.1 += score * bm25_weight;
+        }
+    }
+
+    if semantic {
+        let Some(index) = vault.load_search_index()? else {
+            eprintln!("No search index found. Run `kbase index` to build it first.");
+            std::process::exit(1);
+        };
+        let weight = 1.0 - blend.unwrap_or(0.0);
+        // Logs are always embedded; leave them out unless they're included
+        let logs = vault.logs_included();
+        for (i, score) in index.search(&terms) {
+            let section = &index.sections[i];
+            if section.log && !logs {
+                continue;
+            }
+            let key = (section.path.clone(), section.start_line);
+            let entry = scores.entry(key).or_insert((section.heading.clone(), 0.0));
+            entry.1 += score * weight;
+        }
+    }
+
+    // Rank notes by their best section
+    let mut best_by_note: HashMap<PathBuf, Hit> = HashMap::new();
+    for ((path, line), (heading, score)) in scores {
+        if score <= 0.0 || domain.as_deref().is_some_and(|d| !path.starts_with(d)) {
+            continue;
+        }
+        let better = best_by_note
+            .get(&path)
+            .is_none_or(|hit| score > hit.score || (score == hit.score && line < hit.line));
+        if better {
+            let title = title_of(&path);
+            best_by_note.insert(
+                path.clone(),
+                Hit {
+                    path,
+                    title,
+                    heading,
+                    line,
+                    score,
+                },
+            );
+        }
+    }
+
+    let mut hits: Vec<Hit> = best_by_note.into_values().collect();
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    hits.truncate(limit);
+
+    if json {
+        let mode = match (semantic, blend) {
+            (false, _) => "bm25",
+            (true, Some(_)) => "blend",
+            (true, None) => "semantic",
+        };
+        let result = serde_json::json!({
+            "query": query,
+            "mode": mode,
+            "total": hits.len(),
+            "results": hits,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if hits.is_empty() {
+        println!("No results.");
+    } else {
+        for hit in &hits {
+            let location = format!("{}:{}", hit.path.display(), hit.line);
+            let label = hit.heading.as_deref().unwrap_or(&hit.title);
+            println!("{:>6.2}  {}  {}", hit.score, location, label);
+        }
+    }
+
+    Ok(())
+}
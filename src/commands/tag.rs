@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+use std::fs;
+
+use anyhow::{Result, bail};
+
+use crate::TagAction;
+use crate::commands::index::refresh_indexes;
+use crate::edit::{ChangeSet, FileChange, TextEdit, apply_edits};
+use crate::parser::{MarkdownParser, TreeSitterParser, frontmatter};
+use crate::tags::{TagNormalizer, is_within};
+use crate::vault::Vault;
+
+pub fn handle_tag(vault: &Vault, action: TagAction) -> Result<()> {
+    match action {
+        TagAction::Rename { old, new, dry_run } => retag(vault, &[old], &new, dry_run, "Renamed"),
+        TagAction::Merge {
+            tags,
+            into,
+            dry_run,
+        } => retag(vault, &tags, &into, dry_run, "Merged"),
+    }
+}
+
+/// List the notes tagged `name` (or a tag nested under it). With `verbose`,
+/// print each line the tag appears on, grouped by note.
+pub fn list_tagged(vault: &Vault, name: String, verbose: bool) -> Result<()> {
+    let Some(tag_index) = vault.load_tag_index()? else {
+        bail!("No tag index found. Run `kbase index` to build it first.");
+    };
+    let tag = &tag_index.normalize(name.trim());
+
+    let notes = tag_index.notes_under_tag(tag);
+    if notes.is_empty() {
+        println!("No notes with tag '{}'.", tag);
+        return Ok(());
+    }
+
+    println!("Notes tagged #{} ({}):", tag, notes.len());
+    if !verbose {
+        for note in &notes {
+            println!("  {}", note);
+        }
+        return Ok(());
+    }
+
+    if !tag_index.has_occurrences() {
+        bail!("Tag index has no line numbers. Run `kbase index` to rebuild it.");
+    }
+    let occurrences = tag_index.occurrences_under_tag(tag);
+    let width = occurrences
+        .values()
+        .flatten()
+        .map(|p| p.line.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    // Notes written after the index have stale line numbers: flag them
+    // instead of printing whatever is on those lines now
+    let indexed_at = fs::metadata(vault.index_dir()?.join("tags.json"))?.modified()?;
+    for (note, positions) in &occurrences {
+        println!();
+        let path = vault.root.join(note);
+        let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) else {
+            println!("{} (missing; run `kbase index`)", note);
+            continue;
+        };
+        if modified > indexed_at {
+            println!("{} (changed since the last index; run `kbase index`)", note);
+            continue;
+        }
+        let content = vault.read_note(note)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        println!("{}", note);
+        // A line with several matching tags is printed once
+        let mut line_numbers: Vec<usize> = positions.iter().map(|p| p.line).collect();
+        line_numbers.dedup();
+        for line in line_numbers {
+            let text = lines.get(line - 1).map_or("", |l| l.trim());
+            println!("  Line {:>width$}: {}", line, text, width = width);
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrite every occurrence of the `sources` tags (and tags nested under them)
+/// to `target`, in note bodies and frontmatter `tags` lists. Tags are compared
+/// after the vault's normalization, so every spelling of a source is rewritten.
+fn retag(vault: &Vault, sources: &[String], target: &str, dry_run: bool, verb: &str) -> Result<()> {
+    let target = target.trim().trim_start_matches('#');
+    if target.is_empty() || target.contains(char::is_whitespace) || target.contains('#') {
+        bail!("Invalid tag '{}'", target);
+    }
+    let normalizer = TagNormalizer::new(&vault.config.tags);
+    let sources: Vec<String> = sources
+        .iter()
+        .map(|s| normalizer.normalize(s.trim().trim_start_matches('#').trim_end_matches('/')))
+        .collect();
+
+    let mut parser = TreeSitterParser::new()?;
+    let mut changes = ChangeSet::new();
+    let mut count = 0;
+
+    for note in vault.all_notes()? {
+        let content = vault.read_note(&note.path.to_string_lossy())?;
+        let parsed = parser.parse(&content)?;
+
+        // Inline tags come from tree-sitter, so ones inside code are never touched
+        let inline = parsed
+            .tag_occurrences
+            .iter()
+            .map(|t| (t.tag.as_str(), t.range.clone()));
+        let frontmatter = frontmatter::value_ranges(&content, &["tags", "tag"]);
+        let frontmatter = frontmatter.iter().map(|(v, r)| (v.as_str(), r.clone()));
+
+        // A tag both sources report (however it got there) is rewritten once
+        let mut seen = HashSet::new();
+        let edits: Vec<TextEdit> = inline
+            .chain(frontmatter)
+            .filter(|(_, range)| seen.insert(range.clone()))
+            .filter_map(|(tag, range)| {
+                let renamed = renamed(tag, &sources, target, &normalizer)?;
+                Some(TextEdit::new(range, renamed))
+            })
+            .collect();
+        if edits.is_empty() {
+            continue;
+        }
+
+        count += edits.len();
+        let updated = apply_edits(&content, &edits)?;
+        changes.push(FileChange::modify(note.path, content, updated));
+    }
+
+    if changes.changes().is_empty() {
+        let tags: Vec<String> = sources.iter().map(|s| format!("#{}", s)).collect();
+        bail!("No notes tagged {}", tags.join(", "));
+    }
+
+    if dry_run {
+        print!("{}", changes.diff());
+        return Ok(());
+    }
+
+    changes.commit(&vault.root)?;
+    refresh_indexes(vault)?;
+
+    let from: Vec<String> = sources.iter().map(|s| format!("#{}", s)).collect();
+    println!(
+        "{} {} → #{}: {} occurrences in {} notes",
+        verb,
+        from.join(", "),
+        target,
+        count,
+        changes.changes().len()
+    );
+
+    Ok(())
+}
+
+/// The new spelling of `tag` if it is one of the (normalized) `sources` or
+/// nested under one, keeping the nested part as written (`lang/Rust` →
+/// `language/Rust`).
+fn renamed(
+    tag: &str,
+    sources: &[String],
+    target: &str,
+    normalizer: &TagNormalizer,
+) -> Option<String> {
+    let canonical = normalizer.normalize(tag);
+    let source = sources.iter().find(|s| is_within(&canonical, s))?;
+    // Take the nested segments from the raw tag so normalization doesn't respell them
+    let depth = canonical[source.len()..].matches('/').count();
+    let child = match depth {
+        0 => "",
+        n => tag
+            .rmatch_indices('/')
+            .nth(n - 1)
+            .map_or("", |(i, _)| &tag[i..]),
+    };
+    let renamed = format!("{}{}", target, child);
+    (renamed != tag).then_some(renamed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tags::TagConfig;
+    use std::collections::BTreeMap;
+
+    fn sources(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_renamed_keeps_nested_part() {
+        let plain = TagNormalizer::default();
+        assert_eq!(
+            renamed("lang/rust", &sources(&["lang"]), "language", &plain).as_deref(),
+            Some("language/rust")
+        );
+        assert_eq!(
+            renamed("WIP", &sources(&["wip", "WIP"]), "wip", &plain).as_deref(),
+            Some("wip")
+        );
+        assert_eq!(
+            renamed("wip", &sources(&["wip", "WIP"]), "wip", &plain),
+            None
+        );
+        assert_eq!(renamed("language", &sources(&["lang"]), "x", &plain), None);
+    }
+
+    #[test]
+    fn test_renamed_matches_normalized_spellings() {
+        let normalizer = TagNormalizer::new(&TagConfig {
+            case_insensitive: true,
+            aliases: BTreeMap::from([("wip".to_string(), vec!["in-progress".to_string()])]),
+            ..Default::default()
+        });
+        let wip = sources(&["wip"]);
+        assert_eq!(
+            renamed("WIP", &wip, "done", &normalizer).as_deref(),
+            Some("done")
+        );
+        assert_eq!(
+            renamed("in-progress/review", &wip, "done", &normalizer).as_deref(),
+            Some("done/review")
+        );
+        assert_eq!(
+            renamed("WIP/Review", &wip, "done", &normalizer).as_deref(),
+            Some("done/Review")
+        );
+        assert_eq!(renamed("lang", &wip, "done", &normalizer), None);
+    }
+}
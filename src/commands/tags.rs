@@ -1,11 +1,37 @@
+use std::collections::BTreeSet;
+
+use crate::tags::{CooccurrenceMatrix, TagIndex, TagNode};
 use crate::{SortBy, output, vault::Vault};
-use anyhow::Result;
+use anyhow::{Result, bail};
+
+/// How `kbase tags` lays out the tags.
+pub enum TagsView {
+    List,
+    Tree,
+    ByDomain,
+    Cooccur(String),
+    CooccurMatrix,
+}
 
-pub fn handle_tags(vault: &Vault, sort: SortBy) -> Result<()> {
-    let Some(tag_index) = vault.load_tag_index()? else {
+pub fn handle_tags(
+    vault: &Vault,
+    sort: SortBy,
+    mut domains: Vec<String>,
+    view: TagsView,
+) -> Result<()> {
+    let mut seen = BTreeSet::new();
+    domains.retain(|d| seen.insert(d.clone()));
+    if let Some(domain) = domains.iter().find(|d| !vault.root.join(d).is_dir()) {
+        bail!("Domain '{}' does not exist", domain);
+    }
+
+    let Some(mut tag_index) = vault.load_tag_index()? else {
         println!("No tag index found. Run `kbase index` to build it first.");
         return Ok(());
     };
+    if !domains.is_empty() {
+        tag_index = tag_index.in_domains(&domains);
+    }
 
     let tags = match sort {
         SortBy::Count => tag_index.all_tags_by_count(),
@@ -17,12 +43,153 @@ pub fn handle_tags(vault: &Vault, sort: SortBy) -> Result<()> {
         return Ok(());
     }
 
+    let rows: Vec<_> = match view {
+        TagsView::ByDomain => {
+            print_domain_matrix(&tag_index, &tags, domains);
+            return Ok(());
+        }
+        TagsView::Cooccur(tag) => {
+            print_cooccurring(&tag_index, &tag_index.normalize(&tag));
+            return Ok(());
+        }
+        TagsView::CooccurMatrix => {
+            print_cooccurrence_csv(&tag_index);
+            return Ok(());
+        }
+        TagsView::Tree => {
+            let mut rows = Vec::new();
+            let by_count = matches!(sort, SortBy::Count);
+            tree_rows(tag_index.tree().roots(), 0, by_count, &mut rows);
+            rows
+        }
+        TagsView::List => {
+            print_list(&tag_index, &tags);
+            return Ok(());
+        }
+    };
+
+    output::print_table(("Tag", "Notes"), &rows);
+
+    Ok(())
+}
+
+/// One row per tag. When the vault's `[tags]` config folded other spellings
+/// into a tag, they're listed alongside it.
+fn print_list(tag_index: &TagIndex, tags: &[(String, usize)]) {
+    let spellings = |tag: &str| {
+        tag_index.spellings(tag).map_or(String::new(), |raw| {
+            raw.iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    };
+
+    if tags
+        .iter()
+        .all(|(tag, _)| tag_index.spellings(tag).is_none())
+    {
+        let rows: Vec<_> = tags
+            .iter()
+            .map(|(tag, count)| (tag.clone(), count.to_string()))
+            .collect();
+        output::print_table(("Tag", "Notes"), &rows);
+        return;
+    }
+
     let rows: Vec<_> = tags
         .iter()
-        .map(|(tag, count)| (tag.clone(), count.to_string()))
+        .map(|(tag, count)| (tag.clone(), count.to_string(), spellings(tag)))
         .collect();
+    output::print_table3(("Tag", "Notes", "Spellings"), &rows);
+}
 
-    output::print_table(("Tag", "Notes"), &rows);
+/// Tags sharing notes with `tag`, most shared first.
+fn print_cooccurring(tag_index: &TagIndex, tag: &str) {
+    if tag_index.notes_with_tag(tag).is_empty() {
+        println!("No notes with tag '{}'.", tag);
+        return;
+    }
 
-    Ok(())
+    let found = CooccurrenceMatrix::build(tag_index).with_tag(tag);
+    if found.is_empty() {
+        println!("No other tags are used with '{}'.", tag);
+        return;
+    }
+
+    let rows: Vec<Vec<String>> = found
+        .iter()
+        .map(|c| {
+            vec![
+                c.other.clone(),
+                c.notes.to_string(),
+                format!("{:.2}", c.lift),
+                format!("{:.2}", c.pmi),
+            ]
+        })
+        .collect();
+    output::print_table_n(&["Tag", "Notes", "Lift", "PMI"], &rows);
+}
+
+/// Every co-occurring pair as CSV, for spreadsheets and other tools.
+fn print_cooccurrence_csv(tag_index: &TagIndex) {
+    println!("tag,other,notes,lift,pmi");
+    for c in CooccurrenceMatrix::build(tag_index).pairs() {
+        println!(
+            "{},{},{},{:.4},{:.4}",
+            c.tag, c.other, c.notes, c.lift, c.pmi
+        );
+    }
+}
+
+/// Rows for a level of the tag tree, children indented under their parent.
+/// Counts include notes tagged anywhere below.
+fn tree_rows<'a>(
+    nodes: impl Iterator<Item = &'a TagNode>,
+    depth: usize,
+    by_count: bool,
+    rows: &mut Vec<(String, String)>,
+) {
+    let mut counted: Vec<(&TagNode, usize)> = nodes.map(|n| (n, n.all_notes().len())).collect();
+    if by_count {
+        counted.sort_by(|(_, a), (_, b)| b.cmp(a)); // Descending count, stable by name
+    }
+
+    for (node, count) in counted {
+        rows.push((
+            format!("{}{}", "  ".repeat(depth), node.name()),
+            count.to_string(),
+        ));
+        tree_rows(node.children.values(), depth + 1, by_count, rows);
+    }
+}
+
+/// One row per tag, one column per domain, cells counting the tag's notes in
+/// that domain. Columns are the requested domains, or every domain with tags.
+fn print_domain_matrix(tag_index: &TagIndex, tags: &[(String, usize)], domains: Vec<String>) {
+    let counts = tag_index.counts_by_domain();
+    let columns: Vec<String> = if domains.is_empty() {
+        let all: BTreeSet<&String> = counts.values().flat_map(|c| c.keys()).collect();
+        all.into_iter().cloned().collect()
+    } else {
+        domains
+    };
+
+    // Tags only used at the vault root have no domain to show
+    let rows: Vec<Vec<String>> = tags
+        .iter()
+        .filter_map(|(tag, _)| {
+            let by_domain = counts.get(tag)?;
+            let cells = columns.iter().map(|domain| {
+                by_domain
+                    .get(domain)
+                    .map_or("-".to_string(), |n| n.to_string())
+            });
+            Some(std::iter::once(tag.clone()).chain(cells).collect())
+        })
+        .collect();
+
+    let mut headers = vec!["Tag"];
+    headers.extend(columns.iter().map(String::as_str));
+    output::print_table_n(&headers, &rows);
 }
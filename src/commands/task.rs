@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+
+use crate::TaskAction;
+use crate::commands::index::refresh_indexes;
+use crate::edit::{ChangeSet, FileChange, TextEdit, apply_edits, line_range};
+use crate::parser::{Heading, MarkdownParser, TaskStatus, TreeSitterParser};
+use crate::tasks::TaskIndex;
+use crate::vault::Vault;
+
+pub fn handle_task(vault: &Vault, action: TaskAction) -> Result<()> {
+    match action {
+        TaskAction::Done { location } => mark_done(vault, &location),
+        TaskAction::Add { note, text, under } => add_task(vault, &note, &text, under.as_deref()),
+    }
+}
+
+/// Mark the task at `<note>:<line>` done by rewriting its `[ ]` marker.
+fn mark_done(vault: &Vault, location: &str) -> Result<()> {
+    let (note, line) = parse_location(location)?;
+    let note = vault.resolve_note(&note)?.to_string_lossy().into_owned();
+    let index = load_task_index(vault)?;
+
+    let Some(indexed) = index.get(&note).iter().find(|t| t.line == line).cloned() else {
+        bail!("No task at {}:{} in the task index", note, line);
+    };
+
+    let content = vault.read_note(&note)?;
+    let mut parser = TreeSitterParser::new()?;
+    let current = parser.parse(&content)?.tasks;
+    let unchanged = current
+        .iter()
+        .any(|t| t.line == line && t.status == indexed.status && t.text == indexed.text);
+    if !unchanged {
+        bail!(
+            "{}:{} changed since the last index. Run `kbase index` and check the task again.",
+            note,
+            line
+        );
+    }
+
+    if indexed.status == TaskStatus::Done {
+        println!("Already done: {}:{}  {}", note, line, indexed.text);
+        return Ok(());
+    }
+
+    // The first `[` on a task line is its status bracket: quote and list
+    // markers come before it, the task text after it.
+    let range = line_range(&content, line)
+        .with_context(|| format!("line {} is out of range in {}", line, note))?;
+    let bracket = content[range.clone()]
+        .find('[')
+        .with_context(|| format!("no task marker on {}:{}", note, line))?;
+    let start = range.start + bracket + 1;
+    let marker_len = content[start..].chars().next().map_or(0, char::len_utf8);
+    let updated = apply_edits(&content, &[TextEdit::new(start..start + marker_len, "x")])?;
+
+    write_note(vault, &note, content, updated)?;
+    println!("Marked done: {}:{}  {}", note, line, indexed.text);
+
+    Ok(())
+}
+
+/// Append `- [ ] text` to a heading's section, or to the end of the note.
+fn add_task(vault: &Vault, note: &str, text: &str, under: Option<&str>) -> Result<()> {
+    let text = text.trim();
+    if text.is_empty() {
+        bail!("Task text cannot be empty");
+    }
+
+    let note = &vault.resolve_note(note)?.to_string_lossy().into_owned();
+    let index = load_task_index(vault)?;
+    let content = vault.read_note(note)?;
+    let mut parser = TreeSitterParser::new()?;
+    let parsed = parser.parse(&content)?;
+    if parsed.tasks != index.get(note) {
+        bail!(
+            "{} changed since the last index. Run `kbase index` and try again.",
+            note
+        );
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let (section_start, section_end) = match under {
+        Some(heading) => {
+            let heading = find_heading(&parsed.headings, note, heading)?;
+            let end = parsed
+                .headings
+                .iter()
+                .find(|h| h.line > heading.line)
+                .map_or(lines.len(), |h| h.line - 1);
+            (heading.line, end)
+        }
+        None => (0, lines.len()),
+    };
+
+    // Insert after the last non-blank line of the section
+    let after = (section_start + 1..=section_end)
+        .rev()
+        .find(|&n| !lines[n - 1].trim().is_empty())
+        .unwrap_or(section_start);
+
+    let task_line = format!("- [ ] {}", text);
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let (offset, insertion, new_line) = if after == 0 {
+        (0, format!("{}{}", task_line, eol), 1)
+    } else {
+        let end = line_range(&content, after).map_or(content.len(), |r| r.end);
+        if after == section_start {
+            // Empty section: leave a blank line under the heading
+            (end, format!("{eol}{eol}{}", task_line), after + 2)
+        } else {
+            (end, format!("{eol}{}", task_line), after + 1)
+        }
+    };
+    let updated = apply_edits(&content, &[TextEdit::new(offset..offset, insertion)])?;
+
+    write_note(vault, note, content, updated)?;
+    println!("Added task to {}:{}  {}", note, new_line, text);
+
+    Ok(())
+}
+
+/// Find a heading by text, with optional `#` markers pinning its level.
+fn find_heading<'a>(headings: &'a [Heading], note: &str, query: &str) -> Result<&'a Heading> {
+    let trimmed = query.trim();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let text = trimmed[level..].trim();
+
+    let matches: Vec<&Heading> = headings
+        .iter()
+        .filter(|h| h.text.eq_ignore_ascii_case(text) && (level == 0 || h.level as usize == level))
+        .collect();
+    match matches.as_slice() {
+        [] => bail!("heading not found in {}: {}", note, query),
+        [heading] => Ok(heading),
+        many => {
+            let lines: Vec<String> = many.iter().map(|h| h.line.to_string()).collect();
+            bail!(
+                "heading '{}' appears {} times in {} (lines {})",
+                query,
+                many.len(),
+                note,
+                lines.join(", ")
+            )
+        }
+    }
+}
+
+/// Split `<note>:<line>`.
+fn parse_location(location: &str) -> Result<(String, usize)> {
+    let parsed = location
+        .rsplit_once(':')
+        .and_then(|(note, line)| Some((note.to_string(), line.parse().ok()?)))
+        .filter(|(note, line)| !note.is_empty() && *line > 0);
+
+    parsed.with_context(|| {
+        format!(
+            "Invalid task location '{}': expected <note>:<line>",
+            location
+        )
+    })
+}
+
+fn load_task_index(vault: &Vault) -> Result<TaskIndex> {
+    let Some(index) = vault.load_task_index()? else {
+        eprintln!("No task index found. Run `kbase index` to build it first.");
+        std::process::exit(1);
+    };
+    Ok(index)
+}
+
+/// Write the edited note, then rebuild the existing indexes: a task edit can
+/// add tags and fields, and the next edit's staleness check compares against
+/// the task index.
+fn write_note(vault: &Vault, note: &str, original: String, updated: String) -> Result<()> {
+    let mut changes = ChangeSet::new();
+    changes.push(FileChange::modify(PathBuf::from(note), original, updated));
+    changes.commit(&vault.root)?;
+    refresh_indexes(vault)
+}
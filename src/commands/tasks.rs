@@ -0,0 +1,193 @@
+use crate::{
+    DueFilter, PriorityArg, TaskSort,
+    parser::{Priority, Task, TaskStatus},
+    vault::Vault,
+};
+use anyhow::{Result, bail};
+use chrono::{Datelike, Duration, Local, NaiveDate};
+
+impl From<PriorityArg> for Priority {
+    fn from(arg: PriorityArg) -> Self {
+        match arg {
+            PriorityArg::Highest => Priority::Highest,
+            PriorityArg::High => Priority::High,
+            PriorityArg::Medium => Priority::Medium,
+            PriorityArg::Low => Priority::Low,
+            PriorityArg::Lowest => Priority::Lowest,
+        }
+    }
+}
+
+/// Which tasks to list.
+pub struct TaskFilter {
+    pub done: bool,
+    pub all: bool,
+    pub domain: Option<String>,
+    pub file: Option<String>,
+    pub due: Option<DueFilter>,
+    pub priority: Option<Priority>,
+}
+
+impl TaskFilter {
+    fn matches(&self, path: &str, task: &Task, today: NaiveDate) -> bool {
+        let status_ok = if self.all {
+            true
+        } else if self.done {
+            task.status == TaskStatus::Done
+        } else {
+            task.status.is_open()
+        };
+
+        let domain_ok = self
+            .domain
+            .as_deref()
+            .is_none_or(|d| path.split_once('/').is_some_and(|(domain, _)| domain == d));
+        let file_ok = self.file.as_deref().is_none_or(|f| path == f);
+        let due_ok = self.due.is_none_or(|due| due_matches(due, task.due, today));
+        let priority_ok = self
+            .priority
+            .is_none_or(|p| Priority::rank(task.priority) <= Priority::rank(Some(p)));
+
+        status_ok && domain_ok && file_ok && due_ok && priority_ok
+    }
+
+    fn describe(&self) -> &'static str {
+        if self.all {
+            ""
+        } else if self.done {
+            "done "
+        } else {
+            "open "
+        }
+    }
+}
+
+/// Whether a due date falls in the requested range. Undated tasks never match.
+fn due_matches(filter: DueFilter, due: Option<NaiveDate>, today: NaiveDate) -> bool {
+    let Some(due) = due else {
+        return false;
+    };
+
+    match filter {
+        DueFilter::Today => due == today,
+        DueFilter::Overdue => due < today,
+        DueFilter::ThisWeek => {
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            monday <= due && due <= monday + Duration::days(6)
+        }
+    }
+}
+
+pub fn handle_tasks(
+    vault: &Vault,
+    mut filter: TaskFilter,
+    sort: TaskSort,
+    verbose: bool,
+    total: bool,
+) -> Result<()> {
+    if let Some(domain) = &filter.domain
+        && !vault.root.join(domain).is_dir()
+    {
+        bail!("Domain '{}' does not exist", domain);
+    }
+    if let Some(file) = &filter.file {
+        filter.file = Some(vault.resolve_note(file)?.to_string_lossy().into_owned());
+    }
+
+    let Some(task_index) = vault.load_task_index()? else {
+        eprintln!("No task index found. Run `kbase index` to build it first.");
+        std::process::exit(1);
+    };
+
+    let today = Local::now().date_naive();
+    let mut tasks: Vec<(&str, &Task)> = task_index
+        .iter()
+        .filter(|(path, task)| filter.matches(path, task, today))
+        .collect();
+
+    // Stable sorts keep path/line order among ties
+    match sort {
+        TaskSort::Path => {}
+        TaskSort::Due => tasks.sort_by_key(|(_, task)| (task.due.is_none(), task.due)),
+        TaskSort::Priority => tasks.sort_by_key(|(_, task)| Priority::rank(task.priority)),
+    }
+    if verbose {
+        // Grouped output: keep notes together, sorted within each note
+        tasks.sort_by_key(|(path, _)| *path);
+    }
+
+    if total {
+        println!("{}", tasks.len());
+        return Ok(());
+    }
+
+    if tasks.is_empty() {
+        println!("No {}tasks found.", filter.describe());
+        return Ok(());
+    }
+
+    if verbose {
+        print_grouped(&tasks);
+    } else {
+        print_flat(&tasks);
+    }
+
+    Ok(())
+}
+
+/// One task per line: `path:line  [ ] text`.
+fn print_flat(tasks: &[(&str, &Task)]) {
+    let locations: Vec<String> = tasks
+        .iter()
+        .map(|(path, task)| format!("{}:{}", path, task.line))
+        .collect();
+    let width = locations.iter().map(String::len).max().unwrap_or(0);
+
+    for (location, (_, task)) in locations.iter().zip(tasks) {
+        println!(
+            "{:<width$}  [{}] {}",
+            location,
+            task.status.marker(),
+            task.text,
+            width = width
+        );
+    }
+}
+
+/// Tasks grouped under their note and heading, indented by nesting.
+fn print_grouped(tasks: &[(&str, &Task)]) {
+    let width = tasks
+        .iter()
+        .map(|(_, task)| task.line.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut current_path = None;
+    let mut current_heading = None;
+    for (path, task) in tasks {
+        if current_path != Some(*path) {
+            if current_path.is_some() {
+                println!();
+            }
+            println!("{}", path);
+            current_path = Some(*path);
+            current_heading = None;
+        }
+
+        if current_heading != Some(&task.heading) {
+            if let Some(heading) = &task.heading {
+                println!("  {}", heading);
+            }
+            current_heading = Some(&task.heading);
+        }
+
+        println!(
+            "    {:>width$}  {}[{}] {}",
+            task.line,
+            "  ".repeat(task.depth),
+            task.status.marker(),
+            task.text,
+            width = width
+        );
+    }
+}
@@ -1,11 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 
+use crate::lint::RuleLevel;
 use crate::output;
+use crate::tags::TagConfig;
 
 const DEFAULT_KBASE_HOME: &str = ".kbase";
 const CONFIG_FILE: &str = "config.toml";
@@ -17,9 +19,26 @@ pub struct Config {
     pub vaults: HashMap<String, VaultConfig>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct VaultConfig {
     pub path: PathBuf,
+
+    /// Gitignore-style patterns to leave out of the vault, on top of
+    /// `.kbaseignore` and the built-in `_`/`.` rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+
+    /// Patterns to bring back in when an earlier rule left them out (e.g. `_logs`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Per-rule severity overrides for `kbase lint` (`[vaults.<name>.lint]`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lint: BTreeMap<String, RuleLevel>,
+
+    /// Tag aliases and normalization (`[vaults.<name>.tags]`)
+    #[serde(default, skip_serializing_if = "TagConfig::is_default")]
+    pub tags: TagConfig,
 }
 
 impl Config {
@@ -0,0 +1,233 @@
+//! Assemble a token-budgeted bundle of vault content around a note or query.
+//!
+//! Candidates are the sections of the seed note, of the notes it links to and
+//! of the notes linking to it, plus any section matching the search terms.
+//! Each is scored by link distance and BM25 relevance, then the best ones are
+//! packed greedily until the budget is spent. Pieces end on heading
+//! boundaries, except that a seed section too large for the rest of the
+//! budget is cut after its last line that fits rather than dropped.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::links::LinkIndex;
+use crate::links::resolve::NoteMatch;
+use crate::search::sections::vault_sections;
+use crate::search::{Bm25, tokenize};
+use crate::vault::Vault;
+
+/// How a piece's note relates to the seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Source {
+    /// The seed note itself
+    Seed,
+    /// Linked from the seed
+    LinksTo,
+    /// Links to the seed
+    LinkedFrom,
+    /// Matched the search terms only
+    Search,
+}
+
+impl Source {
+    pub fn label(self) -> &'static str {
+        match self {
+            Source::Seed => "seed",
+            Source::LinksTo => "links-to",
+            Source::LinkedFrom => "linked-from",
+            Source::Search => "search",
+        }
+    }
+
+    /// Score contribution from link distance.
+    fn proximity(self) -> f64 {
+        match self {
+            Source::Seed => 1.0,
+            Source::LinksTo | Source::LinkedFrom => 0.5,
+            Source::Search => 0.0,
+        }
+    }
+}
+
+/// One section included in the bundle.
+#[derive(Debug, Clone, Serialize)]
+pub struct Piece {
+    pub path: PathBuf,
+    pub title: String,
+    /// Heading the section starts with, None for text before the first heading
+    pub heading: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub source: Source,
+    pub score: f64,
+    pub tokens: usize,
+    /// Cut short to fit the budget (seed sections only)
+    pub truncated: bool,
+    pub content: String,
+}
+
+/// The packed context, pieces grouped by note in rank order.
+#[derive(Debug, Clone, Serialize)]
+pub struct Bundle {
+    pub seed: PathBuf,
+    /// Search terms used to find matching sections
+    pub query: String,
+    pub max_tokens: usize,
+    pub tokens: usize,
+    /// Candidate sections left out to stay within the budget
+    pub omitted: usize,
+    pub pieces: Vec<Piece>,
+}
+
+/// Rough token count: about four characters per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Build a context bundle for `target`: a vault-relative note path, or else
+/// free-text search terms whose best-matching note becomes the seed.
+pub fn build(
+    vault: &Vault,
+    link_index: &LinkIndex,
+    target: &str,
+    max_tokens: usize,
+) -> Result<Bundle> {
+    let notes = vault.all_notes()?;
+    let candidates: Vec<Piece> = vault_sections(vault)?
+        .into_iter()
+        .map(|section| Piece {
+            tokens: estimate_tokens(&section.text),
+            path: section.path,
+            title: section.title,
+            heading: section.heading,
+            start_line: section.start_line,
+            end_line: section.end_line,
+            source: Source::Search,
+            score: 0.0,
+            truncated: false,
+            content: section.text,
+        })
+        .collect();
+
+    let index = Bm25::new(candidates.iter().map(|p| tokenize(&p.content)));
+
+    // Seed: the named note, or the note with the best-matching section. Only
+    // a path or short name picks the seed; an ambiguous name or a fragment of
+    // a path is searched like any other words.
+    let named = match vault.find_note_exact(target)? {
+        NoteMatch::Found(path) => Some(path),
+        _ if target.ends_with(".md") => Some(vault.resolve_note(target)?),
+        _ => None,
+    };
+    let (seed, query) = match named {
+        Some(path) => {
+            let title = notes
+                .iter()
+                .find(|n| n.path == path)
+                .map(|n| n.title.clone());
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let query = title.unwrap_or_else(|| stem.into_owned());
+            (path, query)
+        }
+        None => {
+            let Some(&(best, _)) = index.search(&tokenize(target)).first() else {
+                bail!("No notes match '{}'", target);
+            };
+            (candidates[best].path.clone(), target.to_string())
+        }
+    };
+
+    let mut sources: HashMap<PathBuf, Source> = HashMap::new();
+    for path in link_index.get_backward(&seed).unwrap_or_default() {
+        sources.insert(path.clone(), Source::LinkedFrom);
+    }
+    for path in link_index.get_forward(&seed).unwrap_or_default() {
+        sources.insert(path.clone(), Source::LinksTo);
+    }
+    sources.insert(seed.clone(), Source::Seed);
+
+    let hits = index.search(&tokenize(&query));
+    let best = hits.first().map_or(0.0, |&(_, score)| score);
+    let relevance: HashMap<usize, f64> = hits.into_iter().map(|(i, s)| (i, s / best)).collect();
+
+    let mut ranked: Vec<Piece> = candidates
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, mut piece)| {
+            piece.source = sources.get(&piece.path).copied().unwrap_or(Source::Search);
+            piece.score = piece.source.proximity() + relevance.get(&i).copied().unwrap_or(0.0);
+            (piece.score > 0.0).then_some(piece)
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+            .then(a.start_line.cmp(&b.start_line))
+    });
+
+    // Greedy packing: take each section that still fits, trimming the
+    // seed's own sections rather than leaving them out
+    let mut tokens = 0;
+    let mut packed = Vec::new();
+    let mut omitted = 0;
+    for mut piece in ranked {
+        let fits = tokens + piece.tokens <= max_tokens
+            || (piece.source == Source::Seed && trim_to_fit(&mut piece, max_tokens - tokens));
+        if fits {
+            tokens += piece.tokens;
+            packed.push(piece);
+        } else {
+            omitted += 1;
+        }
+    }
+
+    // Group by note in rank order, sections in document order within a note
+    let mut note_rank: HashMap<PathBuf, usize> = HashMap::new();
+    for piece in &packed {
+        let next = note_rank.len();
+        note_rank.entry(piece.path.clone()).or_insert(next);
+    }
+    packed.sort_by_key(|p| (note_rank[&p.path], p.start_line));
+
+    Ok(Bundle {
+        seed,
+        query,
+        max_tokens,
+        tokens,
+        omitted,
+        pieces: packed,
+    })
+}
+
+/// Keep the leading lines of `piece` that fit in `budget` tokens. False if
+/// not even its first line fits.
+fn trim_to_fit(piece: &mut Piece, budget: usize) -> bool {
+    let mut kept = 0;
+    let mut end = 0;
+    for (i, line) in piece.content.split('\n').enumerate() {
+        let len = if i == 0 {
+            line.len()
+        } else {
+            end + 1 + line.len()
+        };
+        if estimate_tokens(&piece.content[..len]) > budget {
+            break;
+        }
+        end = len;
+        kept += 1;
+    }
+    if kept == 0 {
+        return false;
+    }
+
+    piece.content.truncate(end);
+    piece.end_line = piece.start_line + kept - 1;
+    piece.tokens = estimate_tokens(&piece.content);
+    piece.truncated = true;
+    true
+}
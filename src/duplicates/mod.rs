@@ -0,0 +1,296 @@
+//! Near-duplicate detection with shingling and MinHash.
+//!
+//! Each text is cut into overlapping word 5-grams ("shingles"). Two texts are
+//! as similar as the Jaccard overlap of their shingle sets. MinHash signatures
+//! with LSH banding pick candidate pairs without comparing everything with
+//! everything; candidates are then checked against the exact Jaccard score.
+//! Shared shingles also tell us which lines overlap.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::parser::frontmatter;
+use crate::search::hash::{fnv1a, splitmix64};
+use crate::search::sections::vault_sections;
+use crate::vault::Vault;
+
+/// Words per shingle.
+const SHINGLE_WORDS: usize = 5;
+/// Texts with fewer words than this are too short to compare.
+const MIN_WORDS: usize = 10;
+/// MinHash signature length.
+const HASHES: usize = 128;
+
+/// A note body or section to compare.
+#[derive(Debug, Clone)]
+pub struct Unit {
+    pub path: PathBuf,
+    pub heading: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+}
+
+/// One side of a duplicate pair.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Side {
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Line ranges sharing text with the other side
+    pub overlap: Vec<(usize, usize)>,
+}
+
+/// Two units whose similarity reaches the threshold.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Pair {
+    pub similarity: f64,
+    pub a: Side,
+    pub b: Side,
+}
+
+/// Shingle hashes of a unit, each with the line spans it occurs at.
+type Shingles = HashMap<u64, Vec<(usize, usize)>>;
+
+/// Whole note bodies (without frontmatter), optionally limited to a domain.
+pub fn note_units(vault: &Vault, domain: Option<&str>) -> Result<Vec<Unit>> {
+    let mut units = Vec::new();
+    for note in vault.all_notes()? {
+        if domain.is_some_and(|d| !note.path.starts_with(d)) {
+            continue;
+        }
+        let content = vault.read_note(&note.path.to_string_lossy())?;
+        let skip = frontmatter::split(&content).map_or(0, |(_, n)| n);
+        let lines: Vec<&str> = content.lines().skip(skip).collect();
+        units.push(Unit {
+            path: note.path,
+            heading: None,
+            start_line: skip + 1,
+            end_line: skip + lines.len(),
+            text: lines.join("\n"),
+        });
+    }
+    Ok(units)
+}
+
+/// Heading-bounded sections, optionally limited to a domain.
+pub fn section_units(vault: &Vault, domain: Option<&str>) -> Result<Vec<Unit>> {
+    Ok(vault_sections(vault)?
+        .into_iter()
+        .filter(|s| domain.is_none_or(|d| s.path.starts_with(d)))
+        .map(|s| Unit {
+            path: s.path,
+            heading: s.heading,
+            start_line: s.start_line,
+            end_line: s.end_line,
+            text: s.text,
+        })
+        .collect())
+}
+
+/// Pairs of units from different notes with shingle Jaccard similarity at
+/// or above `threshold`, most similar first.
+pub fn find_pairs(units: &[Unit], threshold: f64) -> Vec<Pair> {
+    let shingles: Vec<Option<Shingles>> = units.iter().map(shingle).collect();
+    let signatures: Vec<Option<Vec<u64>>> =
+        shingles.iter().map(|s| s.as_ref().map(signature)).collect();
+
+    let mut pairs: Vec<Pair> = candidates(&signatures, threshold)
+        .into_iter()
+        .filter(|&(i, j)| units[i].path != units[j].path)
+        .filter_map(|(i, j)| {
+            let (a, b) = (shingles[i].as_ref()?, shingles[j].as_ref()?);
+            let shared = a.keys().filter(|k| b.contains_key(k)).count();
+            let similarity = shared as f64 / (a.len() + b.len() - shared) as f64;
+            if similarity < threshold {
+                return None;
+            }
+            Some(Pair {
+                similarity,
+                a: side(&units[i], a, b),
+                b: side(&units[j], b, a),
+            })
+        })
+        .collect();
+
+    pairs.sort_by(|x, y| {
+        y.similarity
+            .total_cmp(&x.similarity)
+            .then_with(|| (&x.a.path, x.a.start_line).cmp(&(&y.a.path, y.a.start_line)))
+            .then_with(|| (&x.b.path, x.b.start_line).cmp(&(&y.b.path, y.b.start_line)))
+    });
+    pairs
+}
+
+/// Lowercased words with their line numbers.
+fn words(unit: &Unit) -> Vec<(String, usize)> {
+    unit.text
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(move |w| (w.to_lowercase(), unit.start_line + i))
+        })
+        .collect()
+}
+
+fn shingle(unit: &Unit) -> Option<Shingles> {
+    let words = words(unit);
+    if words.len() < MIN_WORDS {
+        return None;
+    }
+
+    let mut shingles: Shingles = HashMap::new();
+    for window in words.windows(SHINGLE_WORDS) {
+        let text: Vec<&str> = window.iter().map(|(w, _)| w.as_str()).collect();
+        let span = (window[0].1, window[SHINGLE_WORDS - 1].1);
+        shingles
+            .entry(fnv1a(&text.join(" ")))
+            .or_default()
+            .push(span);
+    }
+    Some(shingles)
+}
+
+/// MinHash signature: the minimum of each of `HASHES` hash functions.
+fn signature(shingles: &Shingles) -> Vec<u64> {
+    (0..HASHES as u64)
+        .map(|seed| {
+            let salt = splitmix64(seed);
+            shingles
+                .keys()
+                .map(|&h| splitmix64(h ^ salt))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+/// Rows per LSH band: the largest that still makes pairs at `threshold`
+/// likely candidates. Fewer rows means more bands and more candidates.
+fn rows_per_band(threshold: f64) -> usize {
+    [8, 4, 2, 1]
+        .into_iter()
+        .find(|&rows| {
+            let bands = (HASHES / rows) as f64;
+            (1.0 / bands).powf(1.0 / rows as f64) <= threshold * 0.8
+        })
+        .unwrap_or(1)
+}
+
+/// Index pairs sharing at least one identical signature band.
+fn candidates(signatures: &[Option<Vec<u64>>], threshold: f64) -> BTreeSet<(usize, usize)> {
+    let rows = rows_per_band(threshold);
+    let mut pairs = BTreeSet::new();
+
+    for band in 0..HASHES / rows {
+        let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+        for (i, signature) in signatures.iter().enumerate() {
+            if let Some(signature) = signature {
+                let key = &signature[band * rows..(band + 1) * rows];
+                buckets.entry(key).or_default().push(i);
+            }
+        }
+        for members in buckets.values() {
+            for (n, &i) in members.iter().enumerate() {
+                for &j in &members[n + 1..] {
+                    pairs.insert((i, j));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Describe `unit`, with the line ranges covered by shingles it shares with `other`.
+fn side(unit: &Unit, own: &Shingles, other: &Shingles) -> Side {
+    let mut spans: Vec<(usize, usize)> = own
+        .iter()
+        .filter(|(hash, _)| other.contains_key(hash))
+        .flat_map(|(_, spans)| spans.iter().copied())
+        .collect();
+    spans.sort();
+
+    let mut overlap: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in spans {
+        match overlap.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => overlap.push((start, end)),
+        }
+    }
+
+    Side {
+        path: unit.path.clone(),
+        heading: unit.heading.clone(),
+        start_line: unit.start_line,
+        end_line: unit.end_line,
+        overlap,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COPIED: &str = "Segments are merged in the background by the merge policy\nwhich picks segments of similar size to keep the index fast";
+
+    fn unit(path: &str, start_line: usize, text: &str) -> Unit {
+        Unit {
+            path: PathBuf::from(path),
+            heading: None,
+            start_line,
+            end_line: start_line + text.lines().count() - 1,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_finds_copied_text_with_line_ranges() {
+        let units = [
+            unit(
+                "a.md",
+                1,
+                &format!("# A\nIntro for note a only\n{}", COPIED),
+            ),
+            unit("b.md", 5, COPIED),
+            unit(
+                "c.md",
+                1,
+                "Ownership rules in Rust move values between bindings\nand the borrow checker enforces lifetimes",
+            ),
+        ];
+
+        let pairs = find_pairs(&units, 0.5);
+        assert_eq!(pairs.len(), 1);
+        let pair = &pairs[0];
+        assert_eq!(pair.a.path, PathBuf::from("a.md"));
+        assert_eq!(pair.a.overlap, vec![(3, 4)]);
+        assert_eq!(pair.b.overlap, vec![(5, 6)]);
+        assert!(pair.similarity > 0.7 && pair.similarity < 1.0);
+    }
+
+    #[test]
+    fn test_same_note_and_short_units_are_skipped() {
+        let units = [
+            unit("a.md", 1, COPIED),
+            unit("a.md", 10, COPIED),
+            unit("b.md", 1, "too short to compare"),
+            unit("c.md", 1, "too short to compare"),
+        ];
+        assert!(find_pairs(&units, 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_rows_per_band_adapts_to_threshold() {
+        assert_eq!(rows_per_band(0.9), 8);
+        assert_eq!(rows_per_band(0.7), 4);
+        assert_eq!(rows_per_band(0.5), 2);
+        assert_eq!(rows_per_band(0.1), 1);
+    }
+}
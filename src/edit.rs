@@ -0,0 +1,400 @@
+//! Vault rewrites: byte-range edits, diff previews, and atomic writes.
+//!
+//! Commands that modify notes (`kbase mv`, `kbase rename-heading`, ...) first
+//! compute every change in memory as a [`ChangeSet`], then either print it as a
+//! unified diff (`--dry-run`) or commit it to disk in one go.
+
+use anyhow::{Context, Result, bail};
+use similar::TextDiff;
+use std::fs::{self, File};
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Replace a byte range of a note with new text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, replacement: impl Into<String>) -> Self {
+        Self {
+            range,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// Apply non-overlapping edits to `content`. Edits may be given in any order.
+pub fn apply_edits(content: &str, edits: &[TextEdit]) -> Result<String> {
+    let mut sorted: Vec<&TextEdit> = edits.iter().collect();
+    sorted.sort_by_key(|e| e.range.start);
+
+    let mut result = String::with_capacity(content.len());
+    let mut cursor = 0;
+    for edit in sorted {
+        if edit.range.start < cursor || edit.range.end > content.len() {
+            bail!(
+                "Overlapping or out-of-bounds edit at byte {}",
+                edit.range.start
+            );
+        }
+        result.push_str(&content[cursor..edit.range.start]);
+        result.push_str(&edit.replacement);
+        cursor = edit.range.end;
+    }
+    result.push_str(&content[cursor..]);
+
+    Ok(result)
+}
+
+/// Convert a 1-indexed line and 0-indexed byte column (tree-sitter positions)
+/// into a byte offset into `content`.
+pub fn byte_offset(content: &str, line: usize, column: usize) -> Option<usize> {
+    let line_start = line_start_offset(content, line)?;
+    let offset = line_start + column;
+    (offset <= content.len()).then_some(offset)
+}
+
+/// Byte range of a 1-indexed line, excluding its line ending.
+pub fn line_range(content: &str, line: usize) -> Option<Range<usize>> {
+    let start = line_start_offset(content, line)?;
+    let mut end = content[start..]
+        .find('\n')
+        .map_or(content.len(), |i| start + i);
+    if end > start && content.as_bytes()[end - 1] == b'\r' {
+        end -= 1;
+    }
+    Some(start..end)
+}
+
+fn line_start_offset(content: &str, line: usize) -> Option<usize> {
+    if line == 0 {
+        return None;
+    }
+    if line == 1 {
+        return Some(0);
+    }
+    content
+        .match_indices('\n')
+        .nth(line - 2)
+        .map(|(i, _)| i + 1)
+        .filter(|&i| i <= content.len())
+}
+
+/// A single file rewrite, optionally combined with a move.
+/// Paths are vault-relative.
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub original: String,
+    pub updated: String,
+}
+
+impl FileChange {
+    /// Rewrite a file in place.
+    pub fn modify(path: PathBuf, original: String, updated: String) -> Self {
+        Self {
+            from: path.clone(),
+            to: path,
+            original,
+            updated,
+        }
+    }
+
+    pub fn is_move(&self) -> bool {
+        self.from != self.to
+    }
+
+    pub fn is_noop(&self) -> bool {
+        !self.is_move() && self.original == self.updated
+    }
+}
+
+/// A set of file changes that are previewed or applied together.
+#[derive(Debug, Default)]
+pub struct ChangeSet {
+    changes: Vec<FileChange>,
+}
+
+impl ChangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a change, ignoring ones that would leave the file untouched.
+    pub fn push(&mut self, change: FileChange) {
+        if !change.is_noop() {
+            self.changes.push(change);
+        }
+    }
+
+    pub fn changes(&self) -> &[FileChange] {
+        &self.changes
+    }
+
+    /// Render all changes as a git-style unified diff.
+    pub fn diff(&self) -> String {
+        let mut out = String::new();
+        for change in &self.changes {
+            out.push_str(&unified_diff(change));
+        }
+        out
+    }
+
+    /// Write all changes under `root`.
+    ///
+    /// Every new file body is written to a temp file first, then the temp
+    /// files are renamed into place. If staging or a rename fails, temp files
+    /// are removed and files already replaced get their original content
+    /// back. Moved notes' old files are only removed once everything else is
+    /// written; if that last step fails, the old file is left next to the new
+    /// one.
+    pub fn commit(&self, root: &Path) -> Result<()> {
+        let mut staged = Vec::with_capacity(self.changes.len());
+        for change in &self.changes {
+            match stage(root, change) {
+                Ok(temp) => staged.push(temp),
+                Err(err) => {
+                    remove_temps(&staged);
+                    return Err(err);
+                }
+            }
+        }
+
+        for (i, (change, temp)) in self.changes.iter().zip(&staged).enumerate() {
+            let target = root.join(&change.to);
+            if let Err(err) = fs::rename(temp, &target) {
+                self.roll_back(root, &self.changes[..i]);
+                remove_temps(&staged[i..]);
+                return Err(err).with_context(|| format!("Could not write {}", target.display()));
+            }
+        }
+
+        for change in self.changes.iter().filter(|c| c.is_move()) {
+            fs::remove_file(root.join(&change.from))
+                .with_context(|| format!("Could not remove {}", change.from.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Undo changes already renamed into place: drop moved files' new copies
+    /// (their old files are still there) and restore rewritten files.
+    /// Best effort, since this only runs while handling another error.
+    fn roll_back(&self, root: &Path, written: &[FileChange]) {
+        for change in written {
+            let target = root.join(&change.to);
+            if change.is_move() {
+                let _ = fs::remove_file(&target);
+            } else {
+                let _ = write_file(&target, &change.original);
+            }
+        }
+    }
+}
+
+/// Write a change's new body next to its destination, returning the temp path.
+fn stage(root: &Path, change: &FileChange) -> Result<PathBuf> {
+    let target = root.join(&change.to);
+    if change.is_move() && target.exists() {
+        bail!("Destination already exists: {}", change.to.display());
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Could not create {}", parent.display()))?;
+    }
+    let temp = temp_path(&target);
+    if let Err(err) = write_file(&temp, &change.updated) {
+        let _ = fs::remove_file(&temp);
+        return Err(err);
+    }
+    Ok(temp)
+}
+
+fn remove_temps(temps: &[PathBuf]) {
+    for temp in temps {
+        let _ = fs::remove_file(temp);
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    let mut file =
+        File::create(path).with_context(|| format!("Could not create {}", path.display()))?;
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Could not write {}", path.display()))?;
+    Ok(())
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".kbase-tmp");
+    path.with_file_name(name)
+}
+
+fn unified_diff(change: &FileChange) -> String {
+    let from = change.from.to_string_lossy();
+    let to = change.to.to_string_lossy();
+
+    let mut out = format!("diff --git a/{} b/{}\n", from, to);
+    if change.is_move() {
+        out.push_str(&format!("rename from {}\nrename to {}\n", from, to));
+    }
+    if change.original != change.updated {
+        let diff = TextDiff::from_lines(&change.original, &change.updated);
+        out.push_str(
+            &diff
+                .unified_diff()
+                .context_radius(3)
+                .header(&format!("a/{}", from), &format!("b/{}", to))
+                .to_string(),
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_apply_edits_out_of_order() {
+        let content = "alpha beta gamma";
+        let edits = vec![TextEdit::new(11..16, "GAMMA"), TextEdit::new(0..5, "ALPHA")];
+        assert_eq!(apply_edits(content, &edits).unwrap(), "ALPHA beta GAMMA");
+    }
+
+    #[test]
+    fn test_apply_edits_rejects_overlap() {
+        let edits = vec![TextEdit::new(0..5, "x"), TextEdit::new(3..8, "y")];
+        assert!(apply_edits("0123456789", &edits).is_err());
+    }
+
+    #[test]
+    fn test_byte_offset_and_line_range() {
+        let content = "first\nsecond line\r\nthird";
+        assert_eq!(byte_offset(content, 1, 2), Some(2));
+        assert_eq!(byte_offset(content, 2, 0), Some(6));
+        assert_eq!(byte_offset(content, 3, 1), Some(20));
+        assert_eq!(byte_offset(content, 4, 0), None);
+
+        assert_eq!(&content[line_range(content, 2).unwrap()], "second line");
+        assert_eq!(&content[line_range(content, 3).unwrap()], "third");
+        assert_eq!(line_range(content, 4), None);
+    }
+
+    #[test]
+    fn test_diff_shows_rename_and_hunk() {
+        let mut changes = ChangeSet::new();
+        changes.push(FileChange {
+            from: PathBuf::from("a/old.md"),
+            to: PathBuf::from("b/new.md"),
+            original: "one\ntwo\n".into(),
+            updated: "one\nTWO\n".into(),
+        });
+        let diff = changes.diff();
+        assert!(diff.contains("rename from a/old.md"));
+        assert!(diff.contains("-two"));
+        assert!(diff.contains("+TWO"));
+    }
+
+    #[test]
+    fn test_commit_moves_and_rewrites() -> Result<()> {
+        let dir = tempdir()?;
+        fs::write(dir.path().join("old.md"), "old")?;
+        fs::write(dir.path().join("other.md"), "[[old]]")?;
+
+        let mut changes = ChangeSet::new();
+        changes.push(FileChange {
+            from: PathBuf::from("old.md"),
+            to: PathBuf::from("sub/new.md"),
+            original: "old".into(),
+            updated: "old".into(),
+        });
+        changes.push(FileChange::modify(
+            PathBuf::from("other.md"),
+            "[[old]]".into(),
+            "[[sub/new]]".into(),
+        ));
+        changes.commit(dir.path())?;
+
+        assert!(!dir.path().join("old.md").exists());
+        assert_eq!(fs::read_to_string(dir.path().join("sub/new.md"))?, "old");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("other.md"))?,
+            "[[sub/new]]"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_commit_leaves_no_trace() -> Result<()> {
+        let dir = tempdir()?;
+        fs::write(dir.path().join("a.md"), "a")?;
+        fs::write(dir.path().join("taken.md"), "taken")?;
+
+        let mut changes = ChangeSet::new();
+        changes.push(FileChange::modify(
+            PathBuf::from("a.md"),
+            "a".into(),
+            "A".into(),
+        ));
+        changes.push(FileChange {
+            from: PathBuf::from("b.md"),
+            to: PathBuf::from("taken.md"),
+            original: "b".into(),
+            updated: "b".into(),
+        });
+        assert!(changes.commit(dir.path()).is_err());
+
+        let mut names: Vec<String> = fs::read_dir(dir.path())?
+            .map(|e| e.map(|e| e.file_name().to_string_lossy().into_owned()))
+            .collect::<std::io::Result<_>>()?;
+        names.sort();
+        assert_eq!(names, ["a.md", "taken.md"]);
+        assert_eq!(fs::read_to_string(dir.path().join("a.md"))?, "a");
+        Ok(())
+    }
+
+    #[test]
+    fn test_roll_back_restores_written_files() -> Result<()> {
+        let dir = tempdir()?;
+        fs::write(dir.path().join("a.md"), "A")?;
+        fs::write(dir.path().join("old.md"), "old")?;
+        fs::write(dir.path().join("new.md"), "old")?;
+
+        let mut changes = ChangeSet::new();
+        changes.push(FileChange::modify(
+            PathBuf::from("a.md"),
+            "a".into(),
+            "A".into(),
+        ));
+        changes.push(FileChange {
+            from: PathBuf::from("old.md"),
+            to: PathBuf::from("new.md"),
+            original: "old".into(),
+            updated: "old".into(),
+        });
+        changes.roll_back(dir.path(), changes.changes());
+
+        assert_eq!(fs::read_to_string(dir.path().join("a.md"))?, "a");
+        assert!(dir.path().join("old.md").exists());
+        assert!(!dir.path().join("new.md").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_noop_changes_are_skipped() {
+        let mut changes = ChangeSet::new();
+        changes.push(FileChange::modify(
+            PathBuf::from("same.md"),
+            "x".into(),
+            "x".into(),
+        ));
+        assert!(changes.changes().is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! Which files and folders of a vault are left out of notes and domains.
+//!
+//! Rules use gitignore syntax and are checked in order, the last match
+//! winning:
+//!
+//! 1. Built-in defaults: anything starting with `.` or `_`, and
+//!    `description.md` (domain descriptions)
+//! 2. `.kbaseignore` at the vault root
+//! 3. `exclude` patterns from the vault's config
+//! 4. `include` patterns from the vault's config, which re-include paths an
+//!    earlier rule left out (e.g. `_logs`)
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::config::VaultConfig;
+
+/// Name of the per-vault ignore file.
+pub const IGNORE_FILE: &str = ".kbaseignore";
+
+/// Daily logs folder, left out unless asked for (`kbase search --logs`).
+pub const LOGS_DIR: &str = "_logs";
+
+const DEFAULTS: &[&str] = &[".*", "_*", "description.md"];
+
+/// Compiled exclusion rules for one vault.
+pub struct Exclusions {
+    rules: Gitignore,
+}
+
+impl Exclusions {
+    /// Build the rules for a vault rooted at `root`.
+    pub fn new(root: &Path, config: &VaultConfig) -> Result<Self> {
+        let mut builder = GitignoreBuilder::new(root);
+        for pattern in DEFAULTS {
+            builder.add_line(None, pattern)?;
+        }
+
+        let ignore_file = root.join(IGNORE_FILE);
+        if ignore_file.is_file()
+            && let Some(err) = builder.add(&ignore_file)
+        {
+            return Err(err).with_context(|| format!("Invalid {}", ignore_file.display()));
+        }
+
+        for pattern in &config.exclude {
+            builder
+                .add_line(None, pattern)
+                .with_context(|| format!("Invalid exclude pattern '{}' in config", pattern))?;
+        }
+        for pattern in &config.include {
+            builder
+                .add_line(None, &format!("!{}", pattern))
+                .with_context(|| format!("Invalid include pattern '{}' in config", pattern))?;
+        }
+
+        Ok(Exclusions {
+            rules: builder.build()?,
+        })
+    }
+
+    /// Whether `path` (absolute, or relative to the vault root) is left out.
+    /// Only the path itself is checked: callers walking the vault skip
+    /// excluded folders without looking inside.
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        self.rules.matched(path, is_dir).is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn exclusions(root: &Path, exclude: &[&str], include: &[&str]) -> Exclusions {
+        let config = VaultConfig {
+            path: root.to_path_buf(),
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            include: include.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        Exclusions::new(root, &config).unwrap()
+    }
+
+    #[test]
+    fn test_defaults_skip_hidden_and_underscore() {
+        let dir = tempdir().unwrap();
+        let rules = exclusions(dir.path(), &[], &[]);
+
+        assert!(rules.is_excluded(Path::new("_logs"), true));
+        assert!(rules.is_excluded(Path::new(".obsidian"), true));
+        assert!(rules.is_excluded(Path::new("lucene/_draft.md"), false));
+        assert!(rules.is_excluded(Path::new("lucene/description.md"), false));
+        assert!(!rules.is_excluded(Path::new("lucene"), true));
+        assert!(!rules.is_excluded(&dir.path().join("lucene/codecs.md"), false));
+    }
+
+    #[test]
+    fn test_config_and_ignore_file_rules() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(IGNORE_FILE), "archive/\n*.draft.md\n").unwrap();
+        let rules = exclusions(dir.path(), &["lucene/old-*.md"], &[LOGS_DIR, "archive"]);
+
+        assert!(rules.is_excluded(Path::new("rust/notes.draft.md"), false));
+        assert!(rules.is_excluded(Path::new("lucene/old-codecs.md"), false));
+        assert!(!rules.is_excluded(Path::new("lucene/codecs.md"), false));
+        // include wins over the defaults and the ignore file
+        assert!(!rules.is_excluded(Path::new("_logs"), true));
+        assert!(!rules.is_excluded(Path::new("archive"), true));
+    }
+}
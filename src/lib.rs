@@ -2,9 +2,19 @@
 // Exposes modules for use in examples and tests
 
 pub mod config;
+pub mod context;
 pub mod domains;
+pub mod duplicates;
+pub mod edit;
+pub mod exclude;
 pub mod links;
+pub mod lint;
+pub mod metadata;
 pub mod output;
 pub mod parser;
+pub mod query;
+pub mod related;
+pub mod search;
 pub mod tags;
+pub mod tasks;
 pub mod vault;
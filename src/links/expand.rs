@@ -0,0 +1,213 @@
+//! Follow wikilinks out of a note and collect the linked content.
+//!
+//! Used by `kbase read --expand-links`. Links are followed breadth-first, in
+//! the order they appear, up to a maximum depth. `[[note#Section]]` pulls in
+//! just that section (with subsections). A note (or section) reached twice is
+//! included once, and links back into the current chain are reported as cycles.
+//! Targets resolve like the link index does, frontmatter aliases included.
+
+use std::collections::{HashSet, VecDeque};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::LinkIndex;
+use super::index::should_index_wikilink;
+use super::resolve::resolve_with_aliases;
+use crate::parser::sections::{find_section, section_lines};
+use crate::parser::{MarkdownParser, TreeSitterParser, Wikilink};
+use crate::vault::Vault;
+
+/// A piece of linked content pulled into the expansion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub path: PathBuf,
+    /// Linked section (`#A#B` chain, without the leading `#`), or None for the whole note
+    pub section: Option<String>,
+    /// Note that linked here
+    pub from: PathBuf,
+    pub depth: usize,
+    /// 1-indexed line range of `content` shown
+    pub lines: RangeInclusive<usize>,
+    pub content: String,
+}
+
+/// Why a link wasn't expanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Already included earlier in the expansion
+    Duplicate,
+    /// Links back to a note in the current chain
+    Cycle,
+    /// The linked section doesn't exist
+    MissingSection,
+    /// The linked section matches more than one heading
+    AmbiguousSection,
+}
+
+/// One step of the expansion, in output order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expanded {
+    Segment(Segment),
+    Skipped {
+        path: PathBuf,
+        section: Option<String>,
+        from: PathBuf,
+        reason: SkipReason,
+    },
+}
+
+/// A note (or section) waiting to be expanded.
+struct Pending {
+    path: PathBuf,
+    section: Option<String>,
+    from: PathBuf,
+    depth: usize,
+    chain: Vec<PathBuf>,
+}
+
+/// Expand links out of `root`, limited to links on `root_lines`, up to
+/// `max_depth` hops. Only edges present in the link index are followed.
+pub fn expand_links(
+    vault: &Vault,
+    link_index: &LinkIndex,
+    root: &Path,
+    root_lines: RangeInclusive<usize>,
+    max_depth: usize,
+) -> Result<Vec<Expanded>> {
+    let all_notes: HashSet<PathBuf> = vault.all_notes()?.into_iter().map(|n| n.path).collect();
+    let aliases = vault.aliases()?;
+    let mut parser = TreeSitterParser::new()?;
+    let mut output = Vec::new();
+
+    // The root counts as fully included
+    let mut included: HashSet<(PathBuf, Option<String>)> = HashSet::new();
+    included.insert((root.to_path_buf(), None));
+
+    let root_content = vault.read_note(&root.to_string_lossy())?;
+    let root_links = parser.parse(&root_content)?.wikilinks;
+
+    let mut queue = VecDeque::new();
+    let root_chain = vec![root.to_path_buf()];
+    let mut discover = |links: &[Wikilink],
+                        lines: &RangeInclusive<usize>,
+                        source: &Path,
+                        depth: usize,
+                        chain: &[PathBuf],
+                        queue: &mut VecDeque<Pending>,
+                        output: &mut Vec<Expanded>| {
+        let forward: HashSet<&PathBuf> = link_index
+            .get_forward(source)
+            .unwrap_or_default()
+            .iter()
+            .collect();
+
+        for link in links.iter().filter(|l| lines.contains(&l.line)) {
+            if link.target.is_empty() || !should_index_wikilink(&link.target) {
+                continue;
+            }
+            let Some(target) = resolve_with_aliases(&link.target, source, &all_notes, &aliases)
+            else {
+                continue;
+            };
+            if !forward.contains(&target) {
+                continue;
+            }
+
+            let section = link.section.clone().filter(|s| !s.trim().is_empty());
+            let skip = |reason| Expanded::Skipped {
+                path: target.clone(),
+                section: section.clone(),
+                from: source.to_path_buf(),
+                reason,
+            };
+
+            if chain.contains(&target) {
+                output.push(skip(SkipReason::Cycle));
+                continue;
+            }
+            let whole = (target.clone(), None);
+            let key = (target.clone(), section.clone());
+            if included.contains(&whole) || !included.insert(key) {
+                output.push(skip(SkipReason::Duplicate));
+                continue;
+            }
+
+            let mut chain = chain.to_vec();
+            chain.push(target.clone());
+            queue.push_back(Pending {
+                path: target,
+                section,
+                from: source.to_path_buf(),
+                depth,
+                chain,
+            });
+        }
+    };
+
+    if max_depth > 0 {
+        discover(
+            &root_links,
+            &root_lines,
+            root,
+            1,
+            &root_chain,
+            &mut queue,
+            &mut output,
+        );
+    }
+
+    while let Some(pending) = queue.pop_front() {
+        let content = vault.read_note(&pending.path.to_string_lossy())?;
+        let parsed = parser.parse(&content)?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        let range = match &pending.section {
+            None => 1..=lines.len(),
+            Some(section) => {
+                let components: Vec<&str> = section.split('#').collect();
+                let found = match find_section(&parsed.headings, &components).as_slice() {
+                    [index] => Ok(section_lines(&lines, &parsed.headings, *index)),
+                    [] => Err(SkipReason::MissingSection),
+                    _ => Err(SkipReason::AmbiguousSection),
+                };
+                match found {
+                    Ok(range) => range,
+                    Err(reason) => {
+                        output.push(Expanded::Skipped {
+                            path: pending.path,
+                            section: pending.section,
+                            from: pending.from,
+                            reason,
+                        });
+                        continue;
+                    }
+                }
+            }
+        };
+
+        output.push(Expanded::Segment(Segment {
+            path: pending.path.clone(),
+            section: pending.section,
+            from: pending.from,
+            depth: pending.depth,
+            lines: range.clone(),
+            content: content.clone(),
+        }));
+
+        if pending.depth < max_depth {
+            discover(
+                &parsed.wikilinks,
+                &range,
+                &pending.path,
+                pending.depth + 1,
+                &pending.chain,
+                &mut queue,
+                &mut output,
+            );
+        }
+    }
+
+    Ok(output)
+}
@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use crate::parser::{MarkdownParser, TreeSitterParser};
 use crate::vault::Vault;
 
-use super::resolve::resolve_target;
+use super::resolve::{AliasMap, resolve_with_aliases};
 
 /// Bidirectional link index for fast link queries.
 /// Stores forward (source→target) and backward (target→source) mappings on disk.
@@ -38,6 +38,9 @@ impl LinkIndex {
     }
 
     /// Build a LinkIndex by scanning all notes in a vault.
+    ///
+    /// Two passes: parse every note (collecting frontmatter aliases), then
+    /// resolve links, so `[[Alias]]` can point at any note in the vault.
     pub fn build_from_vault(vault: &Vault) -> Result<(Self, usize)> {
         let all_notes = vault.all_notes()?;
         let mut parser = TreeSitterParser::new()?;
@@ -45,30 +48,41 @@ impl LinkIndex {
         // Build set of all note paths for resolution
         let all_note_paths: HashSet<PathBuf> = all_notes.iter().map(|n| n.path.clone()).collect();
 
-        let mut forward: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
-        let mut backward: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
-        let mut unresolved_count = 0;
-
-        // Single pass: parse each note, resolve links, populate both maps
+        // Pass 1: parse each note, collect wikilinks and aliases
+        let mut parsed_notes = Vec::with_capacity(all_notes.len());
+        let mut aliases = AliasMap::new();
         for note in &all_notes {
             let content = vault.read_note(&note.path.to_string_lossy())?;
             let parsed = parser.parse(&content)?;
 
-            for wikilink in parsed.wikilinks {
+            for alias in &parsed.aliases {
+                aliases
+                    .entry(alias.to_lowercase())
+                    .or_default()
+                    .push(note.path.clone());
+            }
+            parsed_notes.push((&note.path, parsed.wikilinks));
+        }
+
+        let mut forward: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        let mut backward: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        let mut unresolved_count = 0;
+
+        // Pass 2: resolve links, populate both maps
+        for (source, wikilinks) in parsed_notes {
+            for wikilink in wikilinks {
                 // Filter out non-markdown links (images, etc.)
                 if !should_index_wikilink(&wikilink.target) {
                     continue;
                 }
 
                 // Resolve wikilink to actual path
-                if let Some(resolved_path) = resolve_target(
-                    &wikilink.target,
-                    &note.path,
-                    &all_note_paths,
-                ) {
+                if let Some(resolved_path) =
+                    resolve_with_aliases(&wikilink.target, source, &all_note_paths, &aliases)
+                {
                     // Add to forward map: source → target
                     forward
-                        .entry(note.path.clone())
+                        .entry(source.clone())
                         .or_default()
                         .push(resolved_path.clone());
 
@@ -76,7 +90,7 @@ impl LinkIndex {
                     backward
                         .entry(resolved_path)
                         .or_default()
-                        .push(note.path.clone());
+                        .push(source.clone());
                 } else {
                     unresolved_count += 1;
                 }
@@ -125,7 +139,6 @@ impl LinkIndex {
     }
 
     /// Load LinkIndex from JSON files.
-    #[allow(dead_code)]
     pub fn load_from_json(index_dir: &Path) -> Result<Self> {
         let forward_path = index_dir.join("links-forward.json");
         let backward_path = index_dir.join("links-backward.json");
@@ -151,13 +164,11 @@ impl LinkIndex {
     }
 
     /// Get forward links for a note (notes this note links to).
-    #[allow(dead_code)]
     pub fn get_forward(&self, note: &Path) -> Option<&[PathBuf]> {
         self.forward.get(note).map(|v| v.as_slice())
     }
 
     /// Get backward links for a note (notes that link to this note).
-    #[allow(dead_code)]
     pub fn get_backward(&self, note: &Path) -> Option<&[PathBuf]> {
         self.backward.get(note).map(|v| v.as_slice())
     }
@@ -166,7 +177,7 @@ impl LinkIndex {
 /// Filter wikilinks to only include markdown notes.
 /// Accepts: no extension or .md extension
 /// Rejects: .png, .jpg, .svg, etc.
-fn should_index_wikilink(target: &str) -> bool {
+pub fn should_index_wikilink(target: &str) -> bool {
     // target already has section stripped by parser
     
     // Check if it has an extension
@@ -1,4 +1,6 @@
+pub mod expand;
 pub mod index;
 pub mod resolve;
+pub mod rewrite;
 
 pub use index::LinkIndex;
@@ -1,6 +1,11 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use anyhow::{Result, bail};
+
+/// Frontmatter aliases: lowercased alias → notes declaring it.
+pub type AliasMap = HashMap<String, Vec<PathBuf>>;
+
 /// Resolve a wikilink target to an actual note path.
 ///
 /// # Resolution Strategy
@@ -69,13 +74,142 @@ pub fn resolve_target(
     None
 }
 
+/// Resolve a wikilink target, falling back to frontmatter aliases.
+///
+/// Path rules from [`resolve_target`] win. Otherwise the target is matched
+/// case-insensitively against `aliases`; an alias declared by more than one
+/// note is ambiguous and stays unresolved.
+pub fn resolve_with_aliases(
+    target: &str,
+    source_path: &Path,
+    all_notes: &HashSet<PathBuf>,
+    aliases: &AliasMap,
+) -> Option<PathBuf> {
+    if let Some(path) = resolve_target(target, source_path, all_notes) {
+        return Some(path);
+    }
+
+    match aliases.get(&target.to_lowercase())?.as_slice() {
+        [only] => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// Outcome of resolving a note name typed on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NoteMatch {
+    Found(PathBuf),
+    Ambiguous(Vec<PathBuf>),
+    NotFound,
+}
+
+impl NoteMatch {
+    /// The matched path, None if nothing matched, or an error listing the
+    /// candidates if `name` is ambiguous.
+    pub fn found(self, name: &str) -> Result<Option<PathBuf>> {
+        match self {
+            NoteMatch::Found(path) => Ok(Some(path)),
+            NoteMatch::NotFound => Ok(None),
+            NoteMatch::Ambiguous(candidates) => {
+                let list: Vec<String> = candidates
+                    .iter()
+                    .map(|p| format!("  {}", p.display()))
+                    .collect();
+                bail!(
+                    "note '{}' is ambiguous; did you mean one of:\n{}",
+                    name,
+                    list.join("\n")
+                )
+            }
+        }
+    }
+}
+
+/// Resolve a note argument (`lucene/search-flow.md`, `lucene/search-flow`,
+/// `search-flow`, `flow`) to a vault-relative note path.
+///
+/// The `.md` extension is optional. Tiers are tried in order and the first
+/// one with any match wins; several matches in that tier are ambiguous:
+///
+/// 1. Vault-relative path, as [`resolve_target`] resolves it from the vault root
+/// 2. Trailing path components (`search-flow`, `indexing/inverted-index`)
+/// 3. Trailing path components, ignoring case
+/// 4. Substring of the path, ignoring case
+pub fn resolve_note_name(name: &str, all_notes: &HashSet<PathBuf>) -> NoteMatch {
+    resolve_note_name_up_to(name, all_notes, SUBSTRING_TIER)
+}
+
+/// Like [`resolve_note_name`], but without substring matches (tier 4), for
+/// arguments that may be free text rather than a note name.
+pub fn resolve_note_name_exact(name: &str, all_notes: &HashSet<PathBuf>) -> NoteMatch {
+    resolve_note_name_up_to(name, all_notes, SUBSTRING_TIER - 1)
+}
+
+/// [`match_tier`] of a match that is only a substring of the path.
+const SUBSTRING_TIER: u8 = 2;
+
+fn resolve_note_name_up_to(name: &str, all_notes: &HashSet<PathBuf>, max_tier: u8) -> NoteMatch {
+    let name = name.trim().trim_start_matches("./");
+    let name = name.strip_suffix(".md").unwrap_or(name);
+    if name.is_empty() {
+        return NoteMatch::NotFound;
+    }
+
+    if let Some(path) = resolve_target(name, Path::new(""), all_notes) {
+        return NoteMatch::Found(path);
+    }
+
+    let matches: Vec<(u8, &PathBuf)> = all_notes
+        .iter()
+        .filter_map(|path| {
+            let stem = path.to_string_lossy();
+            let stem = stem.strip_suffix(".md").unwrap_or(&stem);
+            let tier = match_tier(stem, name).filter(|&tier| tier <= max_tier)?;
+            Some((tier, path))
+        })
+        .collect();
+
+    let Some(best) = matches.iter().map(|&(tier, _)| tier).min() else {
+        return NoteMatch::NotFound;
+    };
+    let mut found: Vec<PathBuf> = matches
+        .into_iter()
+        .filter(|&(tier, _)| tier == best)
+        .map(|(_, path)| path.clone())
+        .collect();
+
+    if found.len() == 1 {
+        NoteMatch::Found(found.remove(0))
+    } else {
+        found.sort();
+        NoteMatch::Ambiguous(found)
+    }
+}
+
+/// How closely a note path (without `.md`) matches a name, best first:
+/// trailing components, the same ignoring case, then any substring.
+fn match_tier(stem: &str, name: &str) -> Option<u8> {
+    let stem_lower = stem.to_lowercase();
+    let name_lower = name.to_lowercase();
+
+    if stem.ends_with(&format!("/{}", name)) {
+        Some(0)
+    } else if stem_lower == name_lower || stem_lower.ends_with(&format!("/{}", name_lower)) {
+        Some(1)
+    } else if stem_lower.contains(&name_lower) {
+        Some(SUBSTRING_TIER)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn setup_vault() -> HashSet<PathBuf> {
         // Simulate vault structure
-        let notes = vec![
+        let notes = [
             "lucene/search-flow.md",
             "lucene/codecs.md",
             "lucene/internals/codec-details.md",
@@ -191,4 +325,129 @@ mod tests {
         let result = resolve_target("codecs", &source, &all_notes);
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_alias_fallback() {
+        let all_notes = setup_vault();
+        let source = PathBuf::from("datafusion/01-home.md");
+        let mut aliases = AliasMap::new();
+        aliases.insert(
+            "search flow".into(),
+            vec![PathBuf::from("lucene/search-flow.md")],
+        );
+
+        let result = resolve_with_aliases("Search Flow", &source, &all_notes, &aliases);
+        assert_eq!(result, Some(PathBuf::from("lucene/search-flow.md")));
+    }
+
+    #[test]
+    fn test_path_rules_win_over_alias() {
+        let all_notes = setup_vault();
+        let source = PathBuf::from("lucene/search-flow.md");
+        let mut aliases = AliasMap::new();
+        aliases.insert(
+            "codecs".into(),
+            vec![PathBuf::from("datafusion/01-home.md")],
+        );
+
+        let result = resolve_with_aliases("codecs", &source, &all_notes, &aliases);
+        assert_eq!(result, Some(PathBuf::from("lucene/codecs.md")));
+    }
+
+    #[test]
+    fn test_ambiguous_alias_unresolved() {
+        let all_notes = setup_vault();
+        let source = PathBuf::from("glossary.md");
+        let mut aliases = AliasMap::new();
+        aliases.insert(
+            "home".into(),
+            vec![
+                PathBuf::from("lucene/01-home.md"),
+                PathBuf::from("datafusion/01-home.md"),
+            ],
+        );
+
+        assert_eq!(
+            resolve_with_aliases("home", &source, &all_notes, &aliases),
+            None
+        );
+    }
+
+    #[test]
+    fn test_note_name_without_extension() {
+        let all_notes = setup_vault();
+        let found = |p: &str| NoteMatch::Found(PathBuf::from(p));
+
+        assert_eq!(
+            resolve_note_name("lucene/search-flow.md", &all_notes),
+            found("lucene/search-flow.md")
+        );
+        assert_eq!(
+            resolve_note_name("lucene/search-flow", &all_notes),
+            found("lucene/search-flow.md")
+        );
+        assert_eq!(
+            resolve_note_name("glossary", &all_notes),
+            found("glossary.md")
+        );
+    }
+
+    #[test]
+    fn test_note_short_and_fuzzy_names() {
+        let all_notes = setup_vault();
+        let found = |p: &str| NoteMatch::Found(PathBuf::from(p));
+
+        assert_eq!(
+            resolve_note_name("search-flow", &all_notes),
+            found("lucene/search-flow.md")
+        );
+        assert_eq!(
+            resolve_note_name("internals/codec-details", &all_notes),
+            found("lucene/internals/codec-details.md")
+        );
+        assert_eq!(
+            resolve_note_name("Query-Execution", &all_notes),
+            found("datafusion/query-execution.md")
+        );
+        assert_eq!(
+            resolve_note_name("flow", &all_notes),
+            found("lucene/search-flow.md")
+        );
+        assert_eq!(
+            resolve_note_name("missing", &all_notes),
+            NoteMatch::NotFound
+        );
+    }
+
+    #[test]
+    fn test_note_name_exact_skips_substrings() {
+        let all_notes = setup_vault();
+
+        assert_eq!(
+            resolve_note_name_exact("Search-Flow", &all_notes),
+            NoteMatch::Found(PathBuf::from("lucene/search-flow.md"))
+        );
+        assert_eq!(
+            resolve_note_name_exact("flow", &all_notes),
+            NoteMatch::NotFound
+        );
+    }
+
+    #[test]
+    fn test_note_name_ambiguous() {
+        let all_notes = setup_vault();
+
+        assert_eq!(
+            resolve_note_name("01-home", &all_notes),
+            NoteMatch::Ambiguous(vec![
+                PathBuf::from("datafusion/01-home.md"),
+                PathBuf::from("lucene/01-home.md"),
+            ])
+        );
+        // An earlier tier wins even when a later one would match more notes
+        assert_eq!(
+            resolve_note_name("codecs", &all_notes),
+            NoteMatch::Found(PathBuf::from("lucene/codecs.md"))
+        );
+    }
 }
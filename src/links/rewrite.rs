@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::edit::byte_offset;
+use crate::parser::Wikilink;
+
+use super::resolve::resolve_target;
+
+/// Byte range of a wikilink's target inside `content`.
+///
+/// For `[[lucene/codecs#Postings|codecs]]` this is the span of `lucene/codecs`,
+/// so replacing it keeps the `#section` and `|alias` suffixes untouched.
+/// Returns None if the parser positions don't line up with the content.
+pub fn target_range(content: &str, link: &Wikilink) -> Option<Range<usize>> {
+    let start = byte_offset(content, link.line, link.column)?;
+    let rest = content.get(start..)?;
+    if !rest.starts_with("[[") {
+        return None;
+    }
+
+    let target_start = start + 2;
+    let target_end = target_start + link.target.len();
+    if content.get(target_start..target_end)? != link.target {
+        return None;
+    }
+
+    Some(target_start..target_end)
+}
+
+/// Byte range of a wikilink's section (without the leading `#`) inside `content`.
+///
+/// For `[[codecs#Postings|codecs]]` this is the span of `Postings`.
+/// Returns None if the link has no section or positions don't line up.
+pub fn section_range(content: &str, link: &Wikilink) -> Option<Range<usize>> {
+    let section = link.section.as_ref()?;
+    let target = target_range(content, link)?;
+    if content.get(target.end..target.end + 1)? != "#" {
+        return None;
+    }
+
+    let start = target.end + 1;
+    let end = start + section.len();
+    (content.get(start..end)? == section).then_some(start..end)
+}
+
+/// Choose the shortest wikilink target that resolves from `source` to `target`.
+///
+/// Candidates, in order of preference for equal length:
+/// 1. Bare name: `codecs`
+/// 2. Relative to the source's folder: `indexing/inverted-index`
+/// 3. Absolute from the vault root: `lucene/indexing/inverted-index`
+///
+/// `all_notes` must reflect the vault as it will be once the link is written.
+pub fn shortest_link(target: &Path, source: &Path, all_notes: &HashSet<PathBuf>) -> String {
+    let absolute = target.with_extension("").to_string_lossy().to_string();
+
+    let mut candidates = Vec::new();
+    if let Some(stem) = target.file_stem().and_then(|s| s.to_str()) {
+        candidates.push(stem.to_string());
+    }
+    if let Some(relative) = source
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .and_then(|dir| Path::new(&absolute).strip_prefix(dir).ok())
+    {
+        candidates.push(relative.to_string_lossy().to_string());
+    }
+    candidates.push(absolute.clone());
+
+    candidates
+        .into_iter()
+        .filter(|c| resolve_target(c, source, all_notes).as_deref() == Some(target))
+        .min_by_key(|c| c.len())
+        .unwrap_or(absolute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notes(paths: &[&str]) -> HashSet<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    fn link(target: &str, line: usize, column: usize) -> Wikilink {
+        Wikilink {
+            target: target.to_string(),
+            alias: None,
+            section: None,
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn test_target_range_skips_section_and_alias() {
+        let content = "intro\nSee [[codecs#Postings|the codecs]] here.\n";
+        let range = target_range(content, &link("codecs", 2, 4)).unwrap();
+        assert_eq!(&content[range], "codecs");
+    }
+
+    #[test]
+    fn test_target_range_rejects_mismatch() {
+        let content = "See [[codecs]]\n";
+        assert_eq!(target_range(content, &link("codecs", 1, 0)), None);
+        assert_eq!(target_range(content, &link("other", 1, 4)), None);
+    }
+
+    #[test]
+    fn test_section_range() {
+        let content = "See [[codecs#Postings|the codecs]] and [[#Local]].\n";
+        let mut with_section = link("codecs", 1, 4);
+        with_section.section = Some("Postings".into());
+        assert_eq!(
+            &content[section_range(content, &with_section).unwrap()],
+            "Postings"
+        );
+
+        let mut self_link = link("", 1, 39);
+        self_link.section = Some("Local".into());
+        assert_eq!(
+            &content[section_range(content, &self_link).unwrap()],
+            "Local"
+        );
+
+        assert_eq!(section_range(content, &link("codecs", 1, 4)), None);
+    }
+
+    #[test]
+    fn test_shortest_link_prefers_bare_name_in_same_domain() {
+        let all = notes(&["lucene/codecs.md", "lucene/search-flow.md"]);
+        let link = shortest_link(
+            Path::new("lucene/codecs.md"),
+            Path::new("lucene/search-flow.md"),
+            &all,
+        );
+        assert_eq!(link, "codecs");
+    }
+
+    #[test]
+    fn test_shortest_link_uses_relative_path_for_subfolder() {
+        let all = notes(&["lucene/indexing/inverted-index.md", "lucene/search-flow.md"]);
+        let link = shortest_link(
+            Path::new("lucene/indexing/inverted-index.md"),
+            Path::new("lucene/search-flow.md"),
+            &all,
+        );
+        assert_eq!(link, "indexing/inverted-index");
+    }
+
+    #[test]
+    fn test_shortest_link_falls_back_to_absolute_across_domains() {
+        let all = notes(&["rust/codecs.md", "lucene/search-flow.md"]);
+        let link = shortest_link(
+            Path::new("rust/codecs.md"),
+            Path::new("lucene/search-flow.md"),
+            &all,
+        );
+        assert_eq!(link, "rust/codecs");
+    }
+
+    #[test]
+    fn test_shortest_link_to_root_level_note() {
+        let all = notes(&["glossary.md", "lucene/search-flow.md"]);
+        let link = shortest_link(
+            Path::new("glossary.md"),
+            Path::new("lucene/search-flow.md"),
+            &all,
+        );
+        assert_eq!(link, "glossary");
+    }
+}
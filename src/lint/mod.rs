@@ -0,0 +1,239 @@
+//! Vault lint: rules that check parsed notes for structural problems.
+//!
+//! Each rule implements [`Rule`] and sees the whole vault through a
+//! [`LintContext`], so per-note checks and cross-note checks (duplicate
+//! titles, tag spellings) share one interface. Rules have a default severity
+//! that a vault can override, or turn off, in its config:
+//!
+//! ```toml
+//! [vaults.personal.lint]
+//! heading-jump = "off"
+//! kebab-case = "error"
+//! ```
+//!
+//! Rules may attach a fix to a finding: byte-range edits to the note, which
+//! `kbase lint --fix` applies (or previews as a diff with `--dry-run`). Only
+//! mechanical, unambiguous fixes are offered.
+
+pub mod rules;
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::edit::{ChangeSet, FileChange, TextEdit, apply_edits};
+use crate::links::resolve::AliasMap;
+use crate::parser::{MarkdownParser, ParsedMarkdown, TreeSitterParser, frontmatter};
+use crate::vault::Vault;
+
+/// How serious a finding is. Errors fail `kbase lint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A rule's configured level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Warning,
+    Error,
+}
+
+/// A problem a rule found, before severity is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub path: PathBuf,
+    pub line: usize,
+    /// 1-indexed byte column
+    pub column: usize,
+    pub message: String,
+    /// Edits to the note that resolve the problem, empty if not fixable
+    pub fix: Vec<TextEdit>,
+}
+
+impl Finding {
+    pub fn new(path: &Path, line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            line,
+            column,
+            message: message.into(),
+            fix: Vec::new(),
+        }
+    }
+
+    pub fn with_fix(mut self, fix: Vec<TextEdit>) -> Self {
+        self.fix = fix;
+        self
+    }
+}
+
+/// A finding with its rule and severity, as reported.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub fixable: bool,
+    #[serde(skip)]
+    pub fix: Vec<TextEdit>,
+}
+
+/// A lint rule.
+pub trait Rule {
+    /// Kebab-case name used in output and config
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn default_severity(&self) -> Severity;
+    fn check(&self, ctx: &LintContext) -> Vec<Finding>;
+}
+
+/// A parsed note under lint.
+pub struct LintNote {
+    pub path: PathBuf,
+    pub content: String,
+    pub parsed: ParsedMarkdown,
+    /// First line after frontmatter
+    pub body_start: usize,
+}
+
+/// Everything rules can look at: every parsed note plus what's needed to
+/// resolve links the way the link index does.
+pub struct LintContext {
+    pub notes: Vec<LintNote>,
+    pub all_notes: HashSet<PathBuf>,
+    pub aliases: AliasMap,
+}
+
+impl LintContext {
+    pub fn build(vault: &Vault) -> Result<Self> {
+        let mut parser = TreeSitterParser::new()?;
+        let mut notes = Vec::new();
+        let mut aliases = AliasMap::new();
+
+        for note in vault.all_notes()? {
+            let content = vault.read_note(&note.path.to_string_lossy())?;
+            let parsed = parser.parse(&content)?;
+            for alias in &parsed.aliases {
+                aliases
+                    .entry(alias.to_lowercase())
+                    .or_default()
+                    .push(note.path.clone());
+            }
+            notes.push(LintNote {
+                body_start: frontmatter::split(&content).map_or(1, |(_, n)| n + 1),
+                path: note.path,
+                content,
+                parsed,
+            });
+        }
+
+        let all_notes = notes.iter().map(|n| n.path.clone()).collect();
+        Ok(Self {
+            notes,
+            all_notes,
+            aliases,
+        })
+    }
+}
+
+/// Check that every configured rule exists.
+pub fn validate_config(config: &BTreeMap<String, RuleLevel>) -> Result<()> {
+    let known: Vec<&str> = rules::all().iter().map(|r| r.name()).collect();
+    for name in config.keys() {
+        if !known.contains(&name.as_str()) {
+            bail!(
+                "Unknown lint rule '{}' in config. Rules: {}",
+                name,
+                known.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// A rule's severity under `config`, or None if it's turned off.
+pub fn severity(rule: &dyn Rule, config: &BTreeMap<String, RuleLevel>) -> Option<Severity> {
+    match config.get(rule.name()) {
+        Some(RuleLevel::Off) => None,
+        Some(RuleLevel::Warning) => Some(Severity::Warning),
+        Some(RuleLevel::Error) => Some(Severity::Error),
+        None => Some(rule.default_severity()),
+    }
+}
+
+/// Run `rules` with their configured severities. Diagnostics come back
+/// sorted by path, line and column.
+pub fn run(
+    ctx: &LintContext,
+    rules: &[Box<dyn Rule>],
+    config: &BTreeMap<String, RuleLevel>,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for rule in rules {
+        let Some(severity) = severity(rule.as_ref(), config) else {
+            continue;
+        };
+        diagnostics.extend(rule.check(ctx).into_iter().map(|f| Diagnostic {
+            rule: rule.name(),
+            severity,
+            path: f.path,
+            line: f.line,
+            column: f.column,
+            message: f.message,
+            fixable: !f.fix.is_empty(),
+            fix: f.fix,
+        }));
+    }
+
+    diagnostics.sort_by(|a, b| {
+        (&a.path, a.line, a.column, a.rule).cmp(&(&b.path, b.line, b.column, b.rule))
+    });
+    diagnostics
+}
+
+/// Collect the fixes of `diagnostics` into one change per note.
+pub fn fixes(ctx: &LintContext, diagnostics: &[Diagnostic]) -> Result<ChangeSet> {
+    let mut edits: BTreeMap<&Path, Vec<TextEdit>> = BTreeMap::new();
+    for d in diagnostics {
+        edits
+            .entry(&d.path)
+            .or_default()
+            .extend(d.fix.iter().cloned());
+    }
+
+    let mut changes = ChangeSet::new();
+    for note in &ctx.notes {
+        let Some(edits) = edits.get(note.path.as_path()) else {
+            continue;
+        };
+        let updated = apply_edits(&note.content, edits)
+            .with_context(|| format!("Conflicting fixes in {}", note.path.display()))?;
+        changes.push(FileChange::modify(
+            note.path.clone(),
+            note.content.clone(),
+            updated,
+        ));
+    }
+    Ok(changes)
+}
@@ -0,0 +1,635 @@
+//! Built-in lint rules.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::{Finding, LintContext, LintNote, Rule, Severity};
+use crate::edit::{TextEdit, byte_offset, line_range};
+use crate::links::index::should_index_wikilink;
+use crate::links::resolve::resolve_with_aliases;
+use crate::links::rewrite::{shortest_link, target_range};
+use crate::parser::{Heading, frontmatter};
+
+/// Every built-in rule, in the order they're documented.
+pub fn all() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(MissingTitle),
+        Box::new(HeadingJump),
+        Box::new(DuplicateTitle),
+        Box::new(BrokenLink),
+        Box::new(EmptyNote),
+        Box::new(TagCase),
+        Box::new(KebabCase),
+    ]
+}
+
+/// Note has no frontmatter `title` and no heading.
+///
+/// Fix: insert an H1 made from the file name at the top of the body.
+pub struct MissingTitle;
+
+impl Rule for MissingTitle {
+    fn name(&self) -> &'static str {
+        "missing-title"
+    }
+
+    fn description(&self) -> &'static str {
+        "note has no title (frontmatter `title` or a heading)"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext) -> Vec<Finding> {
+        ctx.notes
+            .iter()
+            .filter(|n| n.parsed.title.is_empty() && !is_empty(n))
+            .map(|n| {
+                let fix = byte_offset(&n.content, n.body_start, 0).map(|offset| {
+                    let heading = format!("# {}\n\n", title_from_filename(&n.path));
+                    TextEdit::new(offset..offset, heading)
+                });
+                Finding::new(&n.path, n.body_start, 1, "note has no title or heading")
+                    .with_fix(fix.into_iter().collect())
+            })
+            .collect()
+    }
+}
+
+/// A heading more than one level deeper than the one before it.
+///
+/// Fix: raise it, and the headings nested under it, so each level follows on
+/// from its parent.
+pub struct HeadingJump;
+
+impl Rule for HeadingJump {
+    fn name(&self) -> &'static str {
+        "heading-jump"
+    }
+
+    fn description(&self) -> &'static str {
+        "heading skips a level (e.g. # followed by ###)"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for note in &ctx.notes {
+            let headings = &note.parsed.headings;
+            let levels = normalized_levels(headings);
+            let jumps: Vec<usize> = (1..headings.len())
+                .filter(|&i| headings[i].level > headings[i - 1].level + 1)
+                .collect();
+
+            for (n, &i) in jumps.iter().enumerate() {
+                // Each jump owns the headings up to the next one
+                let end = jumps.get(n + 1).copied().unwrap_or(headings.len());
+                let fix = (i..end)
+                    .filter(|&j| levels[j] != headings[j].level)
+                    .filter_map(|j| heading_level_edit(&note.content, &headings[j], levels[j]))
+                    .collect();
+                findings.push(
+                    Finding::new(
+                        &note.path,
+                        headings[i].line,
+                        1,
+                        format!(
+                            "heading level jumps from H{} to H{}",
+                            headings[i - 1].level,
+                            headings[i].level
+                        ),
+                    )
+                    .with_fix(fix),
+                );
+            }
+        }
+        findings
+    }
+}
+
+/// Several notes with the same title (ignoring case).
+pub struct DuplicateTitle;
+
+impl Rule for DuplicateTitle {
+    fn name(&self) -> &'static str {
+        "duplicate-title"
+    }
+
+    fn description(&self) -> &'static str {
+        "another note has the same title"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext) -> Vec<Finding> {
+        let mut by_title: BTreeMap<String, Vec<&LintNote>> = BTreeMap::new();
+        for note in ctx.notes.iter().filter(|n| !n.parsed.title.is_empty()) {
+            by_title
+                .entry(note.parsed.title.to_lowercase())
+                .or_default()
+                .push(note);
+        }
+
+        let mut findings = Vec::new();
+        for notes in by_title.values().filter(|notes| notes.len() > 1) {
+            for note in notes {
+                let others: Vec<String> = notes
+                    .iter()
+                    .filter(|other| other.path != note.path)
+                    .map(|other| other.path.display().to_string())
+                    .collect();
+                findings.push(Finding::new(
+                    &note.path,
+                    title_line(note),
+                    1,
+                    format!(
+                        "title '{}' is also used by {}",
+                        note.parsed.title,
+                        others.join(", ")
+                    ),
+                ));
+            }
+        }
+        findings
+    }
+}
+
+/// A wikilink that resolves to no note.
+///
+/// Fix: point it at the only note whose name is a close match, if there is
+/// exactly one.
+pub struct BrokenLink;
+
+impl Rule for BrokenLink {
+    fn name(&self) -> &'static str {
+        "broken-link"
+    }
+
+    fn description(&self) -> &'static str {
+        "wikilink target doesn't resolve to a note"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, ctx: &LintContext) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        for note in &ctx.notes {
+            for link in &note.parsed.wikilinks {
+                if link.target.is_empty() || !should_index_wikilink(&link.target) {
+                    continue;
+                }
+                let resolved =
+                    resolve_with_aliases(&link.target, &note.path, &ctx.all_notes, &ctx.aliases);
+                if resolved.is_some() {
+                    continue;
+                }
+
+                let fix = close_match(&link.target, &ctx.all_notes)
+                    .zip(target_range(&note.content, link))
+                    .map(|(target, range)| {
+                        let new = shortest_link(&target, &note.path, &ctx.all_notes);
+                        TextEdit::new(range, new)
+                    });
+                findings.push(
+                    Finding::new(
+                        &note.path,
+                        link.line,
+                        link.column + 1,
+                        format!("broken link [[{}]]", link.target),
+                    )
+                    .with_fix(fix.into_iter().collect()),
+                );
+            }
+        }
+        findings
+    }
+}
+
+/// Nothing but whitespace after the frontmatter.
+pub struct EmptyNote;
+
+impl Rule for EmptyNote {
+    fn name(&self) -> &'static str {
+        "empty-note"
+    }
+
+    fn description(&self) -> &'static str {
+        "note has no content besides frontmatter"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext) -> Vec<Finding> {
+        ctx.notes
+            .iter()
+            .filter(|n| is_empty(n))
+            .map(|n| Finding::new(&n.path, 1, 1, "note is empty"))
+            .collect()
+    }
+}
+
+/// The same tag spelled with different case across the vault.
+///
+/// Fix: respell every occurrence in the note, inline and in frontmatter.
+pub struct TagCase;
+
+impl Rule for TagCase {
+    fn name(&self) -> &'static str {
+        "tag-case"
+    }
+
+    fn description(&self) -> &'static str {
+        "tag differs only in case from a more common spelling"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext) -> Vec<Finding> {
+        // lowercased tag -> spelling -> notes using it
+        let mut spellings: BTreeMap<String, BTreeMap<&str, BTreeSet<usize>>> = BTreeMap::new();
+        for (i, note) in ctx.notes.iter().enumerate() {
+            for tag in &note.parsed.tags {
+                spellings
+                    .entry(tag.to_lowercase())
+                    .or_default()
+                    .entry(tag)
+                    .or_default()
+                    .insert(i);
+            }
+        }
+
+        let mut findings = Vec::new();
+        for variants in spellings.values().filter(|v| v.len() > 1) {
+            // Most-used spelling wins; ties go to the lowercase one, then alphabetical
+            let canonical = variants
+                .iter()
+                .max_by_key(|(tag, notes)| {
+                    (
+                        notes.len(),
+                        tag.chars().all(|c| !c.is_uppercase()),
+                        std::cmp::Reverse(**tag),
+                    )
+                })
+                .map(|(tag, _)| *tag)
+                .unwrap_or_default();
+
+            for (tag, notes) in variants.iter().filter(|(tag, _)| **tag != canonical) {
+                for &i in notes {
+                    let note = &ctx.notes[i];
+                    let ranges = tag_ranges(note, tag);
+                    let (line, column) = ranges
+                        .first()
+                        .map_or((1, 1), |r| position(&note.content, r.start));
+                    let fix = ranges
+                        .into_iter()
+                        .map(|range| TextEdit::new(range, canonical))
+                        .collect();
+                    findings.push(
+                        Finding::new(
+                            &note.path,
+                            line,
+                            column,
+                            format!("tag #{} differs only in case from #{}", tag, canonical),
+                        )
+                        .with_fix(fix),
+                    );
+                }
+            }
+        }
+        findings
+    }
+}
+
+static KEBAB_CASE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9]+(?:-[a-z0-9]+)*$").unwrap());
+
+/// File name that isn't lowercase words joined by hyphens.
+pub struct KebabCase;
+
+impl Rule for KebabCase {
+    fn name(&self) -> &'static str {
+        "kebab-case"
+    }
+
+    fn description(&self) -> &'static str {
+        "file name isn't kebab-case (lowercase words joined by hyphens)"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, ctx: &LintContext) -> Vec<Finding> {
+        ctx.notes
+            .iter()
+            .filter(|n| {
+                let stem = n.path.file_stem().unwrap_or_default().to_string_lossy();
+                !KEBAB_CASE.is_match(&stem)
+            })
+            .map(|n| {
+                let name = n.path.file_name().unwrap_or_default().to_string_lossy();
+                Finding::new(
+                    &n.path,
+                    1,
+                    1,
+                    format!("file name '{}' is not kebab-case", name),
+                )
+            })
+            .collect()
+    }
+}
+
+fn is_empty(note: &LintNote) -> bool {
+    note.content
+        .lines()
+        .skip(note.body_start - 1)
+        .all(|line| line.trim().is_empty())
+}
+
+/// Line of the heading the title came from, or the frontmatter `title:` line.
+fn title_line(note: &LintNote) -> usize {
+    let from_frontmatter = note
+        .content
+        .lines()
+        .take(note.body_start - 1)
+        .position(|line| line.trim_start().starts_with("title:"));
+    match from_frontmatter {
+        Some(i) => i + 1,
+        None => note.parsed.headings.first().map_or(1, |h| h.line),
+    }
+}
+
+/// Heading text for a note without one: the file name with separators as
+/// spaces and the first letter capitalized (`search-flow.md` → "Search flow").
+fn title_from_filename(path: &Path) -> String {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let words: Vec<&str> = stem
+        .split(['-', '_', ' '])
+        .filter(|w| !w.is_empty())
+        .collect();
+    let title = words.join(" ");
+    let mut chars = title.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => title,
+    }
+}
+
+/// Heading levels with skipped levels closed up. Each heading ends up one
+/// level below the heading it's nested under; headings that don't skip a
+/// level keep theirs.
+fn normalized_levels(headings: &[Heading]) -> Vec<u8> {
+    // (original level, new level) of the headings enclosing the current one
+    let mut stack: Vec<(u8, u8)> = Vec::new();
+    let mut levels = Vec::with_capacity(headings.len());
+    for heading in headings {
+        while stack.last().is_some_and(|&(orig, _)| orig >= heading.level) {
+            stack.pop();
+        }
+        let level = stack
+            .last()
+            .map_or(heading.level, |&(_, parent)| heading.level.min(parent + 1));
+        stack.push((heading.level, level));
+        levels.push(level);
+    }
+    levels
+}
+
+/// Replace the `#` markers of an ATX heading. None for setext headings.
+fn heading_level_edit(content: &str, heading: &Heading, level: u8) -> Option<TextEdit> {
+    let range = line_range(content, heading.line)?;
+    let line = &content[range.clone()];
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let markers = line[indent..].len() - line[indent..].trim_start_matches('#').len();
+    if markers != heading.level as usize {
+        return None;
+    }
+    let start = range.start + indent;
+    Some(TextEdit::new(
+        start..start + markers,
+        "#".repeat(level as usize),
+    ))
+}
+
+/// The single note whose path ends in something within a small edit distance
+/// of `target` (compared over as many path components as the target has).
+fn close_match(target: &str, all_notes: &HashSet<PathBuf>) -> Option<PathBuf> {
+    let target = target.trim_end_matches(".md").to_lowercase();
+    let depth = target.split('/').count();
+    let max_distance = (target.chars().count() / 4).max(1);
+
+    let mut matches = all_notes.iter().filter(|note| {
+        let name = note.with_extension("").to_string_lossy().to_lowercase();
+        let components: Vec<&str> = name.split('/').collect();
+        let tail = components[components.len().saturating_sub(depth)..].join("/");
+        strsim::levenshtein(&tail, &target) <= max_distance
+    });
+
+    let first = matches.next()?;
+    matches.next().is_none().then(|| first.clone())
+}
+
+/// Byte ranges of `tag` (without `#`) in a note: inline `#tag`s outside code
+/// first, then entries of the frontmatter `tags` list.
+fn tag_ranges(note: &LintNote, tag: &str) -> Vec<Range<usize>> {
+    let inline = note
+        .parsed
+        .tag_occurrences
+        .iter()
+        .filter(|t| t.tag == tag)
+        .map(|t| t.range.clone());
+    let frontmatter = frontmatter::value_ranges(&note.content, &["tags", "tag"])
+        .into_iter()
+        .filter(|(value, _)| value == tag)
+        .map(|(_, range)| range);
+    inline.chain(frontmatter).collect()
+}
+
+/// 1-indexed line and column of a byte offset. Inline tags point at their `#`.
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = content[..offset].matches('\n').count() + 1;
+    let column = offset - line_start;
+    // Back up over the `#` of an inline tag
+    if content[..offset].ends_with('#') {
+        (line, column)
+    } else {
+        (line, column + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::links::resolve::AliasMap;
+    use crate::lint::LintContext;
+    use crate::parser::{MarkdownParser, TreeSitterParser};
+
+    fn context(notes: &[(&str, &str)]) -> LintContext {
+        let mut parser = TreeSitterParser::new().unwrap();
+        let notes: Vec<LintNote> = notes
+            .iter()
+            .map(|(path, content)| LintNote {
+                path: PathBuf::from(path),
+                content: content.to_string(),
+                parsed: parser.parse(content).unwrap(),
+                body_start: crate::parser::frontmatter::split(content).map_or(1, |(_, n)| n + 1),
+            })
+            .collect();
+        LintContext {
+            all_notes: notes.iter().map(|n| n.path.clone()).collect(),
+            notes,
+            aliases: AliasMap::new(),
+        }
+    }
+
+    fn locations(findings: &[Finding]) -> Vec<(String, usize, usize)> {
+        findings
+            .iter()
+            .map(|f| (f.path.display().to_string(), f.line, f.column))
+            .collect()
+    }
+
+    #[test]
+    fn test_heading_jump() {
+        let ctx = context(&[("a.md", "# A\n\n### Too deep\n\n## Fine\n\n### Fine too\n")]);
+        let findings = HeadingJump.check(&ctx);
+        assert_eq!(locations(&findings), vec![("a.md".into(), 3, 1)]);
+        assert_eq!(findings[0].message, "heading level jumps from H1 to H3");
+    }
+
+    #[test]
+    fn test_broken_link_column() {
+        let ctx = context(&[
+            ("a.md", "# A\n\nSee [[b]] and [[missing]].\n"),
+            ("b.md", "# B\n"),
+        ]);
+        let findings = BrokenLink.check(&ctx);
+        assert_eq!(locations(&findings), vec![("a.md".into(), 3, 15)]);
+        assert_eq!(findings[0].message, "broken link [[missing]]");
+    }
+
+    #[test]
+    fn test_tag_case_reports_minority_spelling() {
+        let ctx = context(&[
+            ("a.md", "# A\n\n#rust\n"),
+            ("b.md", "# B\n\n#rust\n"),
+            ("c.md", "# C\n\nLearning #Rust today\n"),
+        ]);
+        let findings = TagCase.check(&ctx);
+        assert_eq!(locations(&findings), vec![("c.md".into(), 3, 10)]);
+        assert_eq!(
+            findings[0].message,
+            "tag #Rust differs only in case from #rust"
+        );
+    }
+
+    #[test]
+    fn test_title_rules() {
+        let ctx = context(&[
+            ("a.md", "---\ntitle: Same\n---\nbody\n"),
+            ("b.md", "# same\n"),
+            ("c.md", "no heading\n"),
+            ("d.md", "---\ntags: [x]\n---\n\n"),
+        ]);
+        assert_eq!(
+            locations(&DuplicateTitle.check(&ctx)),
+            vec![("a.md".into(), 2, 1), ("b.md".into(), 1, 1)]
+        );
+        assert_eq!(
+            locations(&MissingTitle.check(&ctx)),
+            vec![("c.md".into(), 1, 1)]
+        );
+        assert_eq!(
+            locations(&EmptyNote.check(&ctx)),
+            vec![("d.md".into(), 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_heading_jump_fix_keeps_nesting() {
+        let content = "# A\n\n### B\n\n#### C\n\n### D\n\n# E\n\n#### F\n";
+        let ctx = context(&[("a.md", content)]);
+        let findings = HeadingJump.check(&ctx);
+        assert_eq!(findings.len(), 2);
+
+        let edits: Vec<_> = findings.iter().flat_map(|f| f.fix.clone()).collect();
+        assert_eq!(
+            crate::edit::apply_edits(content, &edits).unwrap(),
+            "# A\n\n## B\n\n### C\n\n## D\n\n# E\n\n## F\n"
+        );
+    }
+
+    #[test]
+    fn test_close_match_must_be_unique() {
+        let all: HashSet<PathBuf> = ["lucene/codecs.md", "lucene/search-flow.md", "rust/codec.md"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(
+            close_match("serch-flow", &all),
+            Some(PathBuf::from("lucene/search-flow.md"))
+        );
+        assert_eq!(
+            close_match("lucene/codes", &all),
+            Some(PathBuf::from("lucene/codecs.md"))
+        );
+        // codecs and codec are both one edit away
+        assert_eq!(close_match("codecz", &all), None);
+        assert_eq!(close_match("unrelated", &all), None);
+    }
+
+    #[test]
+    fn test_tag_case_fix_skips_code() {
+        let content = "---\ntags:\n  - Rust\n  - other\n---\n# A\n\n#Rust and `#Rust` and #Rust/sub\n\n```\n#Rust\n```\n";
+        let ctx = context(&[
+            ("a.md", content),
+            ("b.md", "# B\n\n#rust\n"),
+            ("c.md", "# C\n\n#rust\n"),
+        ]);
+        let findings = TagCase.check(&ctx);
+        assert_eq!(locations(&findings), vec![("a.md".into(), 8, 1)]);
+        assert_eq!(
+            crate::edit::apply_edits(content, &findings[0].fix).unwrap(),
+            content
+                .replacen("  - Rust", "  - rust", 1)
+                .replacen("#Rust and", "#rust and", 1)
+        );
+    }
+
+    #[test]
+    fn test_kebab_case() {
+        let ctx = context(&[
+            ("lucene/search-flow.md", "# A\n"),
+            ("lucene/01-home.md", "# B\n"),
+            ("lucene/Search Flow.md", "# C\n"),
+            ("lucene/search_flow.md", "# D\n"),
+        ]);
+        let paths: Vec<String> = KebabCase
+            .check(&ctx)
+            .iter()
+            .map(|f| f.path.display().to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["lucene/Search Flow.md", "lucene/search_flow.md"]
+        );
+    }
+}
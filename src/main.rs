@@ -1,10 +1,20 @@
 mod commands;
 mod config;
+mod context;
 mod domains;
+mod duplicates;
+mod edit;
+mod exclude;
 mod links;
+mod lint;
+mod metadata;
 mod output;
 mod parser;
+mod query;
+mod related;
+mod search;
 mod tags;
+mod tasks;
 mod vault;
 
 use anyhow::Result;
@@ -14,6 +24,8 @@ use clap::{Parser, Subcommand, ValueEnum};
 pub enum IndexType {
     Tags,
     Links,
+    Metadata,
+    Tasks,
     Search,
 }
 
@@ -23,6 +35,35 @@ pub enum SortBy {
     Count,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum DueFilter {
+    /// Due today
+    Today,
+    /// Due before today
+    Overdue,
+    /// Due this week (Monday to Sunday)
+    ThisWeek,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum TaskSort {
+    /// Note path, then line
+    Path,
+    /// Due date, earliest first; undated tasks last
+    Due,
+    /// Priority, highest first
+    Priority,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum PriorityArg {
+    Highest,
+    High,
+    Medium,
+    Low,
+    Lowest,
+}
+
 #[derive(Parser)]
 #[command(
     name = "kbase",
@@ -55,19 +96,35 @@ pub enum Command {
         sort: SortBy,
     },
 
-    /// List notes (all, or filtered by domain or search term)
+    /// List notes (all, or filtered by domain, tag or search term)
     Notes {
-        /// Show only notes in this domain
-        #[arg(long)]
-        domain: Option<String>,
+        /// Show only notes in these domains (e.g. `kbase notes lucene elasticsearch`)
+        #[arg(value_name = "DOMAIN")]
+        domains: Vec<String>,
+
+        /// Show only notes in this domain (repeatable)
+        #[arg(long = "domain", value_name = "DOMAIN")]
+        domain: Vec<String>,
 
         /// Filter by name/title match
         #[arg(long)]
         term: Option<String>,
 
-        /// Show only notes with this tag
-        #[arg(long)]
-        tag: Option<String>,
+        /// Show only notes with this tag or one nested under it (repeatable, all must match)
+        #[arg(long = "tag", visible_alias = "all-tags", value_name = "TAG", value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Show only notes with at least one of these tags (repeatable)
+        #[arg(long = "any-tag", value_name = "TAG", value_delimiter = ',')]
+        any_tags: Vec<String>,
+
+        /// Leave out notes with this tag (repeatable)
+        #[arg(long = "not-tag", value_name = "TAG", value_delimiter = ',')]
+        not_tags: Vec<String>,
+
+        /// Filter by metadata field: key=value or key!=value (repeatable)
+        #[arg(long = "where", value_name = "FILTER")]
+        filters: Vec<String>,
 
         /// Show filenames only, no titles
         #[arg(long)]
@@ -76,23 +133,229 @@ pub enum Command {
 
     /// Print a note's content (raw markdown or heading outline)
     Read {
-        /// Vault-relative path with .md extension (e.g. lucene/search-flow.md)
+        /// Note path or name (e.g. lucene/search-flow.md, lucene/search-flow, search-flow)
         path: String,
 
         /// Print heading outline only, indented by level
         #[arg(long)]
         outline: bool,
 
+        /// Print only this section and its subsections; nest with '/' (e.g. "Search Flow/Scoring"), or '#' when a heading contains '/'
+        #[arg(long)]
+        section: Option<String>,
+
+        /// Inline the content of linked notes (or linked #sections) after the note
+        #[arg(long, conflicts_with = "outline")]
+        expand_links: bool,
+
+        /// How many hops of links to follow with --expand-links
+        #[arg(long, default_value_t = 1, requires = "expand_links")]
+        depth: usize,
+
         /// Show line numbers (cat -n style)
         #[arg(short = 'n', long = "line-numbers")]
         line_numbers: bool,
     },
 
+    /// List tasks (`- [ ]` items) across the vault
+    Tasks {
+        /// Show completed tasks only
+        #[arg(long, conflicts_with = "all")]
+        done: bool,
+
+        /// Show tasks of every status (todo, done, cancelled, question)
+        #[arg(long)]
+        all: bool,
+
+        /// Show only tasks in this domain
+        #[arg(long)]
+        domain: Option<String>,
+
+        /// Show only tasks in this note (e.g. lucene/03-task-board.md)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Show only tasks with a 📅 due date in this range
+        #[arg(long, value_enum)]
+        due: Option<DueFilter>,
+
+        /// Show only tasks at or above this priority
+        #[arg(long, value_enum)]
+        priority: Option<PriorityArg>,
+
+        /// Order of the listed tasks
+        #[arg(long, default_value_t = TaskSort::Path, value_enum)]
+        sort: TaskSort,
+
+        /// Group tasks by note and heading
+        #[arg(long)]
+        verbose: bool,
+
+        /// Print only the number of matching tasks
+        #[arg(long)]
+        total: bool,
+    },
+
+    /// Edit tasks in place
+    Task {
+        #[command(subcommand)]
+        action: TaskAction,
+    },
+
+    /// Run a Dataview-style TABLE query over note metadata
+    Query {
+        /// Query, e.g. 'TABLE status FROM domain:lucene WHERE status != "done" SORT status'
+        query: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Gather a note, its links and matching sections into a token-budgeted bundle
+    Context {
+        /// Seed note (path or name, e.g. search-flow) or search terms
+        target: String,
+
+        /// Token budget for the bundle (estimated at ~4 characters per token)
+        #[arg(long, default_value_t = 8000)]
+        max_tokens: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Search note sections by keyword (BM25) or semantic similarity
+    Search {
+        /// Search terms
+        query: String,
+
+        /// Rank by semantic similarity using the search index (run `kbase index` first)
+        #[arg(long)]
+        semantic: bool,
+
+        /// Blend in BM25 with this weight, from 0 (semantic only) to 1 (BM25 only)
+        #[arg(long, requires = "semantic", value_name = "WEIGHT")]
+        blend: Option<f64>,
+
+        /// Only search notes in this domain
+        #[arg(long)]
+        domain: Option<String>,
+
+        /// Also search daily logs in _logs/ (left out by default)
+        #[arg(long)]
+        logs: bool,
+
+        /// Maximum number of notes to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Find notes and sections with copied or overlapping text
+    Duplicates {
+        /// Minimum similarity (shared word 5-grams, 0-1) to report
+        #[arg(long, default_value_t = 0.5)]
+        threshold: f64,
+
+        /// Only compare notes in this domain
+        #[arg(long)]
+        domain: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check notes for structural problems (missing titles, broken links, ...)
+    Lint {
+        /// Only run these rules (repeatable)
+        #[arg(long = "rule", value_name = "NAME")]
+        rules: Vec<String>,
+
+        /// Exit non-zero on warnings too, not just errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Apply safe fixes (tag case, missing H1, close-match links, heading levels)
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, print a unified diff of the fixes without touching any files
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+
+        /// List the available rules and their severities, then exit
+        #[arg(long)]
+        list_rules: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Recommend notes related to a note, with the reasons for each
+    Related {
+        /// Note path or name (e.g. lucene/search-flow.md, search-flow)
+        note: String,
+
+        /// Maximum number of notes to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+
+        /// Leave out notes already linked to or from this note
+        #[arg(long)]
+        unlinked: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List notes with a tag, or rename and merge tags across the vault
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Tag {
+        /// Tag name, `#` optional (e.g. wip); includes tags nested under it
+        #[arg(required = true)]
+        name: Option<String>,
+
+        /// Show each line the tag appears on
+        #[arg(long)]
+        verbose: bool,
+
+        #[command(subcommand)]
+        action: Option<TagAction>,
+    },
+
     /// List all tags
     Tags {
         /// Field to sort by
         #[arg(long, default_value_t = SortBy::Name, value_enum)]
         sort: SortBy,
+
+        /// Show nested tags (`lang/rust`) as a tree, with counts rolled up
+        #[arg(long)]
+        tree: bool,
+
+        /// Only count notes in this domain (repeatable)
+        #[arg(long = "domain", value_name = "DOMAIN")]
+        domains: Vec<String>,
+
+        /// Show a tag-by-domain table of note counts
+        #[arg(long, conflicts_with = "tree")]
+        by_domain: bool,
+
+        /// Show the tags used on the same notes as this one, with lift and PMI
+        #[arg(long, value_name = "TAG", conflicts_with_all = ["tree", "by_domain"])]
+        cooccur: Option<String>,
+
+        /// Export every co-occurring pair of tags as CSV (tag,other,notes,lift,pmi)
+        #[arg(long, conflicts_with_all = ["tree", "by_domain", "cooccur"])]
+        cooccur_matrix: bool,
     },
 
     /// Show links for a note (forward, backward, or both)
@@ -113,14 +376,96 @@ pub enum Command {
         json: bool,
     },
 
+    /// Move or rename a note, rewriting every link to it
+    Mv {
+        /// Current note path (e.g. lucene/search-flow.md)
+        old: String,
+
+        /// New note path or folder (e.g. lucene/query/search-flow.md)
+        new: String,
+
+        /// Print a unified diff of the changes without touching any files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Rename a heading and update every [[note#Heading]] link to it
+    RenameHeading {
+        /// Note path (e.g. lucene/search-flow.md)
+        note: String,
+
+        /// Current heading text
+        old: String,
+
+        /// New heading text
+        new: String,
+
+        /// Print a unified diff of the changes without touching any files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Build search and tag indexes
     Index {
-        /// Build only specific indexes (tags, links, search). Default: build all
+        /// Build only specific indexes (tags, links, metadata, tasks, search). Default: build all
         #[arg(long, value_enum)]
         only: Vec<IndexType>,
     },
 }
 
+#[derive(Subcommand)]
+pub enum TaskAction {
+    /// Mark a task done
+    Done {
+        /// Task location as <note>:<line> (e.g. lucene/03-task-board.md:12)
+        location: String,
+    },
+
+    /// Add a task to a note
+    Add {
+        /// Note path (e.g. lucene/03-task-board.md)
+        note: String,
+
+        /// Task text
+        text: String,
+
+        /// Add under this heading (e.g. "## This Week"); defaults to the end of the note
+        #[arg(long)]
+        under: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagAction {
+    /// Rename a tag (and tags nested under it) in every note
+    Rename {
+        /// Current tag (e.g. wip)
+        old: String,
+
+        /// New tag (e.g. in-progress)
+        new: String,
+
+        /// Print a unified diff of the changes without touching any files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Merge several tags into one
+    Merge {
+        /// Tags to merge (e.g. wip WIP in-progress)
+        #[arg(required = true)]
+        tags: Vec<String>,
+
+        /// Tag to merge them into
+        #[arg(long)]
+        into: String,
+
+        /// Print a unified diff of the changes without touching any files
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 fn main() {
     if let Err(e) = run() {
         eprintln!("Error: {e:#}");
@@ -0,0 +1,134 @@
+//! `key=value` / `key!=value` filters over note metadata (`kbase notes --where`).
+
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+
+use crate::parser::Metadata;
+use crate::parser::inline_fields::normalize_key;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    NotEq,
+}
+
+/// A single metadata condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldFilter {
+    pub key: String,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+impl FromStr for FieldFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (key, op, value) = if let Some((k, v)) = s.split_once("!=") {
+            (k, FilterOp::NotEq, v)
+        } else if let Some((k, v)) = s.split_once('=') {
+            (k, FilterOp::Eq, v)
+        } else {
+            bail!("Invalid filter '{}': expected key=value or key!=value", s);
+        };
+
+        let key = normalize_key(key);
+        if key.is_empty() {
+            bail!("Invalid filter '{}': missing key", s);
+        }
+
+        Ok(Self {
+            key,
+            op,
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+impl FieldFilter {
+    /// Whether a note's metadata satisfies this filter.
+    ///
+    /// Keys are compared after normalisation (`Due Date` = `due-date`). A value
+    /// matches if it equals the needle ignoring case, or starts with it at a
+    /// word boundary, so `status=Learning` matches `Learning - Phase 2B`. For
+    /// lists any item may match. `!=` also matches notes without the key.
+    pub fn matches(&self, metadata: Option<&Metadata>) -> bool {
+        let found = metadata
+            .into_iter()
+            .flat_map(|m| m.iter())
+            .filter(|(key, _)| normalize_key(key) == self.key)
+            .flat_map(|(_, value)| value.as_strings())
+            .any(|value| value_matches(&value, &self.value));
+
+        match self.op {
+            FilterOp::Eq => found,
+            FilterOp::NotEq => !found,
+        }
+    }
+}
+
+fn value_matches(value: &str, needle: &str) -> bool {
+    let value = value.trim().to_lowercase();
+    let needle = needle.to_lowercase();
+
+    match value.strip_prefix(&needle) {
+        Some(rest) => rest.chars().next().is_none_or(|c| !c.is_alphanumeric()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::MetaValue;
+
+    fn metadata(pairs: &[(&str, MetaValue)]) -> Metadata {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_filters() {
+        let filter: FieldFilter = "Due Date=tomorrow".parse().unwrap();
+        assert_eq!(filter.key, "due-date");
+        assert_eq!(filter.op, FilterOp::Eq);
+        assert_eq!(filter.value, "tomorrow");
+
+        let filter: FieldFilter = "status!=done".parse().unwrap();
+        assert_eq!(filter.op, FilterOp::NotEq);
+
+        assert!("status".parse::<FieldFilter>().is_err());
+        assert!("=done".parse::<FieldFilter>().is_err());
+    }
+
+    #[test]
+    fn test_value_prefix_matching() {
+        let meta = metadata(&[("status", MetaValue::Text("Learning - Phase 2B".into()))]);
+        let eq = |s: &str| s.parse::<FieldFilter>().unwrap().matches(Some(&meta));
+
+        assert!(eq("status=learning"));
+        assert!(eq("status=Learning - Phase 2B"));
+        assert!(!eq("status=learn"));
+        assert!(!eq("status=done"));
+    }
+
+    #[test]
+    fn test_lists_and_missing_keys() {
+        let meta = metadata(&[(
+            "tags",
+            MetaValue::List(vec![
+                MetaValue::Text("rust".into()),
+                MetaValue::Text("wip".into()),
+            ]),
+        )]);
+        let filter = |s: &str| s.parse::<FieldFilter>().unwrap();
+
+        assert!(filter("tags=wip").matches(Some(&meta)));
+        assert!(filter("status!=done").matches(Some(&meta)));
+        assert!(filter("status!=done").matches(None));
+        assert!(!filter("status=done").matches(None));
+    }
+}
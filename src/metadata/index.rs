@@ -0,0 +1,113 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::Path;
+
+use crate::parser::{MarkdownParser, Metadata, TreeSitterParser};
+use crate::vault::Vault;
+
+/// Index of structured note metadata.
+/// Stores note path -> metadata fields on disk; notes without metadata are omitted.
+pub struct MetadataIndex {
+    by_note: BTreeMap<String, Metadata>,
+}
+
+impl MetadataIndex {
+    /// Build MetadataIndex from a path->metadata mapping.
+    pub fn from_map(mut by_note: BTreeMap<String, Metadata>) -> Self {
+        by_note.retain(|_, metadata| !metadata.is_empty());
+        MetadataIndex { by_note }
+    }
+
+    /// Build a MetadataIndex by scanning all notes in a vault.
+    pub fn build_from_vault(vault: &Vault) -> Result<Self> {
+        let mut by_note = BTreeMap::new();
+        let mut parser = TreeSitterParser::new()?;
+
+        for note in vault.all_notes()? {
+            let path_str = note.path.to_string_lossy().to_string();
+            let content = vault.read_note(&path_str)?;
+            let parsed = parser.parse(&content)?;
+            by_note.insert(path_str, parsed.metadata);
+        }
+
+        Ok(MetadataIndex::from_map(by_note))
+    }
+
+    /// Load MetadataIndex from JSON file.
+    pub fn load_from_json(json_path: &Path) -> Result<Self> {
+        let file = File::open(json_path)?;
+        let by_note = serde_json::from_reader(file)?;
+        Ok(Self::from_map(by_note))
+    }
+
+    /// Save MetadataIndex to JSON file atomically.
+    pub fn save_to_json(&self, json_path: &Path) -> Result<()> {
+        if let Some(parent) = json_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let temp_path = json_path.with_extension("json.tmp");
+        let file = File::create(&temp_path)?;
+        serde_json::to_writer_pretty(file, &self.by_note)?;
+        fs::rename(temp_path, json_path)?;
+
+        Ok(())
+    }
+
+    /// Number of notes that have metadata.
+    pub fn note_count(&self) -> usize {
+        self.by_note.len()
+    }
+
+    /// Get the metadata for a note, if it has any.
+    pub fn get(&self, path: &str) -> Option<&Metadata> {
+        self.by_note.get(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::MetaValue;
+    use tempfile::tempdir;
+
+    fn metadata(pairs: &[(&str, &str)]) -> Metadata {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), MetaValue::Text(v.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_map_drops_empty_metadata() {
+        let mut map = BTreeMap::new();
+        map.insert("a.md".to_string(), metadata(&[("status", "draft")]));
+        map.insert("b.md".to_string(), Metadata::new());
+
+        let index = MetadataIndex::from_map(map);
+        assert_eq!(index.note_count(), 1);
+        assert!(index.get("b.md").is_none());
+    }
+
+    #[test]
+    fn test_save_and_load_json() -> Result<()> {
+        let mut map = BTreeMap::new();
+        let mut fields = metadata(&[("title", "Codecs")]);
+        fields.insert("rating".into(), MetaValue::Number(4.0));
+        fields.insert(
+            "tags".into(),
+            MetaValue::List(vec![MetaValue::Text("lucene".into())]),
+        );
+        map.insert("lucene/codecs.md".to_string(), fields.clone());
+
+        let temp_dir = tempdir()?;
+        let json_path = temp_dir.path().join("metadata.json");
+        MetadataIndex::from_map(map).save_to_json(&json_path)?;
+
+        let loaded = MetadataIndex::load_from_json(&json_path)?;
+        assert_eq!(loaded.get("lucene/codecs.md"), Some(&fields));
+
+        Ok(())
+    }
+}
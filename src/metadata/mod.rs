@@ -0,0 +1,7 @@
+// Public interface for note metadata (frontmatter and inline fields) functionality
+
+pub mod filter;
+pub mod index;
+
+pub use filter::FieldFilter;
+pub use index::MetadataIndex;
@@ -3,19 +3,11 @@
 /// The left column width is calculated to fit both the header and all data rows.
 /// Columns are separated by two spaces.
 pub fn print_table(headers: (&str, &str), rows: &[(String, String)]) {
-    let (left_header, right_header) = headers;
-
-    // Calculate max width needed for left column (header or data)
-    let data_width = rows.iter().map(|(left, _)| left.len()).max().unwrap_or(0);
-    let width = data_width.max(left_header.len());
-
-    // Print header
-    println!("{:<width$}  {}", left_header, right_header, width = width);
-
-    // Print rows
-    for (left, right) in rows {
-        println!("{:<width$}  {}", left, right, width = width);
-    }
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(left, right)| vec![left.clone(), right.clone()])
+        .collect();
+    print_table_n(&[headers.0, headers.1], &rows);
 }
 
 /// Print a three-column table with headers and aligned columns.
@@ -23,27 +15,49 @@ pub fn print_table(headers: (&str, &str), rows: &[(String, String)]) {
 /// Column widths are calculated to fit headers and all data rows.
 /// Columns are separated by two spaces.
 pub fn print_table3(headers: (&str, &str, &str), rows: &[(String, String, String)]) {
-    let (h1, h2, h3) = headers;
-
-    // Calculate max width needed for each column
-    let w1 = rows
+    let rows: Vec<Vec<String>> = rows
         .iter()
-        .map(|(c1, _, _)| c1.len())
-        .max()
-        .unwrap_or(0)
-        .max(h1.len());
-    let w2 = rows
+        .map(|(c1, c2, c3)| vec![c1.clone(), c2.clone(), c3.clone()])
+        .collect();
+    print_table_n(&[headers.0, headers.1, headers.2], &rows);
+}
+
+/// Print a table with any number of columns.
+///
+/// Every column but the last is padded to fit its header and data.
+/// Columns are separated by two spaces.
+pub fn print_table_n(headers: &[&str], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = headers
         .iter()
-        .map(|(_, c2, _)| c2.len())
-        .max()
-        .unwrap_or(0)
-        .max(h2.len());
+        .enumerate()
+        .map(|(i, header)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(header.chars().count())
+        })
+        .collect();
 
-    // Print header
-    println!("{:<w1$}  {:<w2$}  {}", h1, h2, h3, w1 = w1, w2 = w2);
+    let format_row = |cells: Vec<&str>| {
+        let last = cells.len().saturating_sub(1);
+        let padded: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if i == last {
+                    cell.to_string()
+                } else {
+                    format!("{:<width$}", cell, width = widths[i])
+                }
+            })
+            .collect();
+        padded.join("  ")
+    };
 
-    // Print rows
-    for (c1, c2, c3) in rows {
-        println!("{:<w1$}  {:<w2$}  {}", c1, c2, c3, w1 = w1, w2 = w2);
+    println!("{}", format_row(headers.to_vec()));
+    for row in rows {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
     }
 }
@@ -0,0 +1,219 @@
+//! YAML frontmatter (`---` block at the top of a note).
+//!
+//! The tree-sitter block grammar exposes frontmatter as a `minus_metadata` node;
+//! [`parse_yaml`] turns its body into a [`Metadata`] map. [`split`] is a cheap
+//! text-only variant for callers that don't run the full parser (title lookup
+//! while listing notes).
+
+use std::ops::Range;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use super::{MetaValue, Metadata};
+
+/// Split a note into its frontmatter YAML (without the `---` fences) and the
+/// number of lines the whole block occupies. Returns None if the note doesn't
+/// start with a frontmatter block.
+pub fn split(content: &str) -> Option<(&str, usize)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for (i, line) in rest.split_inclusive('\n').enumerate() {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], i + 2));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Strip the `---` fences from a `minus_metadata` node's text.
+pub fn strip_fences(block: &str) -> &str {
+    split(block).map_or("", |(yaml, _)| yaml)
+}
+
+/// Parse a YAML frontmatter body into a metadata map.
+///
+/// Top-level scalars and lists are kept; nested mappings and nulls are dropped.
+/// Invalid YAML yields an empty map rather than failing the whole note.
+pub fn parse_yaml(yaml: &str) -> Metadata {
+    let mut metadata = Metadata::new();
+
+    let Ok(docs) = YamlLoader::load_from_str(yaml) else {
+        return metadata;
+    };
+    let Some(Yaml::Hash(hash)) = docs.into_iter().next() else {
+        return metadata;
+    };
+
+    for (key, value) in hash {
+        let key = match key {
+            Yaml::String(s) => s,
+            Yaml::Integer(i) => i.to_string(),
+            _ => continue,
+        };
+        if let Some(value) = convert(value) {
+            metadata.insert(key, value);
+        }
+    }
+
+    metadata
+}
+
+fn convert(value: Yaml) -> Option<MetaValue> {
+    match value {
+        Yaml::String(s) => Some(MetaValue::Text(s)),
+        Yaml::Integer(i) => Some(MetaValue::Number(i as f64)),
+        Yaml::Real(r) => r.parse().ok().map(MetaValue::Number),
+        Yaml::Boolean(b) => Some(MetaValue::Bool(b)),
+        Yaml::Array(items) => Some(MetaValue::List(
+            items.into_iter().filter_map(convert).collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// Values of a list-like key (`tags`, `aliases`), accepting the singular form
+/// too (`tag`, `alias`). Strings are split on commas, as Obsidian allows
+/// `tags: rust, wip`.
+pub fn list_values(metadata: &Metadata, keys: &[&str]) -> Vec<String> {
+    keys.iter()
+        .filter_map(|key| metadata.get(*key))
+        .flat_map(|value| match value {
+            MetaValue::Text(s) => s.split(',').map(|v| v.trim().to_string()).collect(),
+            other => other.as_strings(),
+        })
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Byte ranges (in the whole note) of each value of a list-like key, for
+/// rewriting them in place. Handles the forms [`list_values`] accepts: flow
+/// lists (`tags: [a, b]`), comma-separated strings and `- item` blocks.
+/// Quotes and a leading `#` are left outside the range.
+pub fn value_ranges(content: &str, keys: &[&str]) -> Vec<(String, Range<usize>)> {
+    let Some((yaml, _)) = split(content) else {
+        return Vec::new();
+    };
+    let mut offset = content.find('\n').map_or(0, |i| i + 1);
+    let mut in_key = false;
+    let mut ranges = Vec::new();
+
+    for line in yaml.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        // Keys start at column 0; list items and continuations are indented
+        let continuation = text.starts_with([' ', '\t', '-']);
+        if !continuation {
+            in_key = text
+                .split_once(':')
+                .is_some_and(|(key, _)| keys.contains(&key.trim()));
+        }
+        if in_key {
+            let start = if continuation {
+                let item = text.trim_start();
+                let item = item.strip_prefix('-').unwrap_or(item);
+                text.len() - item.len()
+            } else {
+                text.find(':').map_or(text.len(), |colon| colon + 1)
+            };
+            ranges.extend(list_items(&text[start..], offset + start));
+        }
+        offset += line.len();
+    }
+    ranges
+}
+
+/// Comma-separated items of `text` (which starts at byte `base`), trimmed of
+/// whitespace, brackets, quotes and a leading `#`.
+fn list_items(text: &str, base: usize) -> Vec<(String, Range<usize>)> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    for part in text.split(',') {
+        let trimmed = part.trim_start_matches([' ', '\t', '[', '"', '\'', '#']);
+        let lead = part.len() - trimmed.len();
+        let value = trimmed.trim_end_matches([' ', '\t', ']', '"', '\'']);
+        if !value.is_empty() {
+            let from = base + start + lead;
+            items.push((value.to_string(), from..from + value.len()));
+        }
+        start += part.len() + 1;
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_frontmatter() {
+        let content = "---\ntitle: Foo\ntags: [a]\n---\n# Body\n";
+        let (yaml, lines) = split(content).unwrap();
+        assert_eq!(yaml, "title: Foo\ntags: [a]\n");
+        assert_eq!(lines, 4);
+    }
+
+    #[test]
+    fn test_split_requires_leading_fence() {
+        assert_eq!(split("# Title\n---\nfoo: bar\n---\n"), None);
+        assert_eq!(split("---\nunterminated: true\n"), None);
+    }
+
+    #[test]
+    fn test_parse_yaml_types() {
+        let metadata = parse_yaml(
+            "title: Codecs\nrating: 4\nscore: 0.5\ndraft: false\ntags:\n  - lucene\n  - wip\nempty:\nnested:\n  a: b\n",
+        );
+
+        assert_eq!(metadata["title"], MetaValue::Text("Codecs".into()));
+        assert_eq!(metadata["rating"], MetaValue::Number(4.0));
+        assert_eq!(metadata["score"], MetaValue::Number(0.5));
+        assert_eq!(metadata["draft"], MetaValue::Bool(false));
+        assert_eq!(
+            metadata["tags"],
+            MetaValue::List(vec![
+                MetaValue::Text("lucene".into()),
+                MetaValue::Text("wip".into())
+            ])
+        );
+        assert!(!metadata.contains_key("empty"));
+        assert!(!metadata.contains_key("nested"));
+    }
+
+    #[test]
+    fn test_parse_invalid_yaml_is_empty() {
+        assert!(parse_yaml("title: [unclosed\n").is_empty());
+        assert!(parse_yaml("just a string").is_empty());
+    }
+
+    #[test]
+    fn test_list_values_accepts_strings_and_singular_keys() {
+        let metadata = parse_yaml("tag: rust, wip\naliases: [Search Flow]\n");
+        assert_eq!(
+            list_values(&metadata, &["tags", "tag"]),
+            vec!["rust", "wip"]
+        );
+        assert_eq!(
+            list_values(&metadata, &["aliases", "alias"]),
+            vec!["Search Flow"]
+        );
+    }
+
+    #[test]
+    fn test_value_ranges_covers_list_forms() {
+        let content = "---\ntitle: x\ntags: [a, \"#b\"]\ntag: c, d\naliases:\n  - e\ntags2:\n  - f\n---\ntags: g\n";
+        let ranges = value_ranges(content, &["tags", "tag"]);
+        let values: Vec<&str> = ranges.iter().map(|(v, _)| v.as_str()).collect();
+        assert_eq!(values, vec!["a", "b", "c", "d"]);
+        for (value, range) in &ranges {
+            assert_eq!(&content[range.clone()], value);
+        }
+
+        let block = "---\ntags:\n  - rust\n  -  wip\n---\n";
+        let ranges = value_ranges(block, &["tags"]);
+        assert_eq!(&block[ranges[1].1.clone()], "wip");
+        assert_eq!(ranges.len(), 2);
+    }
+}
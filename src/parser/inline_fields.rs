@@ -0,0 +1,117 @@
+//! Inline metadata fields written in the note body.
+//!
+//! Two styles are recognised, one field per line:
+//!
+//! ```markdown
+//! **Status:** Learning - Phase 2B      bold label (colon inside or outside)
+//! - **Alignment**: 🟢 Target employer
+//! updated:: 2026-10-01                  Dataview inline field
+//! ```
+//!
+//! Keys are normalised Dataview-style (`Status` → `status`,
+//! `Due Date` → `due-date`); values are kept as text.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+
+use super::{MetaValue, Metadata};
+
+/// `**Key:** value` or `**Key**: value`, optionally inside a list item.
+static BOLD_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:[-*+]\s+)?\*\*([^*:\n]+?)(?::\*\*|\*\*:)\s*(.*?)\s*$").unwrap()
+});
+
+/// `key:: value`, optionally inside a list item. The space after `::` is
+/// required so prose like `std::vec` isn't mistaken for a field.
+static DATAVIEW_FIELD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:[-*+]\s+)?([A-Za-z][\w \-]*?)::(?:\s+(.*?))?\s*$").unwrap());
+
+/// Extract inline fields from `content`, skipping the given 1-indexed lines
+/// (code blocks, frontmatter). The first occurrence of a key wins.
+pub fn extract(content: &str, skip_lines: &HashSet<usize>) -> Metadata {
+    let mut metadata = Metadata::new();
+
+    for (i, line) in content.lines().enumerate() {
+        if skip_lines.contains(&(i + 1)) {
+            continue;
+        }
+
+        let Some(caps) = BOLD_FIELD
+            .captures(line)
+            .or_else(|| DATAVIEW_FIELD.captures(line))
+        else {
+            continue;
+        };
+
+        let key = normalize_key(&caps[1]);
+        let value = caps.get(2).map_or("", |m| m.as_str()).to_string();
+        if key.is_empty() || value.is_empty() {
+            continue;
+        }
+        metadata.entry(key).or_insert(MetaValue::Text(value));
+    }
+
+    metadata
+}
+
+/// Normalise a field name: trimmed, lowercased, inner whitespace as `-`.
+pub fn normalize_key(key: &str) -> String {
+    key.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> MetaValue {
+        MetaValue::Text(s.to_string())
+    }
+
+    #[test]
+    fn test_bold_label_fields() {
+        let content =
+            "# Note\n\n**Status:** Learning - Phase 2B\n- **Alignment**: 🟢 Target employer\n";
+        let metadata = extract(content, &HashSet::new());
+
+        assert_eq!(metadata["status"], text("Learning - Phase 2B"));
+        assert_eq!(metadata["alignment"], text("🟢 Target employer"));
+    }
+
+    #[test]
+    fn test_dataview_fields() {
+        let content =
+            "updated:: 2026-10-01\n- Due Date:: tomorrow\nnot a field: value\nuse std::vec here\n";
+        let metadata = extract(content, &HashSet::new());
+
+        assert_eq!(metadata["updated"], text("2026-10-01"));
+        assert_eq!(metadata["due-date"], text("tomorrow"));
+        assert_eq!(metadata.len(), 2);
+    }
+
+    #[test]
+    fn test_skipped_lines_and_empty_values() {
+        let content = "**Status:** Done\n```\n**Status:** In code\n```\n**Empty:**\n";
+        let skip: HashSet<usize> = [2, 3, 4].into_iter().collect();
+        let metadata = extract(content, &skip);
+
+        assert_eq!(metadata["status"], text("Done"));
+        assert!(!metadata.contains_key("empty"));
+    }
+
+    #[test]
+    fn test_first_occurrence_wins() {
+        let content = "**Status:** First\n**Status:** Second\n";
+        let metadata = extract(content, &HashSet::new());
+        assert_eq!(metadata["status"], text("First"));
+    }
+
+    #[test]
+    fn test_bold_text_without_colon_is_not_a_field() {
+        let content = "**Important** note about codecs\n";
+        assert!(extract(content, &HashSet::new()).is_empty());
+    }
+}
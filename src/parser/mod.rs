@@ -1,9 +1,16 @@
+pub mod frontmatter;
+pub mod inline_fields;
 pub mod queries;
+pub mod sections;
+pub mod task_fields;
 pub mod tree_sitter;
 pub mod types;
 
+pub use task_fields::Priority;
 pub use tree_sitter::TreeSitterParser;
-pub use types::{Heading, ParsedMarkdown, Wikilink};
+pub use types::{
+    Heading, MetaValue, Metadata, ParsedMarkdown, TagOccurrence, Task, TaskStatus, Wikilink,
+};
 
 use anyhow::Result;
 
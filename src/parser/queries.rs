@@ -6,6 +6,9 @@ pub struct Queries {
     pub wikilinks: Query,
     pub tags: Query,
     pub headings: Query,
+    pub frontmatter: Query,
+    pub code_blocks: Query,
+    pub list_items: Query,
 }
 
 impl Queries {
@@ -16,11 +19,20 @@ impl Queries {
             .context("Failed to compile tag query")?;
         let headings = Query::new(block_lang, include_str!("queries/headings.scm"))
             .context("Failed to compile heading query")?;
+        let frontmatter = Query::new(block_lang, include_str!("queries/frontmatter.scm"))
+            .context("Failed to compile frontmatter query")?;
+        let code_blocks = Query::new(block_lang, include_str!("queries/code_blocks.scm"))
+            .context("Failed to compile code block query")?;
+        let list_items = Query::new(block_lang, include_str!("queries/list_items.scm"))
+            .context("Failed to compile list item query")?;
 
         Ok(Self {
             wikilinks,
             tags,
             headings,
+            frontmatter,
+            code_blocks,
+            list_items,
         })
     }
 }
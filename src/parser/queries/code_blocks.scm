@@ -0,0 +1,6 @@
+; Extract code blocks (fenced and indented)
+; Used to skip line-based extraction (inline metadata fields) inside code.
+
+(fenced_code_block) @code
+
+(indented_code_block) @code
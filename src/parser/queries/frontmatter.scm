@@ -0,0 +1,5 @@
+; Extract YAML frontmatter
+; Matches the `---` fenced block at the very top of a note.
+; The captured text includes the fences; they are stripped before YAML parsing.
+
+(minus_metadata) @frontmatter
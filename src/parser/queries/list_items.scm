@@ -0,0 +1,5 @@
+; Extract list items
+; Every item is captured, including nested ones; task status is read from the
+; item's first line since the grammar only marks `[ ]` and `[x]`, not `[-]` or `[?]`.
+
+(list_item) @item
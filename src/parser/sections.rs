@@ -0,0 +1,263 @@
+//! Locate note sections by heading path.
+//!
+//! A section runs from its heading to the line before the next heading of the
+//! same or a higher level, so it includes its subsections. Heading paths are
+//! matched loosely: `"search flow/scoring"` finds `## Phase 2: Scoring` under
+//! `# Search Flow Deep Dive`.
+
+use std::ops::RangeInclusive;
+
+use super::Heading;
+
+/// How well a path component matches a heading, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchQuality {
+    Exact,
+    Prefix,
+    Contains,
+    AllWords,
+}
+
+/// Lowercase, with punctuation dropped and whitespace collapsed.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn match_quality(heading: &str, component: &str) -> Option<MatchQuality> {
+    if heading.trim().eq_ignore_ascii_case(component.trim()) {
+        return Some(MatchQuality::Exact);
+    }
+
+    let heading = normalize(heading);
+    let component = normalize(component);
+    if component.is_empty() {
+        return None;
+    }
+
+    if heading == component {
+        Some(MatchQuality::Exact)
+    } else if heading.starts_with(&component) {
+        Some(MatchQuality::Prefix)
+    } else if heading.contains(&component) {
+        Some(MatchQuality::Contains)
+    } else if component
+        .split(' ')
+        .all(|word| heading.split(' ').any(|w| w.starts_with(word)))
+    {
+        Some(MatchQuality::AllWords)
+    } else {
+        None
+    }
+}
+
+/// Index one past the last heading nested under `headings[index]`.
+fn descendants_end(headings: &[Heading], index: usize) -> usize {
+    let level = headings[index].level;
+    headings[index + 1..]
+        .iter()
+        .position(|h| h.level <= level)
+        .map_or(headings.len(), |p| index + 1 + p)
+}
+
+/// Find headings matching a path of components, each nested (at any depth)
+/// under the previous one. Returns indices into `headings`; more than one
+/// means the path is ambiguous, none means no match.
+///
+/// Each component prefers exact matches, then prefix, substring and finally
+/// all-words matches, falling back to a weaker tier only if the stronger one
+/// leads nowhere.
+pub fn find_section(headings: &[Heading], path: &[&str]) -> Vec<usize> {
+    let path: Vec<&str> = path
+        .iter()
+        .map(|c| c.trim())
+        .filter(|c| !c.is_empty())
+        .collect();
+    if path.is_empty() {
+        return Vec::new();
+    }
+    resolve(headings, &path, 0, headings.len())
+}
+
+fn resolve(headings: &[Heading], path: &[&str], start: usize, end: usize) -> Vec<usize> {
+    let scored: Vec<(usize, MatchQuality)> = (start..end)
+        .filter_map(|i| match_quality(&headings[i].text, path[0]).map(|q| (i, q)))
+        .collect();
+
+    for tier in [
+        MatchQuality::Exact,
+        MatchQuality::Prefix,
+        MatchQuality::Contains,
+        MatchQuality::AllWords,
+    ] {
+        let mut found = Vec::new();
+        for &(i, _) in scored.iter().filter(|(_, q)| *q == tier) {
+            if path.len() == 1 {
+                found.push(i);
+            } else {
+                found.extend(resolve(
+                    headings,
+                    &path[1..],
+                    i + 1,
+                    descendants_end(headings, i),
+                ));
+            }
+        }
+        if !found.is_empty() {
+            found.dedup();
+            return found;
+        }
+    }
+
+    Vec::new()
+}
+
+/// 1-indexed line range of the section under `headings[index]`, including
+/// subsections. Trailing blank lines are left out.
+pub fn section_lines(lines: &[&str], headings: &[Heading], index: usize) -> RangeInclusive<usize> {
+    let heading = &headings[index];
+    let mut end = headings[index + 1..]
+        .iter()
+        .find(|h| h.level <= heading.level)
+        .map_or(lines.len(), |h| h.line - 1);
+
+    while end > heading.line && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+
+    heading.line..=end
+}
+
+/// A run of lines between two headings of any level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    /// Index into `headings`, or None for text before the first heading
+    pub heading: Option<usize>,
+    pub lines: RangeInclusive<usize>,
+}
+
+/// Split a note into non-overlapping chunks, one per heading plus any text
+/// before the first heading. Lines before `first_line` (frontmatter) are
+/// skipped, as are chunks with nothing but blank lines.
+pub fn chunks(lines: &[&str], headings: &[Heading], first_line: usize) -> Vec<Chunk> {
+    let mut starts: Vec<(Option<usize>, usize)> = vec![(None, first_line)];
+    starts.extend(
+        headings
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.line >= first_line)
+            .map(|(i, h)| (Some(i), h.line)),
+    );
+
+    let mut result = Vec::new();
+    for (i, &(heading, start)) in starts.iter().enumerate() {
+        let mut end = starts.get(i + 1).map_or(lines.len(), |&(_, next)| next - 1);
+        while end >= start && lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+        if end >= start {
+            result.push(Chunk {
+                heading,
+                lines: start..=end,
+            });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headings() -> Vec<Heading> {
+        [
+            (1, "Search Flow Deep Dive", 1),
+            (2, "Phase 1: IndexSearcher.search()", 5),
+            (3, "Step 1: createWeight()", 7),
+            (3, "Scoring", 9),
+            (2, "Phase 2: Scoring", 11),
+            (1, "Appendix", 14),
+            (2, "Scoring", 15),
+        ]
+        .into_iter()
+        .map(|(level, text, line)| Heading {
+            level,
+            text: text.to_string(),
+            line,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_exact_match_beats_fuzzy() {
+        // "Scoring" exists verbatim twice, so it's ambiguous on its own
+        assert_eq!(find_section(&headings(), &["scoring"]), vec![3, 6]);
+        assert_eq!(find_section(&headings(), &["appendix", "scoring"]), vec![6]);
+    }
+
+    #[test]
+    fn test_nested_fuzzy_path() {
+        let headings = headings();
+        assert_eq!(
+            find_section(&headings, &["search flow", "phase 2"]),
+            vec![4]
+        );
+        assert_eq!(
+            find_section(&headings, &["Search Flow", "createweight"]),
+            vec![2]
+        );
+        assert_eq!(find_section(&headings, &["flow dive", "phase 1"]), vec![1]);
+        assert!(find_section(&headings, &["appendix", "phase 2"]).is_empty());
+        assert!(find_section(&headings, &["missing"]).is_empty());
+    }
+
+    #[test]
+    fn test_section_lines_include_subsections() {
+        let content = "# Search Flow Deep Dive\n\nIntro\n\n## Phase 1: IndexSearcher.search()\n\n### Step 1: createWeight()\n\n### Scoring\n\n## Phase 2: Scoring\nBM25\n\n# Appendix\n## Scoring\n";
+        let lines: Vec<&str> = content.lines().collect();
+        let headings = headings();
+
+        assert_eq!(section_lines(&lines, &headings, 1), 5..=9);
+        assert_eq!(section_lines(&lines, &headings, 4), 11..=12);
+        assert_eq!(section_lines(&lines, &headings, 6), 15..=15);
+    }
+
+    #[test]
+    fn test_chunks_split_at_every_heading() {
+        let content = "---\ntitle: x\n---\nIntro\n\n# A\ntext\n\n## B\n\n## C\nmore\n\n";
+        let lines: Vec<&str> = content.lines().collect();
+        let headings: Vec<Heading> = [(1, "A", 6), (2, "B", 9), (2, "C", 11)]
+            .into_iter()
+            .map(|(level, text, line)| Heading {
+                level,
+                text: text.to_string(),
+                line,
+            })
+            .collect();
+
+        let chunks = chunks(&lines, &headings, 4);
+        let ranges: Vec<_> = chunks
+            .iter()
+            .map(|c| (c.heading, c.lines.clone()))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (None, 4..=4),
+                (Some(0), 6..=7),
+                (Some(1), 9..=9),
+                (Some(2), 11..=12)
+            ]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Obsidian Tasks plugin annotations on a task line.
+//!
+//! ```markdown
+//! - [ ] Review codecs ⏫ 🔁 every week 📅 2026-10-20
+//! ```
+//!
+//! | Emoji          | Field       |
+//! |----------------|-------------|
+//! | 📅 📆 🗓        | due         |
+//! | ⏳              | scheduled   |
+//! | 🛫              | start       |
+//! | ✅              | completed   |
+//! | 🔁              | recurrence  |
+//! | 🔺 ⏫ 🔼 🔽 ⏬   | priority    |
+
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Task priority, highest first (the derived order sorts highest first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Highest,
+    High,
+    Medium,
+    Low,
+    Lowest,
+}
+
+impl Priority {
+    /// Sort rank, where a task without a priority counts as "normal",
+    /// between medium and low (as in Obsidian Tasks).
+    pub fn rank(priority: Option<Priority>) -> u8 {
+        match priority {
+            Some(Priority::Highest) => 0,
+            Some(Priority::High) => 1,
+            Some(Priority::Medium) => 2,
+            None => 3,
+            Some(Priority::Low) => 4,
+            Some(Priority::Lowest) => 5,
+        }
+    }
+
+    fn from_emoji(emoji: char) -> Option<Self> {
+        match emoji {
+            '🔺' => Some(Priority::Highest),
+            '⏫' => Some(Priority::High),
+            '🔼' => Some(Priority::Medium),
+            '🔽' => Some(Priority::Low),
+            '⏬' => Some(Priority::Lowest),
+            _ => None,
+        }
+    }
+}
+
+/// Typed annotations parsed from a task's text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskFields {
+    pub priority: Option<Priority>,
+    pub due: Option<NaiveDate>,
+    pub scheduled: Option<NaiveDate>,
+    pub start: Option<NaiveDate>,
+    pub completed: Option<NaiveDate>,
+    pub recurrence: Option<String>,
+}
+
+/// `<emoji> YYYY-MM-DD`, allowing the emoji variation selector.
+static DATE_FIELD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(📅|📆|🗓|⏳|🛫|✅)\x{FE0F}?\s*(\d{4}-\d{2}-\d{2})").unwrap());
+
+/// `🔁 <rule>`, up to the next annotation or tag.
+static RECURRENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"🔁\x{FE0F}?\s*([^📅📆🗓⏳🛫✅➕❌🔺⏫🔼🔽⏬#]+)").unwrap());
+
+/// Parse Tasks plugin annotations. Unknown or malformed dates are ignored.
+pub fn parse(text: &str) -> TaskFields {
+    let mut fields = TaskFields {
+        priority: text.chars().find_map(Priority::from_emoji),
+        ..TaskFields::default()
+    };
+
+    for caps in DATE_FIELD.captures_iter(text) {
+        let Ok(date) = NaiveDate::parse_from_str(&caps[2], "%Y-%m-%d") else {
+            continue;
+        };
+        let slot = match &caps[1] {
+            "⏳" => &mut fields.scheduled,
+            "🛫" => &mut fields.start,
+            "✅" => &mut fields.completed,
+            _ => &mut fields.due,
+        };
+        slot.get_or_insert(date);
+    }
+
+    fields.recurrence = RECURRENCE
+        .captures(text)
+        .map(|caps| caps[1].trim().to_string())
+        .filter(|rule| !rule.is_empty());
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+    }
+
+    #[test]
+    fn test_parse_all_fields() {
+        let fields = parse(
+            "Review codecs ⏫ 🔁 every week on Monday 🛫 2026-10-01 ⏳ 2026-10-15 📅 2026-10-20 ✅ 2026-10-18 #wip",
+        );
+
+        assert_eq!(fields.priority, Some(Priority::High));
+        assert_eq!(fields.due, date("2026-10-20"));
+        assert_eq!(fields.scheduled, date("2026-10-15"));
+        assert_eq!(fields.start, date("2026-10-01"));
+        assert_eq!(fields.completed, date("2026-10-18"));
+        assert_eq!(fields.recurrence.as_deref(), Some("every week on Monday"));
+    }
+
+    #[test]
+    fn test_plain_task_has_no_fields() {
+        assert_eq!(parse("Learn lifetimes #rust"), TaskFields::default());
+    }
+
+    #[test]
+    fn test_invalid_date_is_ignored() {
+        let fields = parse("Fix 📅 2026-13-45 🔽");
+        assert_eq!(fields.due, None);
+        assert_eq!(fields.priority, Some(Priority::Low));
+    }
+
+    #[test]
+    fn test_priority_order() {
+        assert!(Priority::Highest < Priority::High);
+        assert!(Priority::Low < Priority::Lowest);
+    }
+}
@@ -59,12 +59,21 @@
 //! Since both captures are in the same pattern, they're guaranteed to correspond.
 
 use super::queries::Queries;
-use super::{Heading, MarkdownParser, ParsedMarkdown, Wikilink};
+use super::{
+    Heading, MarkdownParser, Metadata, ParsedMarkdown, TagOccurrence, Task, TaskStatus, Wikilink,
+};
+use super::{frontmatter, inline_fields, task_fields};
 use anyhow::{Context, Result};
-use std::collections::BTreeSet;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeSet, HashSet};
 use tree_sitter::{Language, Node, Parser, QueryCursor, StreamingIterator, Tree};
 use tree_sitter_md_obsidian::{INLINE_LANGUAGE, LANGUAGE};
 
+/// A list item's first line, from its marker: `- [x] text` or `1. [ ] text`.
+static TASK_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:[-*+]|\d+[.)])\s+\[(.)\](?:\s+(.*?))?\s*$").unwrap());
+
 /// Helper to create a parser with a language set
 fn new_parser(language: &Language) -> Result<Parser> {
     let mut parser = Parser::new();
@@ -131,7 +140,7 @@ impl TreeSitterParser {
                 let node = capture.node;
                 let capture_name = &self.queries.headings.capture_names()[capture.index as usize];
 
-                match capture_name.as_ref() {
+                match *capture_name {
                     "h1" => level = 1,
                     "h2" => level = 2,
                     "h3" => level = 3,
@@ -154,12 +163,72 @@ impl TreeSitterParser {
         Ok(headings)
     }
 
+    /// Extract frontmatter fields from the `minus_metadata` block, if any.
+    fn extract_frontmatter(&self, ctx: &ExtractionContext) -> Metadata {
+        let mut cursor = QueryCursor::new();
+        let root_node = ctx.block_tree.root_node();
+
+        let mut matches =
+            cursor.matches(&self.queries.frontmatter, root_node, ctx.content.as_bytes());
+        while let Some(match_) = matches.next() {
+            if let Some(capture) = match_.captures.first() {
+                let block = extract_text(ctx.content, &capture.node);
+                return frontmatter::parse_yaml(frontmatter::strip_fences(block));
+            }
+        }
+
+        Metadata::new()
+    }
+
+    /// Byte offset where the body starts: the end of the frontmatter block, or 0.
+    fn frontmatter_end(&self, ctx: &ExtractionContext) -> usize {
+        let mut cursor = QueryCursor::new();
+        let root_node = ctx.block_tree.root_node();
+
+        let mut matches =
+            cursor.matches(&self.queries.frontmatter, root_node, ctx.content.as_bytes());
+        while let Some(match_) = matches.next() {
+            if let Some(capture) = match_.captures.first() {
+                return capture.node.end_byte();
+            }
+        }
+
+        0
+    }
+
+    /// Lines (1-indexed) covered by code blocks or frontmatter, where
+    /// line-based extraction must not look.
+    fn non_prose_lines(&self, ctx: &ExtractionContext) -> HashSet<usize> {
+        let mut lines = HashSet::new();
+        let root_node = ctx.block_tree.root_node();
+
+        for query in [&self.queries.code_blocks, &self.queries.frontmatter] {
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query, root_node, ctx.content.as_bytes());
+            while let Some(match_) = matches.next() {
+                for capture in match_.captures {
+                    let start = capture.node.start_position().row + 1;
+                    // A node ending at column 0 stops before that row
+                    let end = capture.node.end_position();
+                    let end = if end.column == 0 {
+                        end.row
+                    } else {
+                        end.row + 1
+                    };
+                    lines.extend(start..=end);
+                }
+            }
+        }
+
+        lines
+    }
+
     /// Extract both tags and wikilinks in one pass.
     /// Parses inline content once and queries for both element types.
     fn extract_inline_elements(
         &mut self,
         ctx: &ExtractionContext,
-    ) -> Result<(Vec<String>, Vec<Wikilink>)> {
+    ) -> Result<(Vec<TagOccurrence>, Vec<Wikilink>)> {
         // Parse the entire content as inline
         // The inline grammar is context-aware and won't match tags/links in code blocks
         let inline_tree = self
@@ -167,16 +236,24 @@ impl TreeSitterParser {
             .parse(ctx.content, None)
             .context("Failed to parse inline content")?;
 
-        // Extract both element types from the same tree
-        let tags = self.extract_tags_from_tree(&inline_tree, ctx.content)?;
+        // Extract both element types from the same tree. The inline grammar
+        // doesn't know about frontmatter, so `#tags` in YAML are dropped here.
+        let body_start = self.frontmatter_end(ctx);
+        let tags = self.extract_tags_from_tree(&inline_tree, ctx.content, body_start)?;
         let wikilinks = self.extract_wikilinks_from_tree(&inline_tree, ctx.content)?;
 
         Ok((tags, wikilinks))
     }
 
-    /// Extract tags from an inline tree.
-    fn extract_tags_from_tree(&self, inline_tree: &Tree, content: &str) -> Result<Vec<String>> {
-        let mut tags = BTreeSet::new();
+    /// Extract every tag occurrence from an inline tree, in document order,
+    /// skipping any that start before `body_start`.
+    fn extract_tags_from_tree(
+        &self,
+        inline_tree: &Tree,
+        content: &str,
+        body_start: usize,
+    ) -> Result<Vec<TagOccurrence>> {
+        let mut tags = Vec::new();
         let mut cursor = QueryCursor::new();
         let root = inline_tree.root_node();
 
@@ -184,16 +261,110 @@ impl TreeSitterParser {
         while let Some(match_) = matches.next() {
             for capture in match_.captures {
                 let node = capture.node;
+                if node.start_byte() < body_start {
+                    continue;
+                }
                 let tag_text = extract_text(content, &node);
 
                 // Remove the # prefix
                 if let Some(tag_name) = tag_text.strip_prefix('#') {
-                    tags.insert(tag_name.to_string());
+                    let start = node.start_byte() + 1;
+                    tags.push(TagOccurrence {
+                        tag: tag_name.to_string(),
+                        line: node.start_position().row + 1,
+                        column: node.start_position().column,
+                        range: start..start + tag_name.len(),
+                    });
                 }
             }
         }
 
-        Ok(tags.into_iter().collect())
+        Ok(tags)
+    }
+
+    /// Extract task list items. Nesting comes from the block tree; status and
+    /// text come from the item's first line.
+    fn extract_tasks(
+        &self,
+        ctx: &ExtractionContext,
+        headings: &[Heading],
+        tag_occurrences: &[TagOccurrence],
+    ) -> Vec<Task> {
+        let lines: Vec<&str> = ctx.content.lines().collect();
+        let mut tasks: Vec<Task> = Vec::new();
+        let mut task_lines = HashSet::new();
+        let mut cursor = QueryCursor::new();
+        let root_node = ctx.block_tree.root_node();
+
+        let mut matches =
+            cursor.matches(&self.queries.list_items, root_node, ctx.content.as_bytes());
+        while let Some(match_) = matches.next() {
+            for capture in match_.captures {
+                let node = capture.node;
+                let pos = node.start_position();
+                let Some(caps) = lines
+                    .get(pos.row)
+                    .and_then(|line| line.get(pos.column..))
+                    .and_then(|item| TASK_LINE.captures(item))
+                else {
+                    continue;
+                };
+                let Some(status) = caps[1].chars().next().and_then(TaskStatus::from_marker) else {
+                    continue;
+                };
+                let line = pos.row + 1;
+
+                // Walk up through enclosing list items
+                let mut depth = 0;
+                let mut parent = None;
+                let mut ancestor = node.parent();
+                while let Some(n) = ancestor {
+                    if n.kind() == "list_item" {
+                        depth += 1;
+                        let ancestor_line = n.start_position().row + 1;
+                        if parent.is_none() && task_lines.contains(&ancestor_line) {
+                            parent = Some(ancestor_line);
+                        }
+                    }
+                    ancestor = n.parent();
+                }
+
+                let heading = headings
+                    .iter()
+                    .take_while(|h| h.line < line)
+                    .last()
+                    .map(|h| h.text.clone());
+                let mut tags: Vec<String> = tag_occurrences
+                    .iter()
+                    .filter(|t| t.line == line)
+                    .map(|t| t.tag.clone())
+                    .collect();
+                tags.dedup();
+
+                let text = caps.get(2).map_or("", |m| m.as_str()).to_string();
+                let fields = task_fields::parse(&text);
+
+                task_lines.insert(line);
+                tasks.push(Task {
+                    status,
+                    text,
+                    line,
+                    heading,
+                    tags,
+                    depth,
+                    parent,
+                    priority: fields.priority,
+                    due: fields.due,
+                    scheduled: fields.scheduled,
+                    start: fields.start,
+                    completed: fields.completed,
+                    recurrence: fields.recurrence,
+                });
+            }
+        }
+
+        tasks.sort_by_key(|t| t.line);
+        tasks
     }
 
     /// Extract wikilinks from an inline tree.
@@ -217,7 +388,7 @@ impl TreeSitterParser {
                 let node = capture.node;
                 let capture_name = &self.queries.wikilinks.capture_names()[capture.index as usize];
 
-                match capture_name.as_ref() {
+                match *capture_name {
                     "target" => {
                         target = extract_text(content, &node).to_string();
                     }
@@ -271,16 +442,49 @@ impl MarkdownParser for TreeSitterParser {
         let headings = self.extract_headings(&ctx)?;
 
         // Extract tags and wikilinks from inline content in one pass
-        let (tags, wikilinks) = self.extract_inline_elements(&ctx)?;
-
-        // Determine title: first H1 heading, or empty string
-        let title = headings.first().map(|h| h.text.clone()).unwrap_or_default();
+        let (tag_occurrences, wikilinks) = self.extract_inline_elements(&ctx)?;
+        let tasks = self.extract_tasks(&ctx, &headings, &tag_occurrences);
+        let mut tags: Vec<String> = tag_occurrences
+            .iter()
+            .map(|t| t.tag.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        // Frontmatter feeds title, tags and aliases
+        let mut metadata = self.extract_frontmatter(&ctx);
+        let aliases = frontmatter::list_values(&metadata, &["aliases", "alias"]);
+        for tag in frontmatter::list_values(&metadata, &["tags", "tag"]) {
+            let tag = tag.trim_start_matches('#').to_string();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags.sort();
+
+        // Determine title: frontmatter `title`, else first heading, else empty string
+        let title = metadata
+            .get("title")
+            .map(|t| t.to_string())
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| headings.first().map(|h| h.text.clone()))
+            .unwrap_or_default();
+
+        // Inline fields (`**Status:** ...`, `key:: value`) fill in keys frontmatter didn't set
+        let skip_lines = self.non_prose_lines(&ctx);
+        for (key, value) in inline_fields::extract(content, &skip_lines) {
+            metadata.entry(key).or_insert(value);
+        }
 
         Ok(ParsedMarkdown {
             title,
+            metadata,
             headings,
             wikilinks,
             tags,
+            tag_occurrences,
+            aliases,
+            tasks,
             body: content.to_string(),
         })
     }
@@ -390,6 +594,140 @@ After code: #another-real.
         assert_eq!(parsed.headings[2].text, "Subsection");
     }
 
+    #[test]
+    fn test_frontmatter_feeds_title_tags_and_aliases() {
+        let content = r##"---
+title: Search Flow
+tags: [lucene, "#wip"]
+aliases:
+  - Query Path
+status: draft
+---
+# Search Flow Deep Dive
+
+Inline #deep-dive tag.
+"##;
+
+        let mut parser = TreeSitterParser::new().unwrap();
+        let parsed = parser.parse(content).unwrap();
+
+        assert_eq!(parsed.title, "Search Flow");
+        assert_eq!(parsed.tags, vec!["deep-dive", "lucene", "wip"]);
+        // Only body tags have positions; `"#wip"` in the YAML isn't one
+        let occurrences: Vec<&str> = parsed
+            .tag_occurrences
+            .iter()
+            .map(|t| t.tag.as_str())
+            .collect();
+        assert_eq!(occurrences, vec!["deep-dive"]);
+        assert_eq!(parsed.aliases, vec!["Query Path"]);
+        assert_eq!(
+            parsed.metadata.get("status"),
+            Some(&crate::parser::MetaValue::Text("draft".into()))
+        );
+
+        // Frontmatter is not body: headings start after it
+        assert_eq!(parsed.headings.len(), 1);
+        assert_eq!(parsed.headings[0].line, 8);
+    }
+
+    #[test]
+    fn test_inline_fields_right_after_fence_and_frontmatter() {
+        let content =
+            "---\ntitle: x\n---\nstatus:: draft\n\n```\nowner:: code\n```\nreviewer:: ana\n";
+
+        let mut parser = TreeSitterParser::new().unwrap();
+        let parsed = parser.parse(content).unwrap();
+
+        let text = |s: &str| crate::parser::MetaValue::Text(s.into());
+        assert_eq!(parsed.metadata.get("status"), Some(&text("draft")));
+        assert_eq!(parsed.metadata.get("reviewer"), Some(&text("ana")));
+        assert!(!parsed.metadata.contains_key("owner"));
+    }
+
+    #[test]
+    fn test_inline_fields_merge_with_frontmatter() {
+        let content = r#"---
+status: draft
+---
+# Codecs
+
+**Status:** Learning - Phase 2B
+**Alignment:** 🟢 Target employer
+updated:: 2026-10-01
+
+```
+**Owner:** not a field
+```
+"#;
+
+        let mut parser = TreeSitterParser::new().unwrap();
+        let parsed = parser.parse(content).unwrap();
+
+        let text = |s: &str| crate::parser::MetaValue::Text(s.into());
+        // Frontmatter wins for the same key
+        assert_eq!(parsed.metadata.get("status"), Some(&text("draft")));
+        assert_eq!(
+            parsed.metadata.get("alignment"),
+            Some(&text("🟢 Target employer"))
+        );
+        assert_eq!(parsed.metadata.get("updated"), Some(&text("2026-10-01")));
+        assert!(!parsed.metadata.contains_key("owner"));
+    }
+
+    #[test]
+    fn test_extract_tasks() {
+        let content = r#"# Board
+
+## This Week
+
+- [ ] Review codecs #wip #lucene
+  - [x] Read BlockTree
+  - [-] Skip FST
+- [?] Ask about norms
+- plain item
+  - [ ] Nested under plain item
+1. [ ] Numbered task
+
+```
+- [ ] not a task
+```
+"#;
+
+        let mut parser = TreeSitterParser::new().unwrap();
+        let tasks = parser.parse(content).unwrap().tasks;
+
+        let summary: Vec<_> = tasks
+            .iter()
+            .map(|t| (t.status, t.text.as_str(), t.line, t.depth, t.parent))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (TaskStatus::Todo, "Review codecs #wip #lucene", 5, 0, None),
+                (TaskStatus::Done, "Read BlockTree", 6, 1, Some(5)),
+                (TaskStatus::Cancelled, "Skip FST", 7, 1, Some(5)),
+                (TaskStatus::Question, "Ask about norms", 8, 0, None),
+                (TaskStatus::Todo, "Nested under plain item", 10, 1, None),
+                (TaskStatus::Todo, "Numbered task", 11, 0, None),
+            ]
+        );
+        assert_eq!(tasks[0].tags, vec!["wip", "lucene"]);
+        assert!(tasks[1].tags.is_empty());
+        assert_eq!(tasks[0].heading.as_deref(), Some("This Week"));
+    }
+
+    #[test]
+    fn test_no_frontmatter() {
+        let content = "# Title\n\n---\nnot: frontmatter\n---\n";
+
+        let mut parser = TreeSitterParser::new().unwrap();
+        let parsed = parser.parse(content).unwrap();
+
+        assert!(parsed.metadata.is_empty());
+        assert_eq!(parsed.title, "Title");
+    }
+
     #[test]
     fn test_no_title() {
         let content = "Some content without a heading.\n";
@@ -1,20 +1,39 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
 use std::path::PathBuf;
 
+use super::task_fields::Priority;
+
 /// Parsed markdown content with extracted structured data.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParsedMarkdown {
-    /// First H1 heading, or empty if none
+    /// Frontmatter `title`, else first heading, or empty if none
     pub title: String,
 
+    /// Frontmatter and inline fields, keyed as written in the note
+    pub metadata: Metadata,
+
     /// All headings in document order
     pub headings: Vec<Heading>,
 
     /// All wikilinks in document order
     pub wikilinks: Vec<Wikilink>,
 
-    /// Unique tags, sorted alphabetically
+    /// Unique tags (inline and frontmatter), sorted alphabetically
     pub tags: Vec<String>,
 
+    /// Every inline `#tag`, in document order (frontmatter tags aren't included)
+    pub tag_occurrences: Vec<TagOccurrence>,
+
+    /// Alternative names from frontmatter `aliases`, used for link resolution
+    pub aliases: Vec<String>,
+
+    /// All tasks (`- [ ]` list items) in document order
+    pub tasks: Vec<Task>,
+
     /// Full markdown content
     pub body: String,
 }
@@ -46,3 +65,112 @@ pub struct Wikilink {
     pub line: usize,
     pub column: usize,
 }
+
+/// An inline `#tag` and where it appears.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagOccurrence {
+    pub tag: String,   // Without the `#`
+    pub line: usize,   // 1-indexed
+    pub column: usize, // 0-indexed byte column of the `#`
+    /// Byte range of the tag name (without the `#`) in the note
+    pub range: Range<usize>,
+}
+
+/// A task list item: `- [ ] text`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub status: TaskStatus,
+    pub text: String, // Item text after the `[ ]` marker (first line only)
+    pub line: usize,
+    pub heading: Option<String>, // Closest heading above the task
+    pub tags: Vec<String>,       // Tags on the task line, without `#`
+    pub depth: usize,            // List nesting, 0 = top-level
+    pub parent: Option<usize>,   // Line of the enclosing task, if nested under one
+
+    // Obsidian Tasks annotations (⏫, 📅 2026-10-20, 🔁 every week)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<String>,
+}
+
+/// Task status, from the character between the brackets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskStatus {
+    Todo,      // [ ]
+    Done,      // [x]
+    Cancelled, // [-]
+    Question,  // [?]
+}
+
+impl TaskStatus {
+    pub fn from_marker(marker: char) -> Option<Self> {
+        match marker {
+            ' ' => Some(TaskStatus::Todo),
+            'x' | 'X' => Some(TaskStatus::Done),
+            '-' => Some(TaskStatus::Cancelled),
+            '?' => Some(TaskStatus::Question),
+            _ => None,
+        }
+    }
+
+    pub fn marker(self) -> char {
+        match self {
+            TaskStatus::Todo => ' ',
+            TaskStatus::Done => 'x',
+            TaskStatus::Cancelled => '-',
+            TaskStatus::Question => '?',
+        }
+    }
+
+    /// Whether the task still needs attention (todo or open question).
+    pub fn is_open(self) -> bool {
+        matches!(self, TaskStatus::Todo | TaskStatus::Question)
+    }
+}
+
+/// Structured note metadata (frontmatter and inline fields).
+pub type Metadata = BTreeMap<String, MetaValue>;
+
+/// A typed metadata value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MetaValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    List(Vec<MetaValue>),
+}
+
+impl MetaValue {
+    /// Flatten into display strings (one per list item).
+    pub fn as_strings(&self) -> Vec<String> {
+        match self {
+            MetaValue::List(items) => items.iter().flat_map(|v| v.as_strings()).collect(),
+            other => vec![other.to_string()],
+        }
+    }
+}
+
+impl fmt::Display for MetaValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaValue::Bool(b) => write!(f, "{}", b),
+            MetaValue::Number(n) => write!(f, "{}", n),
+            MetaValue::Text(s) => write!(f, "{}", s),
+            MetaValue::List(items) => {
+                let parts: Vec<String> = items.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}", parts.join(", "))
+            }
+        }
+    }
+}
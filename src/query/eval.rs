@@ -0,0 +1,461 @@
+//! Evaluate a parsed [`Query`] over indexed notes.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::parse::{CmpOp, Expr, Query, Source};
+use crate::parser::inline_fields::normalize_key;
+use crate::parser::{MetaValue, Metadata};
+use crate::tags::is_within;
+
+/// A value produced while evaluating a query.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    List(Vec<Value>),
+}
+
+impl From<&MetaValue> for Value {
+    fn from(value: &MetaValue) -> Self {
+        match value {
+            MetaValue::Bool(b) => Value::Bool(*b),
+            MetaValue::Number(n) => Value::Number(*n),
+            MetaValue::Text(s) => Value::Text(s.clone()),
+            MetaValue::List(items) => Value::List(items.iter().map(Value::from).collect()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "-"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(s) => write!(f, "{}", s),
+            Value::List(items) => {
+                let parts: Vec<String> = items.iter().map(|v| v.to_string()).collect();
+                write!(f, "{}", parts.join(", "))
+            }
+        }
+    }
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => *n != 0.0,
+            Value::Text(s) => !s.is_empty(),
+            Value::List(items) => !items.is_empty(),
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Text(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn as_date(&self) -> Option<NaiveDate> {
+        match self {
+            Value::Text(s) => NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok(),
+            _ => None,
+        }
+    }
+
+    /// Kind used to order values of different types when sorting. Numeric and
+    /// ISO-date text sort with numbers and dates, not with other strings.
+    fn sort_kind(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::Text(_) if self.as_number().is_some() => 2,
+            Value::Text(_) if self.as_date().is_some() => 3,
+            Value::Text(_) => 4,
+            Value::List(_) => 5,
+        }
+    }
+}
+
+/// A note as seen by the query engine: file attributes, tags and metadata.
+pub struct NoteRow {
+    pub path: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub metadata: Metadata,
+}
+
+impl NoteRow {
+    /// Domain folder (first path component); root notes have none.
+    fn domain(&self) -> Option<&str> {
+        self.path.split_once('/').map(|(domain, _)| domain)
+    }
+
+    /// Look up a field. `file.*` names are file attributes; anything else is a
+    /// metadata key, matched after normalisation (`Due Date` = `due-date`).
+    pub fn field(&self, name: &str) -> Value {
+        let text = |s: &str| Value::Text(s.to_string());
+
+        match name {
+            "file.path" => text(&self.path),
+            "file.name" => {
+                let filename = self.path.rsplit('/').next().unwrap_or(&self.path);
+                text(filename.strip_suffix(".md").unwrap_or(filename))
+            }
+            "file.folder" => text(self.path.rsplit_once('/').map_or("", |(dir, _)| dir)),
+            "file.domain" => self.domain().map_or(Value::Null, text),
+            "file.title" => text(&self.title),
+            "file.tags" => Value::List(self.tags.iter().map(|t| text(t)).collect()),
+            key => {
+                let key = normalize_key(key);
+                self.metadata
+                    .iter()
+                    .find(|(k, _)| normalize_key(k) == key)
+                    .map_or(Value::Null, |(_, v)| Value::from(v))
+            }
+        }
+    }
+
+    fn matches_source(&self, source: &Source) -> bool {
+        match source {
+            Source::Tag(tag) => self.tags.iter().any(|t| is_within(t, tag)),
+            Source::Folder(folder) => {
+                folder.is_empty() || self.path.starts_with(&format!("{}/", folder))
+            }
+            Source::Domain(domain) => self.domain() == Some(domain.as_str()),
+            Source::Not(inner) => !self.matches_source(inner),
+            Source::And(a, b) => self.matches_source(a) && self.matches_source(b),
+            Source::Or(a, b) => self.matches_source(a) || self.matches_source(b),
+        }
+    }
+
+    fn eval(&self, expr: &Expr) -> Result<Value> {
+        Ok(match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::Field(name) => self.field(name),
+            Expr::Compare(left, op, right) => {
+                Value::Bool(compare(&self.eval(left)?, *op, &self.eval(right)?))
+            }
+            Expr::And(a, b) => Value::Bool(self.eval(a)?.is_truthy() && self.eval(b)?.is_truthy()),
+            Expr::Or(a, b) => Value::Bool(self.eval(a)?.is_truthy() || self.eval(b)?.is_truthy()),
+            Expr::Not(inner) => Value::Bool(!self.eval(inner)?.is_truthy()),
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                call(name, &args)?
+            }
+        })
+    }
+}
+
+/// Evaluate a function call.
+fn call(name: &str, args: &[Value]) -> Result<Value> {
+    match (name, args) {
+        ("contains", [haystack, needle]) => Ok(Value::Bool(match haystack {
+            Value::List(items) => items.iter().any(|item| equals(item, needle)),
+            Value::Text(s) => s
+                .to_lowercase()
+                .contains(&needle.to_string().to_lowercase()),
+            _ => false,
+        })),
+        ("contains", _) => bail!("contains() takes 2 arguments"),
+        (other, _) => bail!("Unknown function '{}' in query", other),
+    }
+}
+
+/// Equality: text ignores case, numbers compare numerically (`"4"` = `4`), and
+/// a list equals a scalar if any of its items does.
+fn equals(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::List(xs), Value::List(ys)) => {
+            xs.len() == ys.len() && xs.iter().zip(ys).all(|(x, y)| equals(x, y))
+        }
+        (Value::List(items), scalar) | (scalar, Value::List(items)) => {
+            items.iter().any(|item| equals(item, scalar))
+        }
+        (Value::Null, Value::Null) => true,
+        (Value::Null, _) | (_, Value::Null) => false,
+        _ => order(a, b) == Some(Ordering::Equal),
+    }
+}
+
+/// Ordering between two scalars, if they're comparable.
+fn order(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Bool(x), Value::Bool(y)) => Some(x.cmp(y)),
+        (Value::Text(x), Value::Text(y)) => match (a.as_number(), b.as_number()) {
+            (Some(x), Some(y)) => x.partial_cmp(&y),
+            _ => Some(x.to_lowercase().cmp(&y.to_lowercase())),
+        },
+        (Value::Number(_), Value::Number(_) | Value::Text(_))
+        | (Value::Text(_), Value::Number(_)) => a.as_number()?.partial_cmp(&b.as_number()?),
+        _ => None,
+    }
+}
+
+fn compare(a: &Value, op: CmpOp, b: &Value) -> bool {
+    match op {
+        CmpOp::Eq => equals(a, b),
+        CmpOp::NotEq => !equals(a, b),
+        CmpOp::Lt => order(a, b) == Some(Ordering::Less),
+        CmpOp::LtEq => matches!(order(a, b), Some(Ordering::Less | Ordering::Equal)),
+        CmpOp::Gt => order(a, b) == Some(Ordering::Greater),
+        CmpOp::GtEq => matches!(order(a, b), Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+/// Total order for SORT: values are grouped by kind (null < bool < number <
+/// date < string < list), then compared within the kind.
+fn sort_order(a: &Value, b: &Value) -> Ordering {
+    let kind = a.sort_kind().cmp(&b.sort_kind());
+    if kind.is_ne() {
+        return kind;
+    }
+    match (a, b) {
+        (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
+        (Value::List(xs), Value::List(ys)) => xs
+            .iter()
+            .zip(ys)
+            .map(|(x, y)| sort_order(x, y))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| xs.len().cmp(&ys.len())),
+        _ => match (a.as_number(), b.as_number(), a.as_date(), b.as_date()) {
+            (Some(x), Some(y), _, _) => x.total_cmp(&y),
+            (_, _, Some(x), Some(y)) => x.cmp(&y),
+            _ => a.to_string().to_lowercase().cmp(&b.to_string().to_lowercase()),
+        },
+    }
+}
+
+/// One result row: the note path and one value per column.
+#[derive(Debug, Serialize)]
+pub struct ResultRow {
+    pub file: String,
+    pub values: Vec<Value>,
+}
+
+/// Query output, ready for printing.
+#[derive(Debug)]
+pub struct ResultTable {
+    pub show_id: bool,
+    pub columns: Vec<String>,
+    pub rows: Vec<ResultRow>,
+}
+
+/// Run a query over the given notes. Results are ordered by path unless the
+/// query has a SORT clause.
+pub fn run(query: &Query, mut notes: Vec<NoteRow>) -> Result<ResultTable> {
+    notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut selected = Vec::new();
+    for note in notes {
+        if let Some(source) = &query.from
+            && !note.matches_source(source)
+        {
+            continue;
+        }
+        if let Some(filter) = &query.filter
+            && !note.eval(filter)?.is_truthy()
+        {
+            continue;
+        }
+        selected.push(note);
+    }
+
+    if !query.sort.is_empty() {
+        selected.sort_by(|a, b| {
+            query
+                .sort
+                .iter()
+                .map(|key| {
+                    let ordering = sort_order(&a.field(&key.field), &b.field(&key.field));
+                    if key.descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    if let Some(limit) = query.limit {
+        selected.truncate(limit);
+    }
+
+    let rows = selected
+        .iter()
+        .map(|note| ResultRow {
+            file: note.path.clone(),
+            values: query.columns.iter().map(|c| note.field(&c.field)).collect(),
+        })
+        .collect();
+
+    Ok(ResultTable {
+        show_id: query.show_id,
+        columns: query.columns.iter().map(|c| c.name.clone()).collect(),
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::parse_query;
+
+    fn note(path: &str, tags: &[&str], fields: &[(&str, MetaValue)]) -> NoteRow {
+        NoteRow {
+            path: path.to_string(),
+            title: path.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            metadata: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        }
+    }
+
+    fn text(s: &str) -> MetaValue {
+        MetaValue::Text(s.to_string())
+    }
+
+    fn notes() -> Vec<NoteRow> {
+        vec![
+            note(
+                "lucene/codecs.md",
+                &["wip"],
+                &[
+                    ("status", text("learning")),
+                    ("updated", text("2026-09-01")),
+                ],
+            ),
+            note(
+                "lucene/search-flow.md",
+                &["deep-dive"],
+                &[("status", text("done")), ("updated", text("2026-10-01"))],
+            ),
+            note(
+                "lucene/scoring.md",
+                &[],
+                &[
+                    ("Status", text("Learning")),
+                    ("updated", text("2026-10-12")),
+                ],
+            ),
+            note(
+                "rust/ownership.md",
+                &["wip"],
+                &[("rating", MetaValue::Number(4.0))],
+            ),
+            note("index.md", &[], &[]),
+        ]
+    }
+
+    fn files(query: &str) -> Vec<String> {
+        let query = parse_query(query).unwrap();
+        run(&query, notes())
+            .unwrap()
+            .rows
+            .into_iter()
+            .map(|r| r.file)
+            .collect()
+    }
+
+    #[test]
+    fn test_from_where_sort() {
+        assert_eq!(
+            files(r#"TABLE status FROM domain:lucene WHERE status != "done" SORT updated DESC"#),
+            vec!["lucene/scoring.md", "lucene/codecs.md"]
+        );
+    }
+
+    #[test]
+    fn test_sources() {
+        assert_eq!(
+            files("TABLE FROM #wip"),
+            vec!["lucene/codecs.md", "rust/ownership.md"]
+        );
+        assert_eq!(
+            files(r#"TABLE FROM "lucene" and -#wip"#),
+            vec!["lucene/scoring.md", "lucene/search-flow.md"]
+        );
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert_eq!(files("TABLE WHERE rating >= 4"), vec!["rust/ownership.md"]);
+        assert_eq!(
+            files(r#"TABLE WHERE updated > "2026-09-15" SORT updated"#),
+            vec!["lucene/search-flow.md", "lucene/scoring.md"]
+        );
+        // Missing fields are null: never ordered, never equal to a value
+        assert_eq!(files("TABLE WHERE rating < 10"), vec!["rust/ownership.md"]);
+        assert_eq!(
+            files(r#"TABLE WHERE contains(file.tags, "wip") and !status"#),
+            vec!["rust/ownership.md"]
+        );
+    }
+
+    #[test]
+    fn test_sort_mixed_kinds() {
+        let t = |s: &str| Value::Text(s.to_string());
+        let expected = vec![
+            Value::Null,
+            t("2"),
+            Value::Number(10.0),
+            t("2026-09-15"),
+            t("2026-10-01"),
+            t("1a"),
+            t("alpha"),
+        ];
+        // "2" < 10 < "1a" < "2" used to form a cycle, so the result depended on input order
+        for rotation in 0..expected.len() {
+            let mut values = expected.clone();
+            values.rotate_left(rotation);
+            values.reverse();
+            values.sort_by(sort_order);
+            assert_eq!(values, expected);
+        }
+    }
+
+    #[test]
+    fn test_file_fields_and_limit() {
+        let query =
+            parse_query("TABLE file.name, file.domain, rating SORT rating DESC LIMIT 2").unwrap();
+        let table = run(&query, notes()).unwrap();
+
+        assert_eq!(table.columns, vec!["file.name", "file.domain", "rating"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[0].file, "rust/ownership.md");
+        assert_eq!(
+            table.rows[0].values,
+            vec![
+                Value::Text("ownership".into()),
+                Value::Text("rust".into()),
+                Value::Number(4.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_function_is_an_error() {
+        let query = parse_query("TABLE WHERE length(status) > 1").unwrap();
+        assert!(run(&query, notes()).is_err());
+    }
+}
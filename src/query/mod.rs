@@ -0,0 +1,7 @@
+// Public interface for Dataview-style queries over note metadata
+
+pub mod eval;
+pub mod parse;
+
+pub use eval::{NoteRow, ResultTable, run};
+pub use parse::{Source, parse_query};
@@ -0,0 +1,608 @@
+//! Parser for Dataview-style `TABLE` queries.
+//!
+//! ```text
+//! TABLE [WITHOUT ID] field [AS "Name"], ...
+//!   [FROM source]            #tag, "folder", domain:name; and / or / -negation
+//!   [WHERE expr]             = != < <= > >=, and / or / !, contains(a, b)
+//!   [SORT field [ASC|DESC], ...]
+//!   [LIMIT n]
+//! ```
+//!
+//! Keywords are case-insensitive. Field names are dotted identifiers
+//! (`status`, `file.name`); dates are compared as quoted strings.
+
+use anyhow::{Result, bail};
+
+use super::eval::Value;
+
+/// A parsed `TABLE` query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub columns: Vec<Column>,
+    /// Whether the leading file column is shown (`WITHOUT ID` hides it).
+    pub show_id: bool,
+    pub from: Option<Source>,
+    pub filter: Option<Expr>,
+    pub sort: Vec<SortKey>,
+    pub limit: Option<usize>,
+}
+
+/// A `TABLE` column: the field it reads and its header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub field: String,
+    pub name: String,
+}
+
+/// A `FROM` source selecting notes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Tag(String),
+    Folder(String),
+    Domain(String),
+    Not(Box<Source>),
+    And(Box<Source>, Box<Source>),
+    Or(Box<Source>, Box<Source>),
+}
+
+/// A `WHERE` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    Field(String),
+    Compare(Box<Expr>, CmpOp, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKey {
+    pub field: String,
+    pub descending: bool,
+}
+
+/// Parse a query string.
+pub fn parse_query(input: &str) -> Result<Query> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let query = parser.query()?;
+    if let Some(token) = parser.peek() {
+        bail!("Unexpected '{}' in query", token);
+    }
+    Ok(query)
+}
+
+// -- Tokenizer ---------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Tag(String),
+    Op(CmpOp),
+    Comma,
+    Colon,
+    LParen,
+    RParen,
+    Bang,
+    Minus,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(s) => write!(f, "{}", s),
+            Token::Str(s) => write!(f, "\"{}\"", s),
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Tag(t) => write!(f, "#{}", t),
+            Token::Op(op) => write!(f, "{}", op_str(*op)),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::Bang => write!(f, "!"),
+            Token::Minus => write!(f, "-"),
+        }
+    }
+}
+
+fn op_str(op: CmpOp) -> &'static str {
+    match op {
+        CmpOp::Eq => "=",
+        CmpOp::NotEq => "!=",
+        CmpOp::Lt => "<",
+        CmpOp::LtEq => "<=",
+        CmpOp::Gt => ">",
+        CmpOp::GtEq => ">=",
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    let take_while = |start: usize, pred: fn(char) -> bool| {
+        let mut end = start;
+        while end < chars.len() && pred(chars[end]) {
+            end += 1;
+        }
+        (chars[start..end].iter().collect::<String>(), end)
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            c if c.is_whitespace() => i += 1,
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            ':' => {
+                tokens.push(Token::Colon);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Op(CmpOp::NotEq));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Bang);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Op(CmpOp::Eq));
+                i += if next == Some('=') { 2 } else { 1 };
+            }
+            '<' | '>' => {
+                let or_eq = next == Some('=');
+                let op = match (c, or_eq) {
+                    ('<', false) => CmpOp::Lt,
+                    ('<', true) => CmpOp::LtEq,
+                    ('>', false) => CmpOp::Gt,
+                    _ => CmpOp::GtEq,
+                };
+                tokens.push(Token::Op(op));
+                i += if or_eq { 2 } else { 1 };
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .map(|p| i + 1 + p);
+                let Some(end) = end else {
+                    bail!("Unterminated string in query");
+                };
+                tokens.push(Token::Str(chars[i + 1..end].iter().collect()));
+                i = end + 1;
+            }
+            '#' => {
+                let (tag, end) = take_while(i + 1, |ch| is_ident_char(ch) || ch == '/');
+                if tag.is_empty() {
+                    bail!("Expected a tag name after '#'");
+                }
+                tokens.push(Token::Tag(tag));
+                i = end;
+            }
+            '-' if !next.is_some_and(|ch| ch.is_ascii_digit()) => {
+                tokens.push(Token::Minus);
+                i += 1;
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                let (number, end) = take_while(i + 1, |ch| ch.is_ascii_digit() || ch == '.');
+                let text = format!("{}{}", c, number);
+                let Ok(value) = text.parse() else {
+                    bail!("Invalid number '{}' in query", text);
+                };
+                tokens.push(Token::Number(value));
+                i = end;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let (ident, end) = take_while(i, is_ident_char);
+                tokens.push(Token::Ident(ident));
+                i = end;
+            }
+            other => bail!("Unexpected character '{}' in query", other),
+        }
+    }
+
+    Ok(tokens)
+}
+
+// -- Parser ------------------------------------------------------------------
+
+const CLAUSE_KEYWORDS: [&str; 4] = ["FROM", "WHERE", "SORT", "LIMIT"];
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(s)) if s.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, token: Token) -> Result<()> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            Some(t) => bail!("Expected '{}', found '{}'", token, t),
+            None => bail!("Expected '{}', found end of query", token),
+        }
+    }
+
+    fn at_clause_end(&self) -> bool {
+        self.peek().is_none() || CLAUSE_KEYWORDS.iter().any(|kw| self.peek_keyword(kw))
+    }
+
+    fn field_name(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Ident(name)) => Ok(name.to_lowercase()),
+            Some(t) => bail!("Expected a field name, found '{}'", t),
+            None => bail!("Expected a field name, found end of query"),
+        }
+    }
+
+    fn query(&mut self) -> Result<Query> {
+        if !self.eat_keyword("TABLE") {
+            match self.peek() {
+                Some(t) => bail!("Only TABLE queries are supported, found '{}'", t),
+                None => bail!("Empty query"),
+            }
+        }
+
+        let show_id = if self.eat_keyword("WITHOUT") {
+            if !self.eat_keyword("ID") {
+                bail!("Expected 'ID' after 'WITHOUT'");
+            }
+            false
+        } else {
+            true
+        };
+
+        let mut columns = Vec::new();
+        while !self.at_clause_end() {
+            let field = self.field_name()?;
+            let name = if self.eat_keyword("AS") {
+                match self.next() {
+                    Some(Token::Str(s) | Token::Ident(s)) => s,
+                    _ => bail!("Expected a column name after 'AS'"),
+                }
+            } else {
+                field.clone()
+            };
+            // Columns are keyed by name in --json output
+            if columns.iter().any(|c: &Column| c.name == name) {
+                bail!("Duplicate column '{}'; rename one with AS", name);
+            }
+            columns.push(Column { field, name });
+            if !self.eat(&Token::Comma) {
+                break;
+            }
+        }
+
+        let from = if self.eat_keyword("FROM") {
+            Some(self.source_or()?)
+        } else {
+            None
+        };
+
+        let filter = if self.eat_keyword("WHERE") {
+            Some(self.expr_or()?)
+        } else {
+            None
+        };
+
+        let mut sort = Vec::new();
+        if self.eat_keyword("SORT") {
+            loop {
+                let field = self.field_name()?;
+                let descending = if self.eat_keyword("DESC") {
+                    true
+                } else {
+                    self.eat_keyword("ASC");
+                    false
+                };
+                sort.push(SortKey { field, descending });
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let limit = if self.eat_keyword("LIMIT") {
+            match self.next() {
+                Some(Token::Number(n)) if n >= 0.0 && n.fract() == 0.0 => Some(n as usize),
+                _ => bail!("Expected a whole number after 'LIMIT'"),
+            }
+        } else {
+            None
+        };
+
+        Ok(Query {
+            columns,
+            show_id,
+            from,
+            filter,
+            sort,
+            limit,
+        })
+    }
+
+    fn source_or(&mut self) -> Result<Source> {
+        let mut left = self.source_and()?;
+        while self.eat_keyword("OR") {
+            left = Source::Or(Box::new(left), Box::new(self.source_and()?));
+        }
+        Ok(left)
+    }
+
+    fn source_and(&mut self) -> Result<Source> {
+        let mut left = self.source_unary()?;
+        while self.eat_keyword("AND") {
+            left = Source::And(Box::new(left), Box::new(self.source_unary()?));
+        }
+        Ok(left)
+    }
+
+    fn source_unary(&mut self) -> Result<Source> {
+        match self.next() {
+            Some(Token::Minus | Token::Bang) => Ok(Source::Not(Box::new(self.source_unary()?))),
+            Some(Token::LParen) => {
+                let source = self.source_or()?;
+                self.expect(Token::RParen)?;
+                Ok(source)
+            }
+            Some(Token::Tag(tag)) => Ok(Source::Tag(tag)),
+            Some(Token::Str(folder)) => Ok(Source::Folder(folder.trim_matches('/').to_string())),
+            Some(Token::Ident(kind)) if kind.eq_ignore_ascii_case("domain") => {
+                self.expect(Token::Colon)?;
+                match self.next() {
+                    Some(Token::Ident(name) | Token::Str(name)) => Ok(Source::Domain(name)),
+                    _ => bail!("Expected a domain name after 'domain:'"),
+                }
+            }
+            Some(t) => bail!(
+                "Invalid FROM source '{}': expected #tag, \"folder\" or domain:name",
+                t
+            ),
+            None => bail!("Expected a source after 'FROM'"),
+        }
+    }
+
+    fn expr_or(&mut self) -> Result<Expr> {
+        let mut left = self.expr_and()?;
+        while self.eat_keyword("OR") {
+            left = Expr::Or(Box::new(left), Box::new(self.expr_and()?));
+        }
+        Ok(left)
+    }
+
+    fn expr_and(&mut self) -> Result<Expr> {
+        let mut left = self.expr_unary()?;
+        while self.eat_keyword("AND") {
+            left = Expr::And(Box::new(left), Box::new(self.expr_unary()?));
+        }
+        Ok(left)
+    }
+
+    fn expr_unary(&mut self) -> Result<Expr> {
+        if self.eat(&Token::Bang) || self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.expr_unary()?)));
+        }
+
+        let left = self.operand()?;
+        if let Some(Token::Op(op)) = self.peek().cloned() {
+            self.pos += 1;
+            let right = self.operand()?;
+            return Ok(Expr::Compare(Box::new(left), op, Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.expr_or()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::Text(s))),
+            Some(Token::Number(n)) => Ok(Expr::Literal(Value::Number(n))),
+            Some(Token::Ident(name)) => {
+                if self.eat(&Token::LParen) {
+                    let mut args = Vec::new();
+                    if !self.eat(&Token::RParen) {
+                        loop {
+                            args.push(self.expr_or()?);
+                            if !self.eat(&Token::Comma) {
+                                break;
+                            }
+                        }
+                        self.expect(Token::RParen)?;
+                    }
+                    return Ok(Expr::Call(name.to_lowercase(), args));
+                }
+
+                Ok(match name.to_lowercase().as_str() {
+                    "true" => Expr::Literal(Value::Bool(true)),
+                    "false" => Expr::Literal(Value::Bool(false)),
+                    "null" => Expr::Literal(Value::Null),
+                    field => Expr::Field(field.to_string()),
+                })
+            }
+            Some(t) => bail!("Expected a value, found '{}'", t),
+            None => bail!("Expected a value, found end of query"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str) -> Box<Expr> {
+        Box::new(Expr::Field(name.into()))
+    }
+
+    fn text(s: &str) -> Box<Expr> {
+        Box::new(Expr::Literal(Value::Text(s.into())))
+    }
+
+    #[test]
+    fn test_parse_full_query() {
+        let query = parse_query(
+            r#"TABLE status, updated AS "Last Updated" FROM domain:lucene WHERE status != "done" SORT updated DESC LIMIT 5"#,
+        )
+        .unwrap();
+
+        assert!(query.show_id);
+        assert_eq!(
+            query.columns,
+            vec![
+                Column {
+                    field: "status".into(),
+                    name: "status".into()
+                },
+                Column {
+                    field: "updated".into(),
+                    name: "Last Updated".into()
+                },
+            ]
+        );
+        assert_eq!(query.from, Some(Source::Domain("lucene".into())));
+        assert_eq!(
+            query.filter,
+            Some(Expr::Compare(field("status"), CmpOp::NotEq, text("done")))
+        );
+        assert_eq!(
+            query.sort,
+            vec![SortKey {
+                field: "updated".into(),
+                descending: true
+            }]
+        );
+        assert_eq!(query.limit, Some(5));
+    }
+
+    #[test]
+    fn test_duplicate_columns_need_a_name() {
+        assert!(parse_query("TABLE status, status").is_err());
+        let query = parse_query(r#"TABLE status, status AS "Again""#).unwrap();
+        assert_eq!(query.columns[1].name, "Again");
+    }
+
+    #[test]
+    fn test_parse_sources() {
+        let query = parse_query(r#"table from #wip and ("lucene/" or -#rust)"#).unwrap();
+        assert!(query.columns.is_empty());
+        assert_eq!(
+            query.from,
+            Some(Source::And(
+                Box::new(Source::Tag("wip".into())),
+                Box::new(Source::Or(
+                    Box::new(Source::Folder("lucene".into())),
+                    Box::new(Source::Not(Box::new(Source::Tag("rust".into())))),
+                )),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_where_precedence() {
+        let query =
+            parse_query(r#"TABLE WHERE !draft and rating >= 4 or contains(file.tags, "wip")"#)
+                .unwrap();
+
+        assert_eq!(
+            query.filter,
+            Some(Expr::Or(
+                Box::new(Expr::And(
+                    Box::new(Expr::Not(field("draft"))),
+                    Box::new(Expr::Compare(
+                        field("rating"),
+                        CmpOp::GtEq,
+                        Box::new(Expr::Literal(Value::Number(4.0)))
+                    )),
+                )),
+                Box::new(Expr::Call(
+                    "contains".into(),
+                    vec![
+                        Expr::Field("file.tags".into()),
+                        Expr::Literal(Value::Text("wip".into()))
+                    ]
+                )),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_without_id_and_hyphenated_fields() {
+        let query = parse_query("TABLE WITHOUT ID due-date, file.name").unwrap();
+        assert!(!query.show_id);
+        assert_eq!(query.columns[0].field, "due-date");
+        assert_eq!(query.columns[1].field, "file.name");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_query("LIST FROM #wip").is_err());
+        assert!(parse_query("TABLE status WHERE status = \"done").is_err());
+        assert!(parse_query("TABLE status FROM domain").is_err());
+        assert!(parse_query("TABLE status LIMIT many").is_err());
+        assert!(parse_query("TABLE status WHERE (a = 1").is_err());
+    }
+}
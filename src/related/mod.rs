@@ -0,0 +1,199 @@
+//! Recommend notes related to a seed note.
+//!
+//! Four signals, each scaled to 0..1 and summed:
+//!
+//! - **tags**: Jaccard overlap of the two notes' tags
+//! - **co-citation**: notes linking to both (cosine-normalized count)
+//! - **coupling**: notes both link to (cosine-normalized count)
+//! - **text**: TF-IDF cosine similarity of the note bodies
+//!
+//! Every result keeps the evidence behind its score, so callers can say why
+//! a note was recommended.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::links::LinkIndex;
+use crate::parser::frontmatter;
+use crate::search::{TfIdf, tokenize};
+use crate::tags::TagIndex;
+use crate::vault::Vault;
+
+/// Shared terms listed per result.
+const SHARED_TERMS: usize = 5;
+
+/// Per-signal scores, each between 0 and 1.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Signals {
+    pub tags: f64,
+    pub co_citation: f64,
+    pub coupling: f64,
+    pub text: f64,
+}
+
+impl Signals {
+    fn total(&self) -> f64 {
+        self.tags + self.co_citation + self.coupling + self.text
+    }
+}
+
+/// A recommended note and the evidence for it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Related {
+    pub path: PathBuf,
+    pub title: String,
+    pub score: f64,
+    /// Whether the seed already links to or from this note
+    pub linked: bool,
+    pub signals: Signals,
+    pub shared_tags: Vec<String>,
+    /// Notes linking to both the seed and this note
+    pub co_cited_by: Vec<PathBuf>,
+    /// Notes both the seed and this note link to
+    pub both_link_to: Vec<PathBuf>,
+    /// Terms contributing most to the text similarity
+    pub shared_terms: Vec<String>,
+}
+
+/// Rank every other note in the vault by relatedness to `seed`, best first.
+/// Notes with no signal at all are left out.
+pub fn related(
+    vault: &Vault,
+    tag_index: &TagIndex,
+    link_index: &LinkIndex,
+    seed: &Path,
+) -> Result<Vec<Related>> {
+    let notes = vault.all_notes()?;
+    let tags_by_note = tag_index.tags_by_note();
+    let tags_of = |path: &Path| -> BTreeSet<&str> {
+        tags_by_note
+            .get(path.to_string_lossy().as_ref())
+            .map(|tags| tags.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    };
+
+    let mut bodies = Vec::new();
+    for note in &notes {
+        let content = vault.read_note(&note.path.to_string_lossy())?;
+        let body = match frontmatter::split(&content) {
+            Some((_, lines)) => content.lines().skip(lines).collect::<Vec<_>>().join("\n"),
+            None => content,
+        };
+        bodies.push(tokenize(&body));
+    }
+    let text = TfIdf::new(bodies);
+    let position: HashMap<&Path, usize> = notes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.path.as_path(), i))
+        .collect();
+
+    let seed_tags = tags_of(seed);
+    let seed_doc = position.get(seed).copied();
+
+    let mut results: Vec<Related> = notes
+        .iter()
+        .filter(|note| note.path != seed)
+        .filter_map(|note| {
+            let path = note.path.as_path();
+
+            let note_tags = tags_of(path);
+            let shared_tags: Vec<String> = seed_tags
+                .intersection(&note_tags)
+                .map(|t| t.to_string())
+                .collect();
+            let union = seed_tags.union(&note_tags).count();
+
+            let citers = |p: &Path| link_set(link_index.get_backward(p), seed, path);
+            let cited = |p: &Path| link_set(link_index.get_forward(p), seed, path);
+            let (co_cited_by, co_citation) = overlap(&citers(seed), &citers(path));
+            let (both_link_to, coupling) = overlap(&cited(seed), &cited(path));
+
+            let (similarity, shared_terms) = match (seed_doc, position.get(path)) {
+                (Some(a), Some(&b)) => (text.cosine(a, b), text.shared_terms(a, b, SHARED_TERMS)),
+                _ => (0.0, Vec::new()),
+            };
+
+            let signals = Signals {
+                tags: if union == 0 {
+                    0.0
+                } else {
+                    shared_tags.len() as f64 / union as f64
+                },
+                co_citation,
+                coupling,
+                text: similarity,
+            };
+            let score = signals.total();
+            if score <= 0.0 {
+                return None;
+            }
+
+            let linked = [link_index.get_forward(seed), link_index.get_backward(seed)]
+                .into_iter()
+                .any(|links| links.unwrap_or_default().iter().any(|p| p == path));
+
+            Some(Related {
+                path: path.to_path_buf(),
+                title: note.title.clone(),
+                score,
+                linked,
+                signals,
+                shared_tags,
+                co_cited_by,
+                both_link_to,
+                shared_terms,
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    Ok(results)
+}
+
+/// Linked notes, minus the two notes being compared.
+fn link_set(links: Option<&[PathBuf]>, a: &Path, b: &Path) -> BTreeSet<PathBuf> {
+    links
+        .unwrap_or_default()
+        .iter()
+        .filter(|p| *p != a && *p != b)
+        .cloned()
+        .collect()
+}
+
+/// Common members and their count scaled by `sqrt(|a| * |b|)`.
+fn overlap(a: &BTreeSet<PathBuf>, b: &BTreeSet<PathBuf>) -> (Vec<PathBuf>, f64) {
+    let common: Vec<PathBuf> = a.intersection(b).cloned().collect();
+    if common.is_empty() {
+        return (common, 0.0);
+    }
+    let score = common.len() as f64 / ((a.len() * b.len()) as f64).sqrt();
+    (common, score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(paths: &[&str]) -> BTreeSet<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_overlap_is_cosine_normalized() {
+        let (common, score) = overlap(&set(&["a.md", "b.md"]), &set(&["b.md", "c.md"]));
+        assert_eq!(common, vec![PathBuf::from("b.md")]);
+        assert!((score - 0.5).abs() < 1e-9);
+
+        let (common, score) = overlap(&set(&["a.md"]), &set(&[]));
+        assert!(common.is_empty());
+        assert_eq!(score, 0.0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Okapi BM25 ranking over an in-memory set of documents.
+
+use std::collections::HashMap;
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// Term statistics for a fixed list of tokenized documents.
+pub struct Bm25 {
+    docs: Vec<HashMap<String, usize>>,
+    lengths: Vec<usize>,
+    avg_length: f64,
+    doc_freq: HashMap<String, usize>,
+}
+
+impl Bm25 {
+    pub fn new<I>(docs: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<String>>,
+    {
+        let mut term_counts = Vec::new();
+        let mut lengths = Vec::new();
+        let mut doc_freq: HashMap<String, usize> = HashMap::new();
+
+        for tokens in docs {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for token in &tokens {
+                *counts.entry(token.clone()).or_default() += 1;
+            }
+            for term in counts.keys() {
+                *doc_freq.entry(term.clone()).or_default() += 1;
+            }
+            lengths.push(tokens.len());
+            term_counts.push(counts);
+        }
+
+        let total: usize = lengths.iter().sum();
+        let avg_length = if lengths.is_empty() {
+            0.0
+        } else {
+            total as f64 / lengths.len() as f64
+        };
+
+        Self {
+            docs: term_counts,
+            lengths,
+            avg_length,
+            doc_freq,
+        }
+    }
+
+    /// Inverse document frequency; always positive, so rare terms count most.
+    fn idf(&self, term: &str) -> f64 {
+        let n = self.docs.len() as f64;
+        let df = self.doc_freq.get(term).copied().unwrap_or(0) as f64;
+        (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+    }
+
+    /// BM25 score of one document for the query terms (0 if none occur).
+    pub fn score(&self, doc: usize, query: &[String]) -> f64 {
+        let counts = &self.docs[doc];
+        let length_norm = if self.avg_length > 0.0 {
+            self.lengths[doc] as f64 / self.avg_length
+        } else {
+            0.0
+        };
+
+        query
+            .iter()
+            .filter_map(|term| counts.get(term).map(|&tf| (term, tf as f64)))
+            .map(|(term, tf)| {
+                self.idf(term) * tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * length_norm))
+            })
+            .sum()
+    }
+
+    /// Documents matching at least one query term, best first.
+    pub fn search(&self, query: &[String]) -> Vec<(usize, f64)> {
+        let mut hits: Vec<(usize, f64)> = (0..self.docs.len())
+            .map(|doc| (doc, self.score(doc, query)))
+            .filter(|&(_, score)| score > 0.0)
+            .collect();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tokenize;
+
+    #[test]
+    fn test_rare_terms_rank_higher() {
+        let index = Bm25::new(
+            [
+                "lucene codecs store postings",
+                "lucene search flow and scoring",
+                "lucene scoring with bm25 scoring",
+            ]
+            .map(tokenize),
+        );
+
+        let hits = index.search(&tokenize("bm25 scoring"));
+        let order: Vec<usize> = hits.iter().map(|&(doc, _)| doc).collect();
+        assert_eq!(order, vec![2, 1]);
+
+        // A term in every document still matches, just weakly
+        assert_eq!(index.search(&tokenize("lucene")).len(), 3);
+        assert!(index.search(&tokenize("elasticsearch")).is_empty());
+    }
+}
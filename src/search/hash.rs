@@ -0,0 +1,17 @@
+//! Stable hashing for vectors and signatures that are stored or compared
+//! across runs. `std`'s `DefaultHasher` may change between Rust releases.
+
+/// FNV-1a 64-bit hash of a string.
+pub fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// SplitMix64 step: scrambles a 64-bit state into a well-mixed value.
+pub fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
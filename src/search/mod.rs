@@ -0,0 +1,15 @@
+//! Lexical search and similarity over note text.
+//!
+//! Everything here works in memory on text the caller has already read, so
+//! commands can rank notes or sections without a prebuilt search index.
+
+pub mod bm25;
+pub mod hash;
+pub mod sections;
+pub mod semantic;
+pub mod tfidf;
+pub mod tokenize;
+
+pub use bm25::Bm25;
+pub use tfidf::TfIdf;
+pub use tokenize::tokenize;
@@ -0,0 +1,50 @@
+//! Split the vault into heading-bounded sections, the unit of search.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::parser::sections::chunks;
+use crate::parser::{MarkdownParser, TreeSitterParser, frontmatter};
+use crate::vault::Vault;
+
+/// The text under one heading (up to the next heading of any level), or
+/// before the first heading. Frontmatter is never part of a section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    pub path: PathBuf,
+    /// Note title
+    pub title: String,
+    /// Heading text, None for text before the first heading
+    pub heading: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+}
+
+/// Every non-blank section of every note, in note and document order.
+pub fn vault_sections(vault: &Vault) -> Result<Vec<Section>> {
+    let mut parser = TreeSitterParser::new()?;
+    let mut sections = Vec::new();
+
+    for note in vault.all_notes()? {
+        let content = vault.read_note(&note.path.to_string_lossy())?;
+        let headings = parser.parse(&content)?.headings;
+        let lines: Vec<&str> = content.lines().collect();
+        let first_line = frontmatter::split(&content).map_or(1, |(_, n)| n + 1);
+
+        for chunk in chunks(&lines, &headings, first_line) {
+            let (start, end) = (*chunk.lines.start(), *chunk.lines.end());
+            sections.push(Section {
+                path: note.path.clone(),
+                title: note.title.clone(),
+                heading: chunk.heading.map(|i| headings[i].text.clone()),
+                start_line: start,
+                end_line: end,
+                text: lines[start - 1..end].join("\n"),
+            });
+        }
+    }
+
+    Ok(sections)
+}
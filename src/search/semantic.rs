@@ -0,0 +1,311 @@
+//! Offline semantic vectors via random indexing.
+//!
+//! Every term gets a fixed sparse random "index vector" derived from a hash of
+//! the term. A term's meaning vector is its own index vector plus the index
+//! vectors of every word seen within a few positions of it across the vault,
+//! so terms used in similar company end up pointing the same way. A section's
+//! vector is the IDF-weighted sum of its terms' meaning vectors.
+//!
+//! Everything is computed from the vault's own text: no model download, no
+//! network, no GPU. Vectors are stored in `search.json` under the vault's
+//! index directory, so queries only have to embed the query terms. Each one is
+//! kept as a hex string of one signed byte per dimension: unit vectors lose
+//! next to nothing to the rounding, and the file is about a third the size it
+//! is with JSON floats.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::hash::{fnv1a, splitmix64};
+use super::sections::{Section, vault_sections};
+use super::tokenize;
+use crate::exclude::LOGS_DIR;
+use crate::vault::Vault;
+
+/// Vector dimensions.
+const DIMS: usize = 128;
+/// Non-zero entries in each term's random index vector.
+const NONZERO: usize = 6;
+/// Neighbouring words on each side that count as a term's context.
+const WINDOW: usize = 2;
+
+/// A term's meaning vector and inverse document frequency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermVector {
+    pub idf: f32,
+    #[serde(with = "packed")]
+    pub vector: Vec<f32>,
+}
+
+/// Where a section vector came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionVector {
+    pub path: PathBuf,
+    pub heading: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// From a daily log in `_logs/`, searched only when logs are included
+    #[serde(default)]
+    pub log: bool,
+    #[serde(with = "packed")]
+    pub vector: Vec<f32>,
+}
+
+/// Term and per-section vectors for one vault.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SemanticIndex {
+    pub dims: usize,
+    pub terms: BTreeMap<String, TermVector>,
+    pub sections: Vec<SectionVector>,
+}
+
+impl SemanticIndex {
+    /// Build from every section of the vault, daily logs included so that
+    /// `kbase search --semantic --logs` can find them.
+    pub fn build_from_vault(vault: &Vault) -> Result<Self> {
+        let mut with_logs = Vault::from_config(vault.name.clone(), vault.config.clone())?;
+        with_logs.include_logs()?;
+        Ok(Self::build(&vault_sections(&with_logs)?))
+    }
+
+    pub fn build(sections: &[Section]) -> Self {
+        let docs: Vec<Vec<String>> = sections.iter().map(|s| tokenize(&s.text)).collect();
+
+        // Meaning vectors: own index vector plus those of nearby words
+        let mut meaning: HashMap<&str, Vec<f32>> = HashMap::new();
+        let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+        for tokens in &docs {
+            for (i, term) in tokens.iter().enumerate() {
+                let vector = meaning
+                    .entry(term.as_str())
+                    .or_insert_with(|| index_vector(term));
+                let lo = i.saturating_sub(WINDOW);
+                let hi = (i + WINDOW + 1).min(tokens.len());
+                for neighbour in tokens[lo..hi].iter().filter(|t| *t != term) {
+                    add_index_vector(vector, neighbour, 1.0);
+                }
+            }
+            let unique: HashSet<&str> = tokens.iter().map(String::as_str).collect();
+            for term in unique {
+                *doc_freq.entry(term).or_default() += 1;
+            }
+        }
+
+        let n = docs.len() as f32;
+        let terms: BTreeMap<String, TermVector> = meaning
+            .into_iter()
+            .map(|(term, mut vector)| {
+                normalize(&mut vector);
+                let idf = (1.0 + n / doc_freq[term] as f32).ln();
+                (term.to_string(), TermVector { idf, vector })
+            })
+            .collect();
+
+        let mut index = SemanticIndex {
+            dims: DIMS,
+            terms,
+            sections: Vec::new(),
+        };
+        index.sections = sections
+            .iter()
+            .zip(&docs)
+            .filter_map(|(section, tokens)| {
+                Some(SectionVector {
+                    path: section.path.clone(),
+                    heading: section.heading.clone(),
+                    start_line: section.start_line,
+                    end_line: section.end_line,
+                    log: section.path.starts_with(LOGS_DIR),
+                    vector: index.embed(tokens)?,
+                })
+            })
+            .collect();
+        index
+    }
+
+    /// Embed tokenized text; None if none of its terms are known.
+    pub fn embed(&self, tokens: &[String]) -> Option<Vec<f32>> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for token in tokens {
+            *counts.entry(token).or_default() += 1;
+        }
+
+        let mut vector = vec![0.0; self.dims];
+        let mut known = false;
+        for (term, count) in counts {
+            let Some(term) = self.terms.get(term) else {
+                continue;
+            };
+            known = true;
+            let weight = (1.0 + (count as f32).ln()) * term.idf;
+            for (v, t) in vector.iter_mut().zip(&term.vector) {
+                *v += weight * t;
+            }
+        }
+
+        known.then(|| {
+            normalize(&mut vector);
+            vector
+        })
+    }
+
+    /// Sections by cosine similarity to the query, best first. Sections with
+    /// no positive similarity are left out.
+    pub fn search(&self, query: &[String]) -> Vec<(usize, f64)> {
+        let Some(query) = self.embed(query) else {
+            return Vec::new();
+        };
+
+        let mut hits: Vec<(usize, f64)> = self
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(i, section)| (i, cosine(&query, &section.vector)))
+            .filter(|&(_, score)| score > 0.0)
+            .collect();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits
+    }
+
+    pub fn load_from_json(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read search index at {}", path.display()))?;
+        serde_json::from_str(&json)
+            .context("Failed to parse search index JSON (run `kbase index` to rebuild it)")
+    }
+
+    pub fn save_to_json(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Atomic write: temp file -> rename
+        let temp_path = path.with_extension("json.tmp");
+        let file = File::create(&temp_path)?;
+        serde_json::to_writer(file, self)?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to write search index to {}", path.display()))
+    }
+}
+
+/// Deterministic sparse ±1 vector for a term.
+fn index_vector(term: &str) -> Vec<f32> {
+    let mut vector = vec![0.0; DIMS];
+    add_index_vector(&mut vector, term, 1.0);
+    vector
+}
+
+fn add_index_vector(vector: &mut [f32], term: &str, weight: f32) {
+    let mut state = fnv1a(term);
+    for _ in 0..NONZERO {
+        state = splitmix64(state);
+        let dim = (state % DIMS as u64) as usize;
+        let sign = if (state >> 32) & 1 == 0 { 1.0 } else { -1.0 };
+        vector[dim] += sign * weight;
+    }
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x * y) as f64).sum()
+}
+
+/// Serde for unit vectors as hex strings, one signed byte per dimension.
+/// Bytes are scaled so the largest component is ±127, and vectors are
+/// normalized again when read back.
+mod packed {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(vector: &[f32], serializer: S) -> Result<S::Ok, S::Error> {
+        let max = vector.iter().fold(0.0f32, |m, v| m.max(v.abs()));
+        let scale = if max > 0.0 { 127.0 / max } else { 0.0 };
+        let hex: String = vector
+            .iter()
+            .map(|v| format!("{:02x}", (v * scale).round() as i8 as u8))
+            .collect();
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f32>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if !hex.is_ascii() || hex.len() % 2 != 0 {
+            return Err(D::Error::custom("invalid packed vector"));
+        }
+        let mut vector = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map(|b| b as i8 as f32))
+            .collect::<Result<Vec<f32>, _>>()
+            .map_err(D::Error::custom)?;
+        super::normalize(&mut vector);
+        Ok(vector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(path: &str, text: &str) -> Section {
+        Section {
+            path: PathBuf::from(path),
+            title: String::new(),
+            heading: None,
+            start_line: 1,
+            end_line: text.lines().count(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_index_vectors_are_deterministic() {
+        assert_eq!(index_vector("lucene"), index_vector("lucene"));
+        assert_ne!(index_vector("lucene"), index_vector("rust"));
+    }
+
+    #[test]
+    fn test_terms_in_similar_contexts_match() {
+        // "postings" and "inverted" never share a section, but both appear
+        // next to "term dictionary"
+        let index = SemanticIndex::build(&[
+            section("a.md", "postings list term dictionary lookup"),
+            section("b.md", "inverted list term dictionary lookup"),
+            section("c.md", "borrow checker ownership lifetimes"),
+            section("d.md", "inverted"),
+        ]);
+
+        let hits = index.search(&tokenize("postings"));
+        let order: Vec<usize> = hits.iter().map(|&(i, _)| i).collect();
+        assert_eq!(order[0], 0);
+        assert!(order.contains(&1), "context should carry over: {:?}", hits);
+        assert!(!order.contains(&2));
+        assert!(index.search(&tokenize("unknown")).is_empty());
+    }
+
+    #[test]
+    fn test_packed_vectors_roundtrip() {
+        let index = SemanticIndex::build(&[
+            section("a.md", "postings list term dictionary lookup"),
+            section("b.md", "borrow checker ownership lifetimes"),
+        ]);
+        let json = serde_json::to_string(&index).unwrap();
+        let loaded: SemanticIndex = serde_json::from_str(&json).unwrap();
+
+        for (before, after) in index.sections.iter().zip(&loaded.sections) {
+            assert_eq!(after.vector.len(), DIMS);
+            assert!(cosine(&before.vector, &after.vector) > 0.999);
+        }
+        let hits = loaded.search(&tokenize("postings"));
+        assert_eq!(hits[0].0, 0);
+    }
+}
@@ -0,0 +1,114 @@
+//! TF-IDF document vectors for comparing whole texts.
+
+use std::collections::HashMap;
+
+/// L2-normalized TF-IDF vectors for a fixed list of tokenized documents.
+///
+/// Term frequency is sublinear (`1 + ln tf`) so a term repeated many times
+/// doesn't swamp the rest; terms found in every document get zero weight.
+pub struct TfIdf {
+    vectors: Vec<HashMap<String, f64>>,
+}
+
+impl TfIdf {
+    pub fn new<I>(docs: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<String>>,
+    {
+        let counts: Vec<HashMap<String, usize>> = docs
+            .into_iter()
+            .map(|tokens| {
+                let mut counts = HashMap::new();
+                for token in tokens {
+                    *counts.entry(token).or_default() += 1;
+                }
+                counts
+            })
+            .collect();
+
+        let mut doc_freq: HashMap<&str, usize> = HashMap::new();
+        for doc in &counts {
+            for term in doc.keys() {
+                *doc_freq.entry(term).or_default() += 1;
+            }
+        }
+
+        let n = counts.len() as f64;
+        let vectors = counts
+            .iter()
+            .map(|doc| {
+                let mut vector: HashMap<String, f64> = doc
+                    .iter()
+                    .map(|(term, &tf)| {
+                        let idf = (n / doc_freq[term.as_str()] as f64).ln();
+                        (term.clone(), (1.0 + (tf as f64).ln()) * idf)
+                    })
+                    .filter(|&(_, weight)| weight > 0.0)
+                    .collect();
+
+                let norm = vector.values().map(|w| w * w).sum::<f64>().sqrt();
+                if norm > 0.0 {
+                    vector.values_mut().for_each(|w| *w /= norm);
+                }
+                vector
+            })
+            .collect();
+
+        Self { vectors }
+    }
+
+    /// Cosine similarity of two documents, from 0 (nothing in common) to 1.
+    pub fn cosine(&self, a: usize, b: usize) -> f64 {
+        let (small, large) = self.ordered(a, b);
+        small
+            .iter()
+            .filter_map(|(term, w)| large.get(term).map(|v| w * v))
+            .sum()
+    }
+
+    /// Terms contributing most to the similarity of two documents.
+    pub fn shared_terms(&self, a: usize, b: usize, limit: usize) -> Vec<String> {
+        let (small, large) = self.ordered(a, b);
+        let mut shared: Vec<(&String, f64)> = small
+            .iter()
+            .filter_map(|(term, w)| large.get(term).map(|v| (term, w * v)))
+            .collect();
+        shared.sort_by(|x, y| y.1.total_cmp(&x.1).then_with(|| x.0.cmp(y.0)));
+        shared
+            .into_iter()
+            .take(limit)
+            .map(|(term, _)| term.clone())
+            .collect()
+    }
+
+    fn ordered(&self, a: usize, b: usize) -> (&HashMap<String, f64>, &HashMap<String, f64>) {
+        let (a, b) = (&self.vectors[a], &self.vectors[b]);
+        if a.len() <= b.len() { (a, b) } else { (b, a) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tokenize;
+
+    #[test]
+    fn test_cosine_favours_shared_rare_terms() {
+        let index = TfIdf::new(
+            [
+                "lucene codecs postings format",
+                "lucene postings format and skip lists",
+                "rust borrow checker lifetimes",
+            ]
+            .map(tokenize),
+        );
+
+        assert!((index.cosine(0, 0) - 1.0).abs() < 1e-9);
+        assert!(index.cosine(0, 1) > 0.2);
+        assert_eq!(index.cosine(0, 2), 0.0);
+        assert_eq!(
+            index.shared_terms(0, 1, 2),
+            vec!["format", "lucene"] // equal weights, so alphabetical
+        );
+    }
+}
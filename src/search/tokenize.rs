@@ -0,0 +1,31 @@
+//! Split text into search terms.
+
+/// Common English words that carry no meaning for ranking.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have", "in",
+    "into", "is", "it", "its", "of", "on", "or", "that", "the", "their", "this", "to", "was",
+    "were", "will", "with",
+];
+
+/// Lowercased alphanumeric terms, without stopwords and single characters.
+/// Markdown punctuation (`#`, `[[`, `**`) just acts as a separator.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_drops_markup_and_stopwords() {
+        assert_eq!(
+            tokenize("## Phase 1: The IndexSearcher.search() and [[codecs]] #wip"),
+            vec!["phase", "indexsearcher", "search", "codecs", "wip"]
+        );
+    }
+}
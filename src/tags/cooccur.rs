@@ -0,0 +1,134 @@
+//! Tag co-occurrence: which tags are used on the same notes.
+//!
+//! Counts come from inverting the tag index (note -> tags). Lift compares how
+//! often two tags share a note with what their separate frequencies predict,
+//! and PMI is its log2, so unrelated tags score around 0.
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::TagIndex;
+
+/// Two tags used on the same notes, and how much more often than chance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cooccurrence {
+    pub tag: String,
+    pub other: String,
+    /// Notes tagged with both
+    pub notes: usize,
+    /// P(tag, other) / (P(tag) P(other)): above 1 means they go together
+    pub lift: f64,
+    /// log2 of the lift
+    pub pmi: f64,
+}
+
+/// Pairwise tag counts over the tagged notes of an index.
+pub struct CooccurrenceMatrix {
+    /// Notes per tag
+    counts: HashMap<String, usize>,
+    /// Notes per pair of tags, the smaller tag first
+    pairs: BTreeMap<(String, String), usize>,
+    /// Notes with at least one tag
+    total: usize,
+}
+
+impl CooccurrenceMatrix {
+    /// Count pairs from the index's note -> tags map. Tags match exactly:
+    /// `lang/rust` and `lang` are different tags here.
+    pub fn build(index: &TagIndex) -> Self {
+        let by_note = index.tags_by_note();
+        let mut counts = HashMap::new();
+        let mut pairs = BTreeMap::new();
+
+        for tags in by_note.values() {
+            // Sorted, so each pair comes out smaller tag first
+            for (i, tag) in tags.iter().enumerate() {
+                *counts.entry(tag.clone()).or_default() += 1;
+                for other in &tags[i + 1..] {
+                    *pairs.entry((tag.clone(), other.clone())).or_default() += 1;
+                }
+            }
+        }
+
+        CooccurrenceMatrix {
+            counts,
+            pairs,
+            total: by_note.len(),
+        }
+    }
+
+    /// Every pair of tags used together at least once, sorted by tag names.
+    pub fn pairs(&self) -> Vec<Cooccurrence> {
+        self.pairs
+            .iter()
+            .map(|((tag, other), &notes)| self.score(tag, other, notes))
+            .collect()
+    }
+
+    /// Tags used together with `tag`: most shared notes first, then highest lift.
+    pub fn with_tag(&self, tag: &str) -> Vec<Cooccurrence> {
+        let mut found: Vec<Cooccurrence> = self
+            .pairs
+            .iter()
+            .filter_map(|((a, b), &notes)| {
+                let other = if a == tag {
+                    b
+                } else if b == tag {
+                    a
+                } else {
+                    return None;
+                };
+                Some(self.score(tag, other, notes))
+            })
+            .collect();
+        found.sort_by(|a, b| {
+            b.notes
+                .cmp(&a.notes)
+                .then(b.lift.total_cmp(&a.lift))
+                .then(a.other.cmp(&b.other))
+        });
+        found
+    }
+
+    fn score(&self, tag: &str, other: &str, notes: usize) -> Cooccurrence {
+        let lift = (notes * self.total) as f64 / (self.counts[tag] * self.counts[other]) as f64;
+        Cooccurrence {
+            tag: tag.to_string(),
+            other: other.to_string(),
+            notes,
+            lift,
+            pmi: lift.log2(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lift_and_pmi() {
+        let mut builder = TagIndex::builder();
+        builder.add("rust", "a.md");
+        builder.add("wip", "a.md");
+        builder.add("rust", "b.md");
+        builder.add("lucene", "c.md");
+        builder.add("wip", "d.md");
+        let matrix = CooccurrenceMatrix::build(&builder.build());
+
+        // 4 tagged notes; rust and wip are on 2 each and share 1
+        let pairs = matrix.pairs();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(
+            (pairs[0].tag.as_str(), pairs[0].other.as_str()),
+            ("rust", "wip")
+        );
+        assert_eq!(pairs[0].notes, 1);
+        assert_eq!(pairs[0].lift, 1.0);
+        assert_eq!(pairs[0].pmi, 0.0);
+
+        let with_wip = matrix.with_tag("wip");
+        assert_eq!(with_wip[0].tag, "wip");
+        assert_eq!(with_wip[0].other, "rust");
+        assert!(matrix.with_tag("lucene").is_empty());
+    }
+}
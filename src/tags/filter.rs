@@ -0,0 +1,90 @@
+//! Boolean tag filters over the tag index.
+//!
+//! Each tag expands to its nested tags (see [`TagIndex::notes_under_tag`]),
+//! then the filter is applied as set operations on note paths.
+
+use std::collections::BTreeSet;
+
+use super::TagIndex;
+
+/// Which tags a note must, may or must not have.
+#[derive(Debug, Clone, Default)]
+pub struct TagFilter {
+    /// Note must have every one of these
+    pub all: Vec<String>,
+    /// Note must have at least one of these
+    pub any: Vec<String>,
+    /// Note must have none of these
+    pub none: Vec<String>,
+}
+
+impl TagFilter {
+    pub fn is_empty(&self) -> bool {
+        self.all.is_empty() && self.any.is_empty() && self.none.is_empty()
+    }
+
+    /// Keep the paths in `paths` that pass the filter.
+    pub fn apply(&self, index: &TagIndex, paths: BTreeSet<String>) -> BTreeSet<String> {
+        let tagged =
+            |tag: &String| -> BTreeSet<String> { index.notes_under_tag(tag).into_iter().collect() };
+
+        let mut result = paths;
+        for tag in &self.all {
+            result = &result & &tagged(tag);
+        }
+        if !self.any.is_empty() {
+            let union: BTreeSet<String> = self.any.iter().flat_map(tagged).collect();
+            result = &result & &union;
+        }
+        for tag in &self.none {
+            result = &result - &tagged(tag);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> TagIndex {
+        let mut builder = TagIndex::builder();
+        builder.add("rust", "a.md");
+        builder.add("rust", "b.md");
+        builder.add("wip", "b.md");
+        builder.add("wip", "c.md");
+        builder.add("lang/go", "d.md");
+        builder.add("archived", "a.md");
+        builder.build()
+    }
+
+    fn paths() -> BTreeSet<String> {
+        ["a.md", "b.md", "c.md", "d.md", "e.md"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    fn run(all: &[&str], any: &[&str], none: &[&str]) -> Vec<String> {
+        let strings = |tags: &[&str]| tags.iter().map(|t| t.to_string()).collect();
+        let filter = TagFilter {
+            all: strings(all),
+            any: strings(any),
+            none: strings(none),
+        };
+        filter.apply(&index(), paths()).into_iter().collect()
+    }
+
+    #[test]
+    fn test_set_operations() {
+        assert_eq!(run(&["rust", "wip"], &[], &[]), vec!["b.md"]);
+        assert_eq!(
+            run(&[], &["rust", "lang"], &[]),
+            vec!["a.md", "b.md", "d.md"]
+        );
+        assert_eq!(run(&[], &[], &["rust", "wip"]), vec!["d.md", "e.md"]);
+        assert_eq!(run(&["rust"], &[], &["archived"]), vec!["b.md"]);
+        assert_eq!(run(&[], &["wip", "lang"], &["rust"]), vec!["c.md", "d.md"]);
+        assert!(run(&["missing"], &[], &[]).is_empty());
+    }
+}
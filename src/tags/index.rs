@@ -1,16 +1,53 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::{self, File};
 use std::path::Path;
 
-use crate::parser::{MarkdownParser, TreeSitterParser};
+use super::normalize::TagNormalizer;
+use super::tree::{TagTree, is_within};
+use crate::parser::{MarkdownParser, TreeSitterParser, frontmatter};
 use crate::vault::Vault;
 
+/// Where a tag appears in a note (both 1-indexed; the column points at the
+/// `#` of an inline tag, or at the value in a frontmatter list).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TagPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// tag -> note path -> positions, in document order
+type Occurrences = HashMap<String, BTreeMap<String, Vec<TagPosition>>>;
+
+/// On-disk form of the index.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StoredIndex {
+    Current {
+        tags: HashMap<String, Vec<String>>,
+        occurrences: Occurrences,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        spellings: HashMap<String, BTreeSet<String>>,
+    },
+    /// Indexes built before occurrences were stored: just tag -> paths
+    Legacy(HashMap<String, Vec<String>>),
+}
+
 /// Bidirectional index for fast tag queries.
-/// Stores tag->paths mapping on disk.
+/// Stores tag->paths mapping on disk, plus where each tag occurs.
 pub struct TagIndex {
     /// Primary storage: tag name -> list of note paths
     by_tag: HashMap<String, Vec<String>>,
+    /// Nested tags (`lang/rust`) as a hierarchy, derived from `by_tag`
+    tree: TagTree,
+    /// Line and column of every occurrence; empty for indexes built from a
+    /// plain tag map
+    occurrences: Occurrences,
+    /// Canonical tag -> the raw spellings folded into it, when they differ
+    spellings: HashMap<String, BTreeSet<String>>,
+    /// Applied to tags given at query time, matching how the index was built
+    normalizer: TagNormalizer,
 }
 
 impl TagIndex {
@@ -21,7 +58,14 @@ impl TagIndex {
             paths.sort();
         }
 
-        TagIndex { by_tag }
+        let tree = TagTree::build(&by_tag);
+        TagIndex {
+            by_tag,
+            tree,
+            occurrences: HashMap::new(),
+            spellings: HashMap::new(),
+            normalizer: TagNormalizer::default(),
+        }
     }
 
     /// Create a new builder for constructing a TagIndex.
@@ -30,9 +74,19 @@ impl TagIndex {
         TagIndexBuilder::new()
     }
 
+    /// Use `normalizer` for tags given at query time.
+    pub fn with_normalizer(mut self, normalizer: TagNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
     /// Build a TagIndex by scanning all notes in a vault.
+    /// Tags are stored in the canonical form set by the vault's `[tags]` config.
     pub fn build_from_vault(vault: &Vault) -> Result<Self> {
+        let normalizer = TagNormalizer::new(&vault.config.tags);
         let mut tag_map: HashMap<String, Vec<String>> = HashMap::new();
+        let mut occurrences: Occurrences = HashMap::new();
+        let mut spellings: HashMap<String, BTreeSet<String>> = HashMap::new();
         let all_notes = vault.all_notes()?;
         let mut parser = TreeSitterParser::new()?;
 
@@ -43,22 +97,71 @@ impl TagIndex {
             // Use tree-sitter parser to extract tags
             let parsed = parser.parse(&content)?;
 
-            for tag in parsed.tags {
+            let inline = parsed.tag_occurrences.iter().map(|t| {
+                let position = TagPosition {
+                    line: t.line,
+                    column: t.column + 1,
+                };
+                (t.tag.clone(), position)
+            });
+            let in_frontmatter = frontmatter::value_ranges(&content, &["tags", "tag"])
+                .into_iter()
+                .filter(|(tag, _)| parsed.tags.contains(tag))
+                .map(|(tag, range)| (tag, position(&content, range.start)));
+            for (tag, position) in inline.chain(in_frontmatter) {
+                occurrences
+                    .entry(normalizer.normalize(&tag))
+                    .or_default()
+                    .entry(path_str.clone())
+                    .or_default()
+                    .push(position);
+            }
+
+            // Several spellings can fold into one tag; list the note once
+            let mut tags = BTreeSet::new();
+            for raw in parsed.tags {
+                let tag = normalizer.normalize(&raw);
+                if tag != raw {
+                    spellings.entry(tag.clone()).or_default().insert(raw);
+                }
+                tags.insert(tag);
+            }
+            for tag in tags {
                 tag_map
                     .entry(tag)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(path_str.clone());
             }
         }
 
-        Ok(TagIndex::from_tag_map(tag_map))
+        for by_note in occurrences.values_mut() {
+            for positions in by_note.values_mut() {
+                positions.sort();
+            }
+        }
+
+        let mut index = TagIndex::from_tag_map(tag_map).with_normalizer(normalizer);
+        index.occurrences = occurrences;
+        index.spellings = spellings;
+        Ok(index)
     }
 
     /// Load TagIndex from JSON file.
     pub fn load_from_json(json_path: &Path) -> Result<Self> {
         let file = File::open(json_path)?;
-        let by_tag = serde_json::from_reader(file)?;
-        Ok(Self::from_tag_map(by_tag))
+        match serde_json::from_reader(file)? {
+            StoredIndex::Current {
+                tags,
+                occurrences,
+                spellings,
+            } => {
+                let mut index = Self::from_tag_map(tags);
+                index.occurrences = occurrences;
+                index.spellings = spellings;
+                Ok(index)
+            }
+            StoredIndex::Legacy(by_tag) => Ok(Self::from_tag_map(by_tag)),
+        }
     }
 
     /// Save TagIndex to JSON file atomically.
@@ -74,7 +177,12 @@ impl TagIndex {
         // temp_path: ~/.kbase/vault-name/tags.json.tmp (temporary write target)
         let temp_path = json_path.with_extension("json.tmp");
         let file = File::create(&temp_path)?;
-        serde_json::to_writer_pretty(file, &self.by_tag)?;
+        let stored = StoredIndex::Current {
+            tags: self.by_tag.clone(),
+            occurrences: self.occurrences.clone(),
+            spellings: self.spellings.clone(),
+        };
+        serde_json::to_writer_pretty(file, &stored)?;
         fs::rename(temp_path, json_path)?;
 
         Ok(())
@@ -102,31 +210,125 @@ impl TagIndex {
         tags
     }
 
-    /// Get all note paths that have the specified tag.
+    /// Count each tag's notes per domain: tag -> domain -> notes.
+    /// Notes at the vault root have no domain and aren't counted.
+    pub fn counts_by_domain(&self) -> BTreeMap<String, BTreeMap<String, usize>> {
+        let mut counts: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+        for (tag, paths) in &self.by_tag {
+            for domain in paths.iter().filter_map(|p| domain_of(p)) {
+                *counts
+                    .entry(tag.clone())
+                    .or_default()
+                    .entry(domain.to_string())
+                    .or_default() += 1;
+            }
+        }
+        counts
+    }
+
+    /// Get all note paths that have exactly the specified tag.
+    /// See [`TagIndex::notes_under_tag`] to include nested tags.
     pub fn notes_with_tag(&self, tag: &str) -> Vec<String> {
-        self.by_tag.get(tag).cloned().unwrap_or_default()
+        let tag = self.normalize(tag);
+        self.by_tag.get(&tag).cloned().unwrap_or_default()
+    }
+
+    /// Get all note paths tagged with `tag` or any tag nested under it
+    /// (`lang` includes `lang/rust` and `lang/rust/async`).
+    pub fn notes_under_tag(&self, tag: &str) -> Vec<String> {
+        self.tree
+            .find(&self.normalize(tag))
+            .map(|node| node.all_notes().into_iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Where `tag`, or any tag nested under it, occurs: note path -> positions
+    /// in document order. Empty if the index has no occurrences stored.
+    pub fn occurrences_under_tag(&self, tag: &str) -> BTreeMap<String, Vec<TagPosition>> {
+        let tag = self.normalize(tag);
+        let mut found: BTreeMap<String, Vec<TagPosition>> = BTreeMap::new();
+        for (_, by_note) in self.occurrences.iter().filter(|(t, _)| is_within(t, &tag)) {
+            for (path, positions) in by_note {
+                found.entry(path.clone()).or_default().extend(positions);
+            }
+        }
+        for positions in found.values_mut() {
+            positions.sort();
+            positions.dedup();
+        }
+        found
+    }
+
+    /// The canonical form of a tag given at query time (`#` optional).
+    pub fn normalize(&self, tag: &str) -> String {
+        self.normalizer.normalize(tag.trim_start_matches('#'))
+    }
+
+    /// Raw spellings folded into `tag` by the vault's `[tags]` config, if any.
+    pub fn spellings(&self, tag: &str) -> Option<&BTreeSet<String>> {
+        self.spellings.get(tag)
+    }
+
+    /// The index restricted to notes in `domains`, keeping positions,
+    /// spellings and the normalizer for the tags that remain.
+    pub fn in_domains(&self, domains: &[String]) -> TagIndex {
+        let by_tag = self.filter_by_domains(domains);
+        let mut index = TagIndex::from_tag_map(by_tag).with_normalizer(self.normalizer.clone());
+        index.occurrences = self
+            .occurrences
+            .iter()
+            .filter(|(tag, _)| index.by_tag.contains_key(*tag))
+            .map(|(tag, by_note)| {
+                let by_note = by_note
+                    .iter()
+                    .filter(|(path, _)| index.by_tag[tag].contains(path))
+                    .map(|(path, positions)| (path.clone(), positions.clone()))
+                    .collect();
+                (tag.clone(), by_note)
+            })
+            .collect();
+        index.spellings = self
+            .spellings
+            .iter()
+            .filter(|(tag, _)| index.by_tag.contains_key(*tag))
+            .map(|(tag, raw)| (tag.clone(), raw.clone()))
+            .collect();
+        index
+    }
+
+    /// Whether line and column positions were stored when the index was built.
+    pub fn has_occurrences(&self) -> bool {
+        !self.occurrences.is_empty()
+    }
+
+    /// Tags as a hierarchy split on `/`.
+    pub fn tree(&self) -> &TagTree {
+        &self.tree
+    }
+
+    /// Invert the index: note path -> its tags, sorted.
+    pub fn tags_by_note(&self) -> HashMap<String, Vec<String>> {
+        let mut by_note: HashMap<String, Vec<String>> = HashMap::new();
+        for (tag, paths) in &self.by_tag {
+            for path in paths {
+                by_note.entry(path.clone()).or_default().push(tag.clone());
+            }
+        }
+        for tags in by_note.values_mut() {
+            tags.sort();
+        }
+        by_note
     }
 
     /// Filter tags to only include notes in the specified domains.
     /// Returns a new tag->paths mapping with domain filtering applied.
-    /// TODO: Use this in `kbase tags --domain <domain>` command
-    #[allow(dead_code)]
     pub fn filter_by_domains(&self, domains: &[String]) -> HashMap<String, Vec<String>> {
         let mut filtered = HashMap::new();
 
         for (tag, paths) in &self.by_tag {
             let domain_paths: Vec<String> = paths
                 .iter()
-                .filter(|path| {
-                    // Extract domain: everything before first '/'
-                    if let Some(slash_pos) = path.find('/') {
-                        let domain = &path[..slash_pos];
-                        domains.contains(&domain.to_string())
-                    } else {
-                        // Root files (no '/') have no domain, never match
-                        false
-                    }
-                })
+                .filter(|path| domain_of(path).is_some_and(|d| domains.iter().any(|x| x == d)))
                 .cloned()
                 .collect();
 
@@ -139,6 +341,21 @@ impl TagIndex {
     }
 }
 
+/// Domain of a vault-relative path: everything before the first '/'.
+/// Root files have no domain.
+fn domain_of(path: &str) -> Option<&str> {
+    path.find('/').map(|slash_pos| &path[..slash_pos])
+}
+
+/// 1-indexed line and column of a byte offset.
+fn position(content: &str, offset: usize) -> TagPosition {
+    let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    TagPosition {
+        line: content[..offset].matches('\n').count() + 1,
+        column: offset - line_start + 1,
+    }
+}
+
 /// Builder for constructing a TagIndex incrementally.
 #[allow(dead_code)]
 pub struct TagIndexBuilder {
@@ -158,7 +375,7 @@ impl TagIndexBuilder {
     pub fn add(&mut self, tag: &str, path: &str) -> &mut Self {
         self.map
             .entry(tag.to_string())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(path.to_string());
         self
     }
@@ -207,6 +424,27 @@ mod tests {
         assert_eq!(index.notes_with_tag("nonexistent"), Vec::<String>::new());
     }
 
+    #[test]
+    fn test_notes_under_tag_includes_nested() {
+        let mut builder = TagIndex::builder();
+        builder.add("lang/rust", "rust/basics.md");
+        builder.add("lang/rust/async", "rust/tokio.md");
+        builder.add("lang", "notes/languages.md");
+        builder.add("language", "notes/linguistics.md");
+        let index = builder.build();
+
+        assert_eq!(index.notes_with_tag("lang"), vec!["notes/languages.md"]);
+        assert_eq!(
+            index.notes_under_tag("lang"),
+            vec!["notes/languages.md", "rust/basics.md", "rust/tokio.md"]
+        );
+        assert_eq!(
+            index.notes_under_tag("lang/rust"),
+            vec!["rust/basics.md", "rust/tokio.md"]
+        );
+        assert!(index.notes_under_tag("lang/go").is_empty());
+    }
+
     #[test]
     fn test_all_tags_sorted_by_name() {
         let index = create_test_index();
@@ -249,7 +487,7 @@ mod tests {
             &vec!["rust/advanced.md", "rust/basics.md", "rust/ownership.md"]
         );
         assert_eq!(filtered.get("wip").unwrap(), &vec!["rust/basics.md"]);
-        assert!(filtered.get("deep-dive").is_none());
+        assert!(!filtered.contains_key("deep-dive"));
     }
 
     #[test]
@@ -264,6 +502,24 @@ mod tests {
         assert!(filtered.contains_key("deep-dive"));
     }
 
+    #[test]
+    fn test_counts_by_domain() {
+        let mut builder = TagIndex::builder();
+        builder.add("wip", "rust/basics.md");
+        builder.add("wip", "rust/ownership.md");
+        builder.add("wip", "lucene/study.md");
+        builder.add("wip", "inbox.md");
+        builder.add("rust", "rust/basics.md");
+        let counts = builder.build().counts_by_domain();
+
+        // Root-level notes have no domain and aren't counted
+        assert_eq!(
+            counts["wip"],
+            BTreeMap::from([("lucene".to_string(), 1), ("rust".to_string(), 2)])
+        );
+        assert_eq!(counts["rust"], BTreeMap::from([("rust".to_string(), 1)]));
+    }
+
     #[test]
     fn test_filter_by_nonexistent_domain() {
         let index = create_test_index();
@@ -292,6 +548,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_occurrences_roundtrip_and_nest() -> Result<()> {
+        let mut index = create_test_index();
+        let at = |line, column| TagPosition { line, column };
+        index.occurrences = HashMap::from([
+            (
+                "lang".to_string(),
+                BTreeMap::from([("a.md".to_string(), vec![at(3, 1)])]),
+            ),
+            (
+                "lang/rust".to_string(),
+                BTreeMap::from([("a.md".to_string(), vec![at(1, 5), at(3, 1)])]),
+            ),
+        ]);
+
+        let temp_dir = tempdir()?;
+        let json_path = temp_dir.path().join("test_tags.json");
+        index.save_to_json(&json_path)?;
+        let loaded = TagIndex::load_from_json(&json_path)?;
+
+        assert_eq!(
+            loaded.occurrences_under_tag("lang")["a.md"],
+            vec![at(1, 5), at(3, 1)]
+        );
+        assert_eq!(loaded.occurrences_under_tag("lang/rust")["a.md"].len(), 2);
+        assert!(loaded.occurrences_under_tag("rust").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_legacy_json_without_occurrences() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let json_path = temp_dir.path().join("test_tags.json");
+        fs::write(&json_path, r#"{"rust": ["rust/basics.md"]}"#)?;
+
+        let loaded = TagIndex::load_from_json(&json_path)?;
+        assert_eq!(loaded.notes_with_tag("rust"), vec!["rust/basics.md"]);
+        assert!(!loaded.has_occurrences());
+        Ok(())
+    }
+
     #[test]
     fn test_load_from_nonexistent_file() {
         let temp_dir = tempdir().unwrap();
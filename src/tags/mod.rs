@@ -1,6 +1,14 @@
 // Public interface for tag functionality
 
+pub mod cooccur;
+pub mod filter;
 pub mod index;
+pub mod normalize;
+pub mod tree;
 
 // Re-export commonly used types and functions
+pub use cooccur::CooccurrenceMatrix;
+pub use filter::TagFilter;
 pub use index::TagIndex;
+pub use normalize::{TagConfig, TagNormalizer};
+pub use tree::{TagNode, is_within};
@@ -0,0 +1,137 @@
+//! Per-vault tag normalization.
+//!
+//! `[vaults.<name>.tags]` in the config can fold spelling variants together:
+//! case, Unicode composition, trailing punctuation, and explicit aliases.
+//! The same [`TagNormalizer`] runs when the tag index is built and on tags
+//! given at query time, so `#WIP`, `#wip.` and an aliased `#in-progress` all
+//! end up as one entry.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use super::tree::is_within;
+
+/// Tag normalization settings for a vault (`[vaults.<name>.tags]`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TagConfig {
+    /// Treat tags that differ only in case as the same (folded to lowercase)
+    pub case_insensitive: bool,
+    /// Compare tags in Unicode NFC, so composed and decomposed accents match
+    pub unicode_nfc: bool,
+    /// Drop trailing punctuation: `#wip.` and `#wip!` become `#wip`
+    pub strip_punctuation: bool,
+    /// Canonical tag -> other spellings of it
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, Vec<String>>,
+}
+
+impl TagConfig {
+    pub fn is_default(&self) -> bool {
+        *self == TagConfig::default()
+    }
+}
+
+/// Maps raw tags to their canonical form under a [`TagConfig`].
+#[derive(Debug, Clone, Default)]
+pub struct TagNormalizer {
+    config: TagConfig,
+    /// (normalized alias, normalized canonical tag), longest alias first
+    aliases: Vec<(String, String)>,
+}
+
+impl TagNormalizer {
+    pub fn new(config: &TagConfig) -> Self {
+        let mut normalizer = TagNormalizer {
+            config: config.clone(),
+            aliases: Vec::new(),
+        };
+
+        let mut aliases: Vec<(String, String)> = config
+            .aliases
+            .iter()
+            .flat_map(|(canonical, spellings)| {
+                let canonical = normalizer.clean(canonical);
+                spellings
+                    .iter()
+                    .map(move |alias| (alias, canonical.clone()))
+            })
+            .map(|(alias, canonical)| (normalizer.clean(alias), canonical))
+            .filter(|(alias, canonical)| alias != canonical)
+            .collect();
+        // Longest first, so `wip/old` wins over `wip` for `#wip/old/x`
+        aliases.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then(a.cmp(b)));
+        normalizer.aliases = aliases;
+        normalizer
+    }
+
+    /// The canonical form of `tag` (given without `#`). Aliases apply to
+    /// nested tags too: with `wip = ["in-progress"]`, `#in-progress/review`
+    /// becomes `wip/review`.
+    pub fn normalize(&self, tag: &str) -> String {
+        let tag = self.clean(tag);
+        let alias = self
+            .aliases
+            .iter()
+            .find(|(alias, _)| is_within(&tag, alias));
+        match alias {
+            Some((alias, canonical)) => format!("{}{}", canonical, &tag[alias.len()..]),
+            None => tag,
+        }
+    }
+
+    /// Case, NFC and punctuation rules, without aliases.
+    fn clean(&self, tag: &str) -> String {
+        let mut tag = if self.config.unicode_nfc {
+            tag.nfc().collect()
+        } else {
+            tag.to_string()
+        };
+        if self.config.strip_punctuation {
+            let stripped = tag.trim_end_matches(|c: char| !c.is_alphanumeric());
+            // A tag that is all punctuation is left alone rather than emptied
+            if !stripped.is_empty() {
+                tag.truncate(stripped.len());
+            }
+        }
+        if self.config.case_insensitive {
+            tag = tag.to_lowercase();
+        }
+        tag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_leaves_tags_alone() {
+        let normalizer = TagNormalizer::new(&TagConfig::default());
+        assert_eq!(normalizer.normalize("WIP."), "WIP.");
+    }
+
+    #[test]
+    fn test_normalize_applies_rules_then_aliases() {
+        let config = TagConfig {
+            case_insensitive: true,
+            unicode_nfc: true,
+            strip_punctuation: true,
+            aliases: BTreeMap::from([(
+                "wip".to_string(),
+                vec!["in-progress".to_string(), "WIP".to_string()],
+            )]),
+        };
+        let normalizer = TagNormalizer::new(&config);
+
+        assert_eq!(normalizer.normalize("WIP"), "wip");
+        assert_eq!(normalizer.normalize("In-Progress!"), "wip");
+        assert_eq!(normalizer.normalize("in-progress/review"), "wip/review");
+        assert_eq!(normalizer.normalize("in-progressive"), "in-progressive");
+        // "e" + combining acute accent composes to "é"
+        assert_eq!(normalizer.normalize("Cafe\u{301}"), "caf\u{e9}");
+        assert_eq!(normalizer.normalize("---"), "---");
+    }
+}
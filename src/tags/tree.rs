@@ -0,0 +1,138 @@
+//! Nested tags as a hierarchy.
+//!
+//! `#lang/rust/async` is stored in the index as one tag, but also sits under
+//! `lang/rust` and `lang` here, so lookups and counts can roll up a whole
+//! branch. Ancestors that are never used as tags on their own still get a
+//! node, with no notes of their own.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// One segment of a tag path, with the notes tagged exactly with it.
+#[derive(Debug, Default)]
+pub struct TagNode {
+    /// Full tag up to this segment (`lang/rust`)
+    pub tag: String,
+    /// Notes tagged with exactly this tag
+    pub notes: BTreeSet<String>,
+    pub children: BTreeMap<String, TagNode>,
+}
+
+impl TagNode {
+    /// Last segment of the tag (`rust` for `lang/rust`).
+    pub fn name(&self) -> &str {
+        self.tag.rsplit('/').next().unwrap_or(&self.tag)
+    }
+
+    /// Notes tagged with this tag or any tag below it, without duplicates.
+    pub fn all_notes(&self) -> BTreeSet<String> {
+        let mut notes = self.notes.clone();
+        for child in self.children.values() {
+            notes.extend(child.all_notes());
+        }
+        notes
+    }
+}
+
+/// Prefix tree of tags split on `/`.
+#[derive(Debug, Default)]
+pub struct TagTree {
+    roots: BTreeMap<String, TagNode>,
+}
+
+impl TagTree {
+    pub fn build(by_tag: &HashMap<String, Vec<String>>) -> Self {
+        let mut tree = TagTree::default();
+        for (tag, paths) in by_tag {
+            let mut nodes = &mut tree.roots;
+            let mut prefix = String::new();
+            let mut node = None;
+            for segment in tag.split('/').filter(|s| !s.is_empty()) {
+                if !prefix.is_empty() {
+                    prefix.push('/');
+                }
+                prefix.push_str(segment);
+                let entry = nodes.entry(segment.to_string()).or_insert_with(|| TagNode {
+                    tag: prefix.clone(),
+                    ..Default::default()
+                });
+                nodes = &mut entry.children;
+                node = Some(&mut entry.notes);
+            }
+            if let Some(notes) = node {
+                notes.extend(paths.iter().cloned());
+            }
+        }
+        tree
+    }
+
+    /// Top-level tags, alphabetically.
+    pub fn roots(&self) -> impl Iterator<Item = &TagNode> {
+        self.roots.values()
+    }
+
+    /// The node for a tag, whether or not it's used on its own.
+    pub fn find(&self, tag: &str) -> Option<&TagNode> {
+        let mut segments = tag.split('/').filter(|s| !s.is_empty());
+        let mut node = self.roots.get(segments.next()?)?;
+        for segment in segments {
+            node = node.children.get(segment)?;
+        }
+        Some(node)
+    }
+}
+
+/// True if `tag` is `ancestor` or nested under it (`lang/rust` is within `lang`).
+pub fn is_within(tag: &str, ancestor: &str) -> bool {
+    let ancestor = ancestor.trim_end_matches('/');
+    tag.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> TagTree {
+        let by_tag: HashMap<String, Vec<String>> = [
+            ("lang/rust", vec!["a.md", "b.md"]),
+            ("lang/rust/async", vec!["b.md", "c.md"]),
+            ("lang/go", vec!["d.md"]),
+            ("language", vec!["e.md"]),
+        ]
+        .into_iter()
+        .map(|(tag, paths)| {
+            (
+                tag.to_string(),
+                paths.iter().map(|p| p.to_string()).collect(),
+            )
+        })
+        .collect();
+        TagTree::build(&by_tag)
+    }
+
+    #[test]
+    fn test_rolls_up_descendants() {
+        let tree = tree();
+        let lang = tree.find("lang").unwrap();
+        assert!(lang.notes.is_empty());
+        assert_eq!(
+            lang.all_notes().into_iter().collect::<Vec<_>>(),
+            vec!["a.md", "b.md", "c.md", "d.md"]
+        );
+        assert_eq!(tree.find("lang/rust").unwrap().all_notes().len(), 3);
+        assert_eq!(tree.find("lang/rust/async").unwrap().name(), "async");
+        assert!(tree.find("lang/python").is_none());
+
+        let roots: Vec<&str> = tree.roots().map(|n| n.tag.as_str()).collect();
+        assert_eq!(roots, vec!["lang", "language"]);
+    }
+
+    #[test]
+    fn test_is_within_matches_whole_segments() {
+        assert!(is_within("lang", "lang"));
+        assert!(is_within("lang/rust/async", "lang"));
+        assert!(is_within("lang/rust", "lang/"));
+        assert!(!is_within("language", "lang"));
+        assert!(!is_within("lang", "lang/rust"));
+    }
+}
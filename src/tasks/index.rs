@@ -0,0 +1,137 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::Path;
+
+use crate::parser::{MarkdownParser, Task, TreeSitterParser};
+use crate::vault::Vault;
+
+/// Index of tasks across the vault.
+/// Stores note path -> tasks (in line order) on disk; notes without tasks are omitted.
+pub struct TaskIndex {
+    by_note: BTreeMap<String, Vec<Task>>,
+}
+
+impl TaskIndex {
+    /// Build TaskIndex from a path->tasks mapping.
+    pub fn from_map(mut by_note: BTreeMap<String, Vec<Task>>) -> Self {
+        by_note.retain(|_, tasks| !tasks.is_empty());
+        TaskIndex { by_note }
+    }
+
+    /// Build a TaskIndex by scanning all notes in a vault.
+    pub fn build_from_vault(vault: &Vault) -> Result<Self> {
+        let mut by_note = BTreeMap::new();
+        let mut parser = TreeSitterParser::new()?;
+
+        for note in vault.all_notes()? {
+            let path_str = note.path.to_string_lossy().to_string();
+            let content = vault.read_note(&path_str)?;
+            let parsed = parser.parse(&content)?;
+            by_note.insert(path_str, parsed.tasks);
+        }
+
+        Ok(TaskIndex::from_map(by_note))
+    }
+
+    /// Load TaskIndex from JSON file.
+    pub fn load_from_json(json_path: &Path) -> Result<Self> {
+        let file = File::open(json_path)?;
+        let by_note = serde_json::from_reader(file)?;
+        Ok(Self::from_map(by_note))
+    }
+
+    /// Save TaskIndex to JSON file atomically.
+    pub fn save_to_json(&self, json_path: &Path) -> Result<()> {
+        if let Some(parent) = json_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let temp_path = json_path.with_extension("json.tmp");
+        let file = File::create(&temp_path)?;
+        serde_json::to_writer_pretty(file, &self.by_note)?;
+        fs::rename(temp_path, json_path)?;
+
+        Ok(())
+    }
+
+    /// Tasks recorded for a note (empty if it has none).
+    pub fn get(&self, path: &str) -> &[Task] {
+        self.by_note.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Total number of tasks.
+    pub fn task_count(&self) -> usize {
+        self.by_note.values().map(Vec::len).sum()
+    }
+
+    /// All tasks with their note paths, ordered by path then line.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Task)> {
+        self.by_note
+            .iter()
+            .flat_map(|(path, tasks)| tasks.iter().map(move |task| (path.as_str(), task)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::TaskStatus;
+    use tempfile::tempdir;
+
+    fn task(text: &str, line: usize) -> Task {
+        Task {
+            status: TaskStatus::Todo,
+            text: text.to_string(),
+            line,
+            heading: None,
+            tags: Vec::new(),
+            depth: 0,
+            parent: None,
+            priority: None,
+            due: None,
+            scheduled: None,
+            start: None,
+            completed: None,
+            recurrence: None,
+        }
+    }
+
+    #[test]
+    fn test_from_map_drops_notes_without_tasks() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "b.md".to_string(),
+            vec![task("second", 3), task("third", 4)],
+        );
+        map.insert("a.md".to_string(), vec![task("first", 1)]);
+        map.insert("c.md".to_string(), Vec::new());
+
+        let index = TaskIndex::from_map(map);
+        assert_eq!(index.task_count(), 3);
+
+        let order: Vec<_> = index.iter().map(|(path, t)| (path, t.line)).collect();
+        assert_eq!(order, vec![("a.md", 1), ("b.md", 3), ("b.md", 4)]);
+    }
+
+    #[test]
+    fn test_save_and_load_json() -> Result<()> {
+        let mut map = BTreeMap::new();
+        let mut done = task("Read BlockTree", 6);
+        done.status = TaskStatus::Done;
+        done.heading = Some("This Week".into());
+        done.parent = Some(5);
+        done.depth = 1;
+        map.insert("lucene/board.md".to_string(), vec![task("Review", 5), done]);
+
+        let temp_dir = tempdir()?;
+        let json_path = temp_dir.path().join("tasks.json");
+        TaskIndex::from_map(map.clone()).save_to_json(&json_path)?;
+
+        let loaded = TaskIndex::load_from_json(&json_path)?;
+        let tasks: Vec<Task> = loaded.iter().map(|(_, t)| t.clone()).collect();
+        assert_eq!(tasks, map["lucene/board.md"]);
+
+        Ok(())
+    }
+}
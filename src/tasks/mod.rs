@@ -0,0 +1,5 @@
+// Public interface for task (`- [ ]` list item) functionality
+
+pub mod index;
+
+pub use index::TaskIndex;
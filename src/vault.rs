@@ -1,17 +1,29 @@
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
 use walkdir::WalkDir;
 
-use crate::config::kbase_home;
+use crate::config::{VaultConfig, kbase_home};
 use crate::domains;
-use crate::tags::TagIndex;
+use crate::exclude::{Exclusions, LOGS_DIR};
+use crate::links::LinkIndex;
+use crate::links::resolve::{AliasMap, NoteMatch, resolve_note_name, resolve_note_name_exact};
+use crate::metadata::MetadataIndex;
+use crate::parser::frontmatter;
+use crate::search::semantic::SemanticIndex;
+use crate::tags::{TagIndex, TagNormalizer};
+use crate::tasks::TaskIndex;
 
 /// An open markdown vault rooted at a filesystem path.
 pub struct Vault {
     pub root: PathBuf,
     pub name: String,
+    /// Settings from the vault's entry in config.toml
+    pub config: VaultConfig,
+    /// Files and folders left out of notes and domains
+    exclusions: Exclusions,
 }
 
 /// A top-level domain folder inside a vault.
@@ -30,9 +42,28 @@ pub struct Note {
 }
 
 impl Vault {
-    pub fn open(root: PathBuf, name: String) -> Result<Self> {
-        validate_dir(&root, "Vault path")?;
-        Ok(Vault { root, name })
+    /// Open a vault from its config.toml entry, keeping its settings.
+    pub fn from_config(name: String, config: VaultConfig) -> Result<Self> {
+        validate_dir(&config.path, "Vault path")?;
+        Ok(Vault {
+            root: config.path.clone(),
+            exclusions: Exclusions::new(&config.path, &config)?,
+            name,
+            config,
+        })
+    }
+
+    /// Also include notes under `_logs/`, which are left out by default.
+    pub fn include_logs(&mut self) -> Result<()> {
+        self.config.include.push(LOGS_DIR.to_string());
+        self.exclusions = Exclusions::new(&self.root, &self.config)?;
+        Ok(())
+    }
+
+    /// Whether notes under `_logs/` are part of this vault, through config
+    /// `include` or [`Vault::include_logs`].
+    pub fn logs_included(&self) -> bool {
+        !self.exclusions.is_excluded(Path::new(LOGS_DIR), true)
     }
 
     /// Get the directory where indexes for this vault are stored.
@@ -47,7 +78,9 @@ impl Vault {
         if !path.exists() {
             return Ok(None);
         }
-        Ok(Some(TagIndex::load_from_json(&path)?))
+        let normalizer = TagNormalizer::new(&self.config.tags);
+        let index = TagIndex::load_from_json(&path)?.with_normalizer(normalizer);
+        Ok(Some(index))
     }
 
     /// Save the tag index for this vault.
@@ -56,6 +89,71 @@ impl Vault {
         index.save_to_json(&path)
     }
 
+    /// Load the link index for this vault.
+    /// Returns None if the index hasn't been built yet.
+    pub fn load_link_index(&self) -> Result<Option<LinkIndex>> {
+        let index_dir = self.index_dir()?;
+        if !index_dir.join("links-forward.json").exists() {
+            return Ok(None);
+        }
+        Ok(Some(
+            LinkIndex::load_from_json(&index_dir).context("Failed to load link index")?,
+        ))
+    }
+
+    /// Save the link index for this vault.
+    pub fn save_link_index(&self, index: &LinkIndex) -> Result<()> {
+        index.save_to_json(&self.index_dir()?)
+    }
+
+    /// Load the metadata index for this vault.
+    /// Returns None if the index hasn't been built yet.
+    pub fn load_metadata_index(&self) -> Result<Option<MetadataIndex>> {
+        let path = self.index_dir()?.join("metadata.json");
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(MetadataIndex::load_from_json(&path)?))
+    }
+
+    /// Save the metadata index for this vault.
+    pub fn save_metadata_index(&self, index: &MetadataIndex) -> Result<()> {
+        let path = self.index_dir()?.join("metadata.json");
+        index.save_to_json(&path)
+    }
+
+    /// Load the task index for this vault.
+    /// Returns None if the index hasn't been built yet.
+    pub fn load_task_index(&self) -> Result<Option<TaskIndex>> {
+        let path = self.index_dir()?.join("tasks.json");
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(TaskIndex::load_from_json(&path)?))
+    }
+
+    /// Save the task index for this vault.
+    pub fn save_task_index(&self, index: &TaskIndex) -> Result<()> {
+        let path = self.index_dir()?.join("tasks.json");
+        index.save_to_json(&path)
+    }
+
+    /// Load the semantic search index for this vault.
+    /// Returns None if the index hasn't been built yet.
+    pub fn load_search_index(&self) -> Result<Option<SemanticIndex>> {
+        let path = self.index_dir()?.join("search.json");
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(SemanticIndex::load_from_json(&path)?))
+    }
+
+    /// Save the semantic search index for this vault.
+    pub fn save_search_index(&self, index: &SemanticIndex) -> Result<()> {
+        let path = self.index_dir()?.join("search.json");
+        index.save_to_json(&path)
+    }
+
     /// Get the description for a domain by reading its description files.
     /// Returns None if no description file exists.
     pub fn domain_description(&self, domain_name: &str) -> Option<String> {
@@ -63,7 +161,8 @@ impl Vault {
         domains::extract_description(&domain_path)
     }
 
-    /// List all domain folders (top-level dirs, excluding those starting with `_` or `.`).
+    /// List all domain folders: top-level dirs not excluded (by default, those
+    /// starting with `_` or `.`; see [`Exclusions`]).
     pub fn domains(&self) -> Result<Vec<Domain>> {
         let mut domains: Vec<Domain> = read_dir(&self.root)?
             .filter_map(|entry| {
@@ -79,11 +178,11 @@ impl Vault {
                 let name = path.file_name()?.to_str()?.to_string();
 
                 // Skip excluded directories
-                if is_excluded_domain(&name) {
+                if self.exclusions.is_excluded(&path, true) {
                     return None;
                 }
 
-                let note_count = count_md_files(&path).unwrap_or(0);
+                let note_count = self.count_md_files(&path);
 
                 Some(Domain { name, note_count })
             })
@@ -103,6 +202,56 @@ impl Vault {
         fs::read_to_string(&full_path).with_context(|| format!("Could not read {}", path))
     }
 
+    /// Look up a note argument: a vault-relative path with or without `.md`,
+    /// a short name (`search-flow`) or a unique fragment of the path.
+    /// See [`resolve_note_name`] for the matching rules.
+    pub fn find_note(&self, name: &str) -> Result<NoteMatch> {
+        // An existing file is taken as-is, even outside the indexed folders
+        if name.ends_with(".md") && self.root.join(name).is_file() {
+            return Ok(NoteMatch::Found(PathBuf::from(name)));
+        }
+        let all_notes: HashSet<PathBuf> = self.all_notes()?.into_iter().map(|n| n.path).collect();
+        Ok(resolve_note_name(name, &all_notes))
+    }
+
+    /// Like [`Vault::find_note`], but a fragment of a path doesn't count: only
+    /// paths and short names match. See [`resolve_note_name_exact`].
+    pub fn find_note_exact(&self, name: &str) -> Result<NoteMatch> {
+        if name.ends_with(".md") && self.root.join(name).is_file() {
+            return Ok(NoteMatch::Found(PathBuf::from(name)));
+        }
+        let all_notes: HashSet<PathBuf> = self.all_notes()?.into_iter().map(|n| n.path).collect();
+        Ok(resolve_note_name_exact(name, &all_notes))
+    }
+
+    /// Resolve a note argument to its vault-relative path, failing with the
+    /// list of candidates if it matches more than one note.
+    pub fn resolve_note(&self, name: &str) -> Result<PathBuf> {
+        self.find_note(name)?
+            .found(name)?
+            .with_context(|| format!("note not found: {}", name))
+    }
+
+    /// Frontmatter aliases of every note, for resolving `[[Alias]]` links the
+    /// way the link index does.
+    pub fn aliases(&self) -> Result<AliasMap> {
+        let mut aliases = AliasMap::new();
+        for note in self.all_notes()? {
+            let content = self.read_note(&note.path.to_string_lossy())?;
+            let Some((yaml, _)) = frontmatter::split(&content) else {
+                continue;
+            };
+            let metadata = frontmatter::parse_yaml(yaml);
+            for alias in frontmatter::list_values(&metadata, &["aliases", "alias"]) {
+                aliases
+                    .entry(alias.to_lowercase())
+                    .or_default()
+                    .push(note.path.clone());
+            }
+        }
+        Ok(aliases)
+    }
+
     /// List all .md notes across the entire vault (all domains + root level).
     pub fn all_notes(&self) -> Result<Vec<Note>> {
         let mut all = Vec::new();
@@ -112,7 +261,7 @@ impl Vault {
             let entry = entry?;
             let path = entry.path();
             
-            if is_valid_note(&path) {
+            if self.is_valid_note(&path) {
                 let filename = path.file_name()
                     .and_then(|n| n.to_str())
                     .unwrap_or("")
@@ -148,18 +297,19 @@ impl Vault {
         let domain_path = self.root.join(domain);
         validate_dir(&domain_path, &format!("Domain '{}'", domain))?;
 
-        let mut notes: Vec<Note> = create_vault_walker(&domain_path)
+        let mut notes: Vec<Note> = self
+            .walk(&domain_path)
             .filter_map(|entry| {
                 let path = entry.path();
 
-                if !is_valid_note(path) {
+                if !self.is_valid_note(path) {
                     return None;
                 }
 
                 let filename = path.file_name()?.to_str()?.to_string();
                 let stem = path.file_stem()?.to_str().unwrap_or(&filename).to_string();
-                let title = read_first_heading(&path).unwrap_or(stem);
-                let rel_path = path.strip_prefix(&self.root).unwrap_or(&path).to_path_buf();
+                let title = read_first_heading(path).unwrap_or(stem);
+                let rel_path = path.strip_prefix(&self.root).unwrap_or(path).to_path_buf();
 
                 Some(Note {
                     path: rel_path,
@@ -172,37 +322,33 @@ impl Vault {
         notes.sort_by(|a, b| a.filename.cmp(&b.filename));
         Ok(notes)
     }
+
+    /// Create a recursive walker that skips excluded directories.
+    fn walk<'a>(&'a self, dir: &Path) -> impl Iterator<Item = walkdir::DirEntry> + 'a {
+        WalkDir::new(dir)
+            .into_iter()
+            .filter_entry(|e| !e.path().is_dir() || !self.exclusions.is_excluded(e.path(), true))
+            .filter_map(|e| e.ok())
+    }
+
+    /// Check if a path is a markdown note that isn't excluded.
+    fn is_valid_note(&self, path: &Path) -> bool {
+        path.is_file()
+            && path.extension().and_then(|e| e.to_str()) == Some("md")
+            && !self.exclusions.is_excluded(path, false)
+    }
+
+    fn count_md_files(&self, dir: &Path) -> usize {
+        self.walk(dir)
+            .filter(|entry| self.is_valid_note(entry.path()))
+            .count()
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
-/// Create a recursive walker that skips excluded directories (_*, .*).
-fn create_vault_walker(dir: &Path) -> impl Iterator<Item = walkdir::DirEntry> {
-    WalkDir::new(dir)
-        .into_iter()
-        .filter_entry(|e| {
-            if !e.path().is_dir() {
-                return true;
-            }
-            e.file_name()
-                .to_str()
-                .map_or(true, |name| !is_excluded_domain(name))
-        })
-        .filter_map(|e| e.ok())
-}
-
-/// Check if a path is a valid markdown note (not metadata, is .md file).
-fn is_valid_note(path: &Path) -> bool {
-    path.is_file()
-        && path.extension().and_then(|e| e.to_str()) == Some("md")
-        && path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .map_or(false, |name| !is_metadata_file(name))
-}
-
 /// Validate that a path exists and is a directory.
 fn validate_dir(path: &Path, label: &str) -> Result<()> {
     if !path.exists() {
@@ -219,33 +365,23 @@ fn read_dir(path: &Path) -> Result<fs::ReadDir> {
     fs::read_dir(path).with_context(|| format!("Could not read directory: {}", path.display()))
 }
 
-/// Returns true if a directory name should be excluded from domains.
-/// Currently excludes hidden dirs (.) and utility dirs (_).
-/// TODO: Make this configurable via Config
-fn is_excluded_domain(name: &str) -> bool {
-    name.starts_with('.') || name.starts_with('_')
-}
+/// Read a note's title: frontmatter `title:` if present, else the first
+/// level-1 heading (`# Title`). Only scans the first 20 body lines for performance.
+pub(crate) fn read_first_heading(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
 
-/// Returns true if a file is a metadata/description file and should not be counted as a note.
-/// Metadata files: _description.md, description.md, or files starting with _ or .
-fn is_metadata_file(filename: &str) -> bool {
-    if filename.starts_with('.') || filename.starts_with('_') {
-        return true;
+    let mut body_start = 0;
+    if let Some((yaml, lines)) = frontmatter::split(&content) {
+        let metadata = frontmatter::parse_yaml(yaml);
+        if let Some(title) = metadata.get("title").map(|t| t.to_string())
+            && !title.trim().is_empty()
+        {
+            return Some(title.trim().to_string());
+        }
+        body_start = lines;
     }
-    matches!(filename, "description.md")
-}
-
-fn count_md_files(dir: &Path) -> Result<usize> {
-    Ok(create_vault_walker(dir)
-        .filter(|entry| is_valid_note(entry.path()))
-        .count())
-}
 
-/// Read the first level-1 heading (`# Title`) from a file.
-/// Only scans the first 20 lines for performance.
-fn read_first_heading(path: &Path) -> Option<String> {
-    let content = fs::read_to_string(path).ok()?;
-    for line in content.lines().take(20) {
+    for line in content.lines().skip(body_start).take(20) {
         if let Some(rest) = line.trim().strip_prefix("# ") {
             let title = rest.trim().to_string();
             if !title.is_empty() {
@@ -61,7 +61,7 @@ fn copy_dir(src: &Path, dst: &Path) -> std::io::Result<()> {
             fs::create_dir_all(&dst_path)?;
             copy_dir(&entry.path(), &dst_path)?;
         } else {
-            fs::copy(&entry.path(), &dst_path)?;
+            fs::copy(entry.path(), &dst_path)?;
         }
     }
     Ok(())
@@ -0,0 +1,159 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use serde_json::Value;
+use std::fs;
+
+fn context_json(tmp: &tempfile::TempDir, args: &[&str]) -> Value {
+    let output = kbase(tmp)
+        .arg("context")
+        .args(args)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn context_gathers_seed_links_and_backlinks() {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("lucene/scoring-notes.md"),
+        "# Scoring Notes\n\nFollow-up to [[search-flow]].\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["context", "lucene/search-flow.md"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "<!-- kbase: lucene/search-flow.md:1-7 (seed, score 2.00) -->\n# Search Flow Deep Dive\n",
+        ))
+        .stdout(contains("<!-- kbase: end lucene/search-flow.md:1-7 -->"))
+        .stdout(contains("<!-- kbase: lucene/codecs.md:1-5 (links-to"))
+        .stdout(contains("<!-- kbase: lucene/scoring-notes.md:1-3 (linked-from"));
+}
+
+#[test]
+fn context_stays_within_token_budget() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    let bundle = context_json(&tmp, &["lucene/search-flow.md", "--max-tokens", "80"]);
+    let pieces = bundle["pieces"].as_array().unwrap();
+    let total: u64 = pieces.iter().map(|p| p["tokens"].as_u64().unwrap()).sum();
+
+    assert_eq!(bundle["tokens"].as_u64().unwrap(), total);
+    assert!(total <= 80);
+    assert!(bundle["omitted"].as_u64().unwrap() > 0);
+
+    // The seed's opening section ranks first and is never split
+    assert_eq!(pieces[0]["path"], "lucene/search-flow.md");
+    assert_eq!(pieces[0]["start_line"], 1);
+    assert_eq!(pieces[0]["end_line"], 7);
+    assert_eq!(pieces[0]["source"], "seed");
+}
+
+#[test]
+fn context_trims_seed_section_to_fit() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    let bundle = context_json(&tmp, &["lucene/search-flow.md", "--max-tokens", "20"]);
+    let pieces = bundle["pieces"].as_array().unwrap();
+
+    assert!(bundle["tokens"].as_u64().unwrap() <= 20);
+    assert_eq!(pieces[0]["path"], "lucene/search-flow.md");
+    assert_eq!(pieces[0]["start_line"], 1);
+    assert_eq!(pieces[0]["truncated"], true);
+    assert!(pieces[0]["end_line"].as_u64().unwrap() < 7);
+    assert!(
+        pieces[0]["content"]
+            .as_str()
+            .unwrap()
+            .starts_with("# Search Flow Deep Dive")
+    );
+}
+
+#[test]
+fn context_query_seeds_from_best_match() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    let bundle = context_json(&tmp, &["bm25 scoring"]);
+    assert_eq!(bundle["seed"], "lucene/search-flow.md");
+    assert_eq!(bundle["query"], "bm25 scoring");
+
+    let best = bundle["pieces"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .max_by(|a, b| {
+            a["score"]
+                .as_f64()
+                .partial_cmp(&b["score"].as_f64())
+                .unwrap()
+        })
+        .unwrap();
+    assert_eq!(best["heading"], "Phase 2: Scoring");
+}
+
+#[test]
+fn context_unknown_query_errors() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["context", "zebra giraffe"])
+        .assert()
+        .failure()
+        .stderr(contains("No notes match 'zebra giraffe'"));
+}
+
+#[test]
+fn context_requires_link_index() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["context", "lucene/search-flow.md"])
+        .assert()
+        .failure()
+        .stderr(contains("Link index not found"));
+}
+
+#[test]
+fn context_searches_names_that_are_not_a_note() {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("rust/zebra-notes.md"),
+        "# Misc\n\nNothing here.\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("rust/stripes.md"),
+        "# Stripes\n\nA zebra has stripes.\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    // Matches several paths: searched, not an error
+    let bundle = context_json(&tmp, &["lucene"]);
+    assert_eq!(bundle["query"], "lucene");
+
+    // Only part of one path: searched, not taken as the seed
+    let bundle = context_json(&tmp, &["zebra"]);
+    assert_eq!(bundle["seed"], "rust/stripes.md");
+    assert_eq!(bundle["query"], "zebra");
+
+    // A short name still names the seed
+    let bundle = context_json(&tmp, &["codecs"]);
+    assert_eq!(bundle["seed"], "lucene/codecs.md");
+}
@@ -2,6 +2,8 @@ mod common;
 
 use assert_cmd::cargo::cargo_bin_cmd;
 use common::{kbase, setup_vault};
+use std::fs;
+use std::io::Write;
 
 #[test]
 fn domains_lists_all() {
@@ -92,3 +94,38 @@ fn domains_no_vault_shows_error() {
         "Error: No config found. Run `kbase config add <name> <path>` to add a vault.\n"
     );
 }
+
+#[test]
+fn domains_respect_kbaseignore_and_config_patterns() {
+    let tmp = setup_vault();
+    fs::create_dir_all(tmp.path().join("archive")).unwrap();
+    fs::write(tmp.path().join("archive/old.md"), "# Old\n").unwrap();
+    fs::create_dir_all(tmp.path().join("_inbox")).unwrap();
+    fs::write(tmp.path().join("_inbox/idea.md"), "# Idea\n").unwrap();
+    fs::write(tmp.path().join("lucene/scratch.md"), "# Scratch\n").unwrap();
+    fs::write(tmp.path().join(".kbaseignore"), "archive/\n").unwrap();
+
+    // kbase() rewrites config.toml, so the patterns go in afterwards
+    let mut cmd = kbase(&tmp);
+    let mut config = fs::OpenOptions::new()
+        .append(true)
+        .open(tmp.path().join(".kbase/config.toml"))
+        .unwrap();
+    writeln!(
+        config,
+        "exclude = [\"lucene/scratch.md\"]\ninclude = [\"_inbox\"]"
+    )
+    .unwrap();
+
+    let output = cmd.arg("domains").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let domains: Vec<String> = stdout
+        .lines()
+        .skip(1)
+        .map(|l| l.split_whitespace().take(2).collect::<Vec<_>>().join(" "))
+        .collect();
+    assert_eq!(
+        domains,
+        ["_inbox 1", "elasticsearch 3", "lucene 5", "rust 1"]
+    );
+}
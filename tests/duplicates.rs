@@ -0,0 +1,100 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::prelude::*;
+use predicates::str::contains;
+use serde_json::Value;
+use std::fs;
+use tempfile::TempDir;
+
+const MERGING: &str = "Segments are merged in the background by the merge policy,\nwhich picks segments of similar size so the index stays fast.";
+
+/// A lucene note whose section was copied into a new note, in another domain too.
+fn setup_copies() -> TempDir {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("lucene/merge-notes.md"),
+        format!("# Merge Notes\n\n## Background\n\n{}\n\n## Tuning\n\nTiered merge policy knobs: segmentsPerTier, maxMergedSegmentMB.\n", MERGING),
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("lucene/merge-copy.md"),
+        format!(
+            "---\ntags: [draft]\n---\n# Merge Copy\n\n## How merging works\n\n{}\n",
+            MERGING
+        ),
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("elasticsearch/merges.md"),
+        format!("# Merges\n\n{}\n", MERGING),
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn duplicates_reports_sections_with_line_ranges() {
+    let tmp = setup_copies();
+
+    kbase(&tmp)
+        .args(["duplicates", "--domain", "lucene"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "  0.53  lucene/merge-copy.md (overlap: 8-9)\n        lucene/merge-notes.md (overlap: 5-6)\n",
+        ))
+        .stdout(contains("Similar sections (>= 0.50):\n"))
+        .stdout(contains(
+            "  0.81  lucene/merge-copy.md:6-9 (How merging works)\n        lucene/merge-notes.md:3-6 (Background)\n",
+        ))
+        .stdout(contains("elasticsearch").not());
+}
+
+#[test]
+fn duplicates_json_includes_note_overlap() {
+    let tmp = setup_copies();
+    let output = kbase(&tmp)
+        .args(["duplicates", "--threshold", "0.3", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["threshold"], 0.3);
+
+    let notes = json["notes"].as_array().unwrap();
+    let pair = notes
+        .iter()
+        .find(|p| {
+            p["a"]["path"] == "elasticsearch/merges.md" && p["b"]["path"] == "lucene/merge-copy.md"
+        })
+        .expect("copied note pair");
+    assert_eq!(pair["a"]["overlap"], serde_json::json!([[3, 4]]));
+    assert_eq!(pair["b"]["overlap"], serde_json::json!([[8, 9]]));
+
+    // Frontmatter is not part of the compared body
+    assert_eq!(pair["b"]["start_line"], 4);
+}
+
+#[test]
+fn duplicates_none_found() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .arg("duplicates")
+        .assert()
+        .success()
+        .stdout("No duplicates found (threshold 0.50).\n");
+}
+
+#[test]
+fn duplicates_rejects_bad_threshold() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["duplicates", "--threshold", "1.5"])
+        .assert()
+        .failure()
+        .stderr(contains("--threshold must be greater than 0 and at most 1"));
+}
@@ -26,7 +26,8 @@ fn test_index_command_builds_tag_index() {
 
     // Verify tags.json contents
     let tags_content = fs::read_to_string(&tags_json_path).unwrap();
-    let tags: serde_json::Value = serde_json::from_str(&tags_content).unwrap();
+    let index: serde_json::Value = serde_json::from_str(&tags_content).unwrap();
+    let tags = &index["tags"];
 
     // Check expected tags from our fixture files
     assert!(tags.get("deep-dive").is_some());
@@ -65,7 +66,8 @@ fn test_index_command_skips_code_block_tags() {
     // Check tags.json in centralized location
     let tags_json_path = vault.path().join(".kbase/test-vault/tags.json");
     let tags_content = fs::read_to_string(&tags_json_path).unwrap();
-    let tags: serde_json::Value = serde_json::from_str(&tags_content).unwrap();
+    let index: serde_json::Value = serde_json::from_str(&tags_content).unwrap();
+    let tags = &index["tags"];
 
     // Should not have fake tags from code blocks
     assert!(tags.get("fake-tag").is_none());
@@ -83,3 +85,58 @@ fn test_index_command_invalid_only_option() {
         .failure()
         .stderr(predicate::str::contains("invalid value 'invalid'"));
 }
+
+#[test]
+fn test_index_command_builds_metadata_index() {
+    let vault = common::setup_vault();
+    fs::write(
+        vault.path().join("lucene/postings.md"),
+        "---\ntitle: Postings Format\nstatus: learning\nrating: 4\ntags: [lucene, formats]\naliases: [Postings List]\n---\n# Postings\n",
+    )
+    .unwrap();
+
+    let mut cmd = common::kbase(&vault);
+    cmd.arg("index");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Built metadata index: 1 notes with metadata",
+    ));
+
+    let metadata_path = vault.path().join(".kbase/test-vault/metadata.json");
+    let metadata: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&metadata_path).unwrap()).unwrap();
+    let postings = &metadata["lucene/postings.md"];
+    assert_eq!(postings["status"], "learning");
+    assert_eq!(postings["rating"], 4.0);
+    assert_eq!(postings["aliases"][0], "Postings List");
+
+    // Frontmatter tags land in the tag index
+    let tags: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(vault.path().join(".kbase/test-vault/tags.json")).unwrap(),
+    )
+    .unwrap();
+    let formats = tags["tags"]["formats"].as_array().unwrap();
+    assert_eq!(formats[0], "lucene/postings.md");
+}
+
+#[test]
+fn test_index_command_resolves_frontmatter_aliases() {
+    let vault = common::setup_vault();
+    fs::write(
+        vault.path().join("lucene/postings.md"),
+        "---\naliases: [Postings List]\n---\n# Postings\n",
+    )
+    .unwrap();
+    fs::write(
+        vault.path().join("rust/notes.md"),
+        "# Notes\n\nCompare with [[Postings List]].\n",
+    )
+    .unwrap();
+
+    common::kbase(&vault).arg("index").assert().success();
+
+    common::kbase(&vault)
+        .args(["links", "lucene/postings.md", "--backward"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rust/notes.md"));
+}
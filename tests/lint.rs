@@ -0,0 +1,185 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use tempfile::TempDir;
+
+/// Append a `[vaults.test-vault.lint]` table to the test config.
+fn configure_lint(tmp: &TempDir, rules: &str) {
+    let mut config = fs::OpenOptions::new()
+        .append(true)
+        .open(tmp.path().join(".kbase/config.toml"))
+        .unwrap();
+    write!(config, "\n[vaults.test-vault.lint]\n{}", rules).unwrap();
+}
+
+#[test]
+fn lint_reports_warnings_without_failing() {
+    let tmp = setup_vault();
+
+    kbase(&tmp).arg("lint").assert().success().stdout(
+        "lucene/codecs.md:1:1: warning[missing-title] note has no title or heading\n\
+             \n0 errors, 1 warning (1 fixable with --fix)\n",
+    );
+
+    kbase(&tmp).args(["lint", "--strict"]).assert().failure();
+}
+
+#[test]
+fn lint_broken_link_is_an_error() {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("rust/Ownership Notes.md"),
+        "# Ownership\n\n### Borrowing\n\nSee [[nowhere]].\n",
+    )
+    .unwrap();
+
+    kbase(&tmp)
+        .arg("lint")
+        .assert()
+        .failure()
+        .stdout(contains(
+            "rust/Ownership Notes.md:1:1: warning[kebab-case] file name 'Ownership Notes.md' is not kebab-case\n\
+             rust/Ownership Notes.md:3:1: warning[heading-jump] heading level jumps from H1 to H3\n\
+             rust/Ownership Notes.md:5:5: error[broken-link] broken link [[nowhere]]\n",
+        ))
+        .stdout(contains("1 error, 3 warnings"));
+}
+
+#[test]
+fn lint_rule_filter_and_json() {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("rust/empty.md"),
+        "---\ntags: [draft]\n---\n\n",
+    )
+    .unwrap();
+
+    let output = kbase(&tmp)
+        .args(["lint", "--rule", "empty-note", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["errors"], 0);
+    assert_eq!(json["warnings"], 1);
+    let diagnostic = &json["diagnostics"][0];
+    assert_eq!(diagnostic["rule"], "empty-note");
+    assert_eq!(diagnostic["severity"], "warning");
+    assert_eq!(diagnostic["path"], "rust/empty.md");
+
+    kbase(&tmp)
+        .args(["lint", "--rule", "no-such-rule"])
+        .assert()
+        .failure()
+        .stderr(contains("Unknown lint rule 'no-such-rule'"));
+}
+
+#[test]
+fn lint_severities_come_from_config() {
+    let tmp = setup_vault();
+
+    // kbase() rewrites config.toml, so the lint table goes in afterwards
+    let mut cmd = kbase(&tmp);
+    configure_lint(&tmp, "missing-title = \"off\"\n");
+    cmd.arg("lint")
+        .assert()
+        .success()
+        .stdout("No problems found.\n");
+
+    let mut cmd = kbase(&tmp);
+    configure_lint(&tmp, "missing-title = \"error\"\n");
+    cmd.args(["lint", "--list-rules"])
+        .assert()
+        .success()
+        .stdout(contains("missing-title    error"));
+
+    let mut cmd = kbase(&tmp);
+    configure_lint(&tmp, "missing-title = \"error\"\n");
+    cmd.arg("lint")
+        .assert()
+        .failure()
+        .stdout(contains("error[missing-title]"));
+
+    let mut cmd = kbase(&tmp);
+    configure_lint(&tmp, "no-such-rule = \"error\"\n");
+    cmd.arg("lint")
+        .assert()
+        .failure()
+        .stderr(contains("Unknown lint rule 'no-such-rule' in config"));
+}
+
+#[test]
+fn lint_fix_dry_run_prints_diff_only() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["lint", "--fix", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("+++ b/lucene/codecs.md"))
+        .stdout(contains("+# Codecs\n+\n No heading here"));
+
+    let content = fs::read_to_string(tmp.path().join("lucene/codecs.md")).unwrap();
+    assert!(content.starts_with("No heading here"));
+}
+
+#[test]
+fn lint_fix_applies_safe_fixes() {
+    let tmp = setup_vault();
+    let note = tmp.path().join("rust/ownership.md");
+    fs::write(
+        &note,
+        "---\ntags: [Rust]\n---\n# Ownership\n\n### Borrowing\n\n#### Detail\n\n\
+         See [[serch-flow]] and [[nowhere]]. #Rust\n\n```\n#Rust\n```\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("rust/borrowing.md"),
+        "# Borrowing\n\n#rust\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("rust/traits.md"), "# Traits\n\n#rust\n").unwrap();
+
+    kbase(&tmp)
+        .args(["lint", "--fix"])
+        .assert()
+        .failure()
+        .stdout(contains("Fixed 4 problems in 2 notes\n"))
+        .stdout(contains(
+            "rust/ownership.md:10:24: error[broken-link] broken link [[nowhere]]\n\
+             \n1 error, 0 warnings\n",
+        ));
+
+    assert_eq!(
+        fs::read_to_string(&note).unwrap(),
+        "---\ntags: [rust]\n---\n# Ownership\n\n## Borrowing\n\n### Detail\n\n\
+         See [[lucene/search-flow]] and [[nowhere]]. #rust\n\n```\n#Rust\n```\n"
+    );
+    let codecs = fs::read_to_string(tmp.path().join("lucene/codecs.md")).unwrap();
+    assert!(codecs.starts_with("# Codecs\n\n"));
+}
+
+#[test]
+fn lint_fix_quoted_frontmatter_tag() {
+    let tmp = setup_vault();
+    let note = tmp.path().join("rust/ownership.md");
+    fs::write(&note, "---\ntags: [wip, \"#Rust\"]\n---\n# Ownership\n").unwrap();
+    fs::write(
+        tmp.path().join("rust/borrowing.md"),
+        "# Borrowing\n\n#rust\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("rust/traits.md"), "# Traits\n\n#rust\n").unwrap();
+
+    kbase(&tmp)
+        .args(["lint", "--fix", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("+tags: [wip, \"#rust\"]"))
+        .stdout(contains("+++ b/lucene/codecs.md"));
+}
@@ -0,0 +1,162 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use std::fs;
+
+#[test]
+fn mv_requires_link_index() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/codecs.md", "lucene/codec-internals.md"])
+        .assert()
+        .failure()
+        .stderr(contains("Link index not found"));
+}
+
+#[test]
+fn mv_renames_note_and_rewrites_backlinks() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/search-flow.md", "lucene/query-flow"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Moved lucene/search-flow.md → lucene/query-flow.md",
+        ))
+        .stdout(contains("Updated 1 links in 1 notes"));
+
+    assert!(!tmp.path().join("lucene/search-flow.md").exists());
+    assert!(tmp.path().join("lucene/query-flow.md").exists());
+
+    let codecs = fs::read_to_string(tmp.path().join("lucene/codecs.md")).unwrap();
+    assert!(codecs.contains("See also: [[query-flow]]"));
+}
+
+#[test]
+fn mv_across_domains_rewrites_links_both_ways() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/codecs.md", "rust/"])
+        .assert()
+        .success();
+
+    // Incoming link from the old domain now needs the full path
+    let search_flow = fs::read_to_string(tmp.path().join("lucene/search-flow.md")).unwrap();
+    assert!(search_flow.contains("Related: [[rust/codecs]] and [[indexing/inverted-index]]"));
+
+    // The moved note's own bare link no longer resolves from rust/
+    let codecs = fs::read_to_string(tmp.path().join("rust/codecs.md")).unwrap();
+    assert!(codecs.contains("See also: [[lucene/search-flow]]"));
+}
+
+#[test]
+fn mv_preserves_alias_and_section() {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("lucene/postings.md"),
+        "# Postings\n\nSee [[codecs#Block Tree|the codec notes]] and [[codecs|codecs]].\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/codecs.md", "lucene/formats/codecs.md"])
+        .assert()
+        .success();
+
+    let postings = fs::read_to_string(tmp.path().join("lucene/postings.md")).unwrap();
+    assert_eq!(
+        postings,
+        "# Postings\n\nSee [[formats/codecs#Block Tree|the codec notes]] and [[formats/codecs|codecs]].\n"
+    );
+}
+
+#[test]
+fn mv_dry_run_prints_diff_without_changes() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/codecs.md", "rust/codecs.md", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("rename from lucene/codecs.md"))
+        .stdout(contains("rename to rust/codecs.md"))
+        .stdout(contains(
+            "-Related: [[codecs]] and [[indexing/inverted-index]]",
+        ))
+        .stdout(contains(
+            "+Related: [[rust/codecs]] and [[indexing/inverted-index]]",
+        ));
+
+    assert!(tmp.path().join("lucene/codecs.md").exists());
+    assert!(!tmp.path().join("rust/codecs.md").exists());
+}
+
+#[test]
+fn mv_refuses_to_overwrite() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/codecs.md", "lucene/search-flow.md"])
+        .assert()
+        .failure()
+        .stderr(contains("destination already exists"));
+}
+
+#[test]
+fn mv_updates_link_index() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/codecs.md", "rust/codecs.md"])
+        .assert()
+        .success();
+
+    kbase(&tmp)
+        .args(["links", "rust/codecs.md", "--backward"])
+        .assert()
+        .success()
+        .stdout(contains("lucene/search-flow.md"));
+}
+
+#[test]
+fn mv_accepts_short_note_name() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["mv", "codecs", "rust/"])
+        .assert()
+        .success();
+
+    assert!(!tmp.path().join("lucene/codecs.md").exists());
+    assert!(tmp.path().join("rust/codecs.md").exists());
+}
+
+#[test]
+fn mv_rebuilds_unreadable_index() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+    let search_index = tmp.path().join(".kbase/test-vault/search.json");
+    fs::write(&search_index, "{\"stale\": true}").unwrap();
+
+    kbase(&tmp)
+        .args(["mv", "lucene/codecs.md", "rust/"])
+        .assert()
+        .success();
+
+    kbase(&tmp)
+        .args(["search", "codecs", "--semantic"])
+        .assert()
+        .success()
+        .stdout(contains("rust/codecs.md"));
+}
@@ -194,3 +194,176 @@ fn notes_tag_and_domain_no_results() {
             "No notes in domain 'elasticsearch' with tag 'rust'",
         ));
 }
+
+#[test]
+fn notes_title_from_frontmatter() {
+    let tmp = setup_vault();
+    std::fs::write(
+        tmp.path().join("lucene/postings.md"),
+        "---\ntitle: Postings Format\n---\n# Postings\n",
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join("lucene/doc-values.md"),
+        "---\nstatus: draft\n---\n\n# Doc Values\n",
+    )
+    .unwrap();
+
+    kbase(&tmp)
+        .args(["notes", "--domain", "lucene"])
+        .assert()
+        .success()
+        .stdout(contains("Postings Format"))
+        .stdout(contains("Doc Values"));
+}
+
+#[test]
+fn notes_where_filters_on_inline_and_frontmatter_fields() {
+    let tmp = setup_vault();
+    std::fs::write(
+        tmp.path().join("lucene/postings.md"),
+        "# Postings\n\n**Status:** Learning - Phase 2B\n",
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join("lucene/doc-values.md"),
+        "---\nstatus: done\n---\n# Doc Values\n",
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join("lucene/norms.md"),
+        "# Norms\n\nstatus:: learning\nowner:: me\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    let output = kbase(&tmp)
+        .args(["notes", "--where", "status=Learning", "--files"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("lucene/postings.md"));
+    assert!(stdout.contains("lucene/norms.md"));
+    assert!(!stdout.contains("lucene/doc-values.md"));
+
+    let output = kbase(&tmp)
+        .args([
+            "notes",
+            "--where",
+            "status=learning",
+            "--where",
+            "owner!=me",
+            "--files",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "lucene/postings.md");
+}
+
+#[test]
+fn notes_where_requires_index() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["notes", "--where", "status=done"])
+        .assert()
+        .failure()
+        .stderr(contains("No metadata index found"));
+}
+
+#[test]
+fn notes_tag_filter_includes_nested_tags() {
+    let tmp = setup_vault();
+    std::fs::write(
+        tmp.path().join("rust/tokio.md"),
+        "# Tokio\n\n#lang/rust/async\n",
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join("rust/traits.md"),
+        "# Traits\n\n#lang/rust\n",
+    )
+    .unwrap();
+    std::fs::write(tmp.path().join("rust/speech.md"), "# Speech\n\n#language\n").unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["notes", "--tag", "lang", "--files"])
+        .assert()
+        .success()
+        .stdout("rust/tokio.md\nrust/traits.md\n");
+
+    kbase(&tmp)
+        .args(["notes", "--tag", "lang/rust/async", "--files"])
+        .assert()
+        .success()
+        .stdout("rust/tokio.md\n");
+}
+
+#[test]
+fn notes_multiple_domains_positional_and_flag() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args([
+            "notes",
+            "rust",
+            "elasticsearch",
+            "--domain",
+            "rust",
+            "--files",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            "rust/01-home.md\n\
+             elasticsearch/01-home.md\n\
+             elasticsearch/esql-analysis.md\n\
+             elasticsearch/esql/functions.md\n",
+        );
+}
+
+#[test]
+fn notes_boolean_tag_filters() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args([
+            "notes",
+            "--tag",
+            "performance",
+            "--not-tag",
+            "rust",
+            "--files",
+        ])
+        .assert()
+        .success()
+        .stdout("lucene/01-home.md\nelasticsearch/esql-analysis.md\nlucene/search-flow.md\n");
+
+    kbase(&tmp)
+        .args(["notes", "--any-tag", "rust,lucene", "--files"])
+        .assert()
+        .success()
+        .stdout("lucene/01-home.md\nrust/01-home.md\nlucene/search-flow.md\n");
+
+    kbase(&tmp)
+        .args([
+            "notes",
+            "--all-tags",
+            "performance",
+            "--tag",
+            "lucene",
+            "--files",
+        ])
+        .assert()
+        .success()
+        .stdout("lucene/01-home.md\nlucene/search-flow.md\n");
+
+    kbase(&tmp)
+        .args(["notes", "--not-tag", "performance", "rust"])
+        .assert()
+        .success()
+        .stdout("No notes in domain 'rust' with the given tags.\n");
+}
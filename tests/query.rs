@@ -0,0 +1,105 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use std::fs;
+use tempfile::TempDir;
+
+/// Add notes with status metadata, then build the index.
+fn setup_query_vault() -> TempDir {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("lucene/postings.md"),
+        "# Postings\n\n**Status:** Learning\n**Updated:** 2026-10-12\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("lucene/doc-values.md"),
+        "---\nstatus: done\nupdated: 2026-10-01\n---\n# Doc Values\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("lucene/norms.md"),
+        "# Norms\n\nstatus:: draft\nupdated:: 2026-09-20\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+#[test]
+fn query_table_from_where_sort() {
+    let tmp = setup_query_vault();
+
+    let output = kbase(&tmp)
+        .args([
+            "query",
+            r#"TABLE status, updated FROM domain:lucene WHERE status != "done" SORT updated DESC"#,
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("File"));
+    assert!(lines[0].contains("status"));
+    assert!(lines[1].starts_with("lucene/postings.md"));
+    assert!(lines[1].contains("Learning"));
+    assert!(lines[2].starts_with("lucene/norms.md"));
+    assert!(!stdout.contains("doc-values.md"));
+}
+
+#[test]
+fn query_json_output() {
+    let tmp = setup_query_vault();
+
+    let output = kbase(&tmp)
+        .args([
+            "query",
+            r#"TABLE status FROM "lucene" WHERE status = "done""#,
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total"], 1);
+    assert_eq!(json["rows"][0]["file"], "lucene/doc-values.md");
+    assert_eq!(json["rows"][0]["fields"]["status"], "done");
+}
+
+#[test]
+fn query_tag_source() {
+    let tmp = setup_query_vault();
+
+    kbase(&tmp)
+        .args(["query", "TABLE WITHOUT ID file.name FROM #deep-dive"])
+        .assert()
+        .success()
+        .stdout(contains("search-flow"))
+        .stdout(contains("esql-analysis"));
+}
+
+#[test]
+fn query_requires_index() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["query", "TABLE status"])
+        .assert()
+        .failure()
+        .stderr(contains("Run `kbase index`"));
+}
+
+#[test]
+fn query_syntax_error() {
+    let tmp = setup_query_vault();
+
+    kbase(&tmp)
+        .args(["query", "TABLE status WHERE"])
+        .assert()
+        .failure()
+        .stderr(contains("Expected a value"));
+}
@@ -210,3 +210,326 @@ fn read_wrong_domain_errors() {
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(stderr.contains("note not found: no-such-domain/note.md"));
 }
+
+#[test]
+fn read_resolves_names_without_extension() {
+    let tmp = setup_vault();
+
+    for name in ["lucene/search-flow", "search-flow", "Search-Flow.md"] {
+        kbase(&tmp)
+            .args(["read", name, "-n"])
+            .assert()
+            .success()
+            .stdout(EXPECTED_READ_WITH_LINE_NUMBERS);
+    }
+}
+
+#[test]
+fn read_resolves_unique_fragment() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp).args(["read", "inverted"]).output().unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("# Inverted Index\n"));
+}
+
+#[test]
+fn read_ambiguous_name_lists_candidates() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp).args(["read", "home"]).output().unwrap();
+
+    assert!(!output.status.success(), "expected non-zero exit");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("note 'home' is ambiguous"));
+    assert!(stderr.contains("  elasticsearch/01-home.md\n"));
+    assert!(stderr.contains("  lucene/01-home.md\n"));
+    assert!(stderr.contains("  rust/01-home.md"));
+}
+
+// ---------------------------------------------------------------------------
+// kb read --section
+// ---------------------------------------------------------------------------
+
+#[test]
+fn read_section_includes_subsections() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp)
+        .args(["read", "lucene/search-flow.md", "--section", "Phase 1"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "expected exit 0");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("## Phase 1: IndexSearcher.search()\n"));
+    assert!(stdout.contains("### Step 2: BulkScorer"));
+    assert!(stdout.ends_with("Scores documents in bulk for a segment.\n"));
+    assert!(!stdout.contains("Phase 2"));
+}
+
+#[test]
+fn read_nested_section_keeps_line_numbers() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp)
+        .args([
+            "read",
+            "lucene/search-flow.md",
+            "--section",
+            "search flow/bulkscorer",
+            "-n",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "expected exit 0");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "    17\t### Step 2: BulkScorer\n    18\t\n    19\tScores documents in bulk for a segment.\n"
+    );
+}
+
+#[test]
+fn read_section_outline() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp)
+        .args([
+            "read",
+            "lucene/search-flow.md",
+            "--section",
+            "Phase 1",
+            "--outline",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "  ## Phase 1: IndexSearcher.search()\n    ### Step 1: createWeight()\n    ### Step 2: BulkScorer\n"
+    );
+}
+
+#[test]
+fn read_section_not_found() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp)
+        .args(["read", "lucene/search-flow.md", "--section", "Phase 9"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success(), "expected non-zero exit");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("section not found in lucene/search-flow.md: Phase 9"));
+}
+
+#[test]
+fn read_section_ambiguous_lists_candidates() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp)
+        .args(["read", "lucene/search-flow.md", "--section", "Step"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success(), "expected non-zero exit");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("is ambiguous"));
+    assert!(stderr.contains("e.g. \"Parent/Step\""));
+    assert!(stderr.contains("### Step 1: createWeight() (line 13)"));
+    assert!(stderr.contains("### Step 2: BulkScorer (line 17)"));
+}
+
+#[test]
+fn read_section_with_slash_in_heading() {
+    let tmp = setup_vault();
+    std::fs::write(
+        tmp.path().join("lucene/io.md"),
+        "# IO\n\n## Read/Write path\n\nBuffered.\n\n## Merges\n",
+    )
+    .unwrap();
+
+    let output = kbase(&tmp)
+        .args(["read", "lucene/io.md", "--section", "io#Read/Write path"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "expected exit 0");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout, "## Read/Write path\n\nBuffered.\n");
+
+    // Without `#`, a `/` path that matches no nesting is tried as one heading
+    let output = kbase(&tmp)
+        .args(["read", "lucene/io.md", "--section", "Read/Write path"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "## Read/Write path\n\nBuffered.\n"
+    );
+}
+
+#[test]
+fn read_section_nested_with_hash() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp)
+        .args([
+            "read",
+            "lucene/search-flow.md",
+            "--section",
+            "Search Flow#BulkScorer",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "expected exit 0");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("### Step 2: BulkScorer\n"));
+}
+
+// ---------------------------------------------------------------------------
+// kb read --expand-links
+// ---------------------------------------------------------------------------
+
+fn setup_linked_notes() -> tempfile::TempDir {
+    let tmp = setup_vault();
+    let dir = tmp.path().join("lucene");
+    std::fs::write(
+        dir.join("expand-a.md"),
+        "# A\n\nSee [[expand-b]], [[expand-c#Details]] and [[expand-b]] again.\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("expand-b.md"),
+        "# B\n\nBack to [[expand-a]], on to [[expand-c]].\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("expand-c.md"),
+        "# C\n\nIntro.\n\n## Details\n\nThe details.\n\n## Other\n\nNot linked.\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+#[test]
+fn read_expand_links_inlines_notes_and_sections() {
+    let tmp = setup_linked_notes();
+    let output = kbase(&tmp)
+        .args(["read", "lucene/expand-a.md", "--expand-links"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "# A
+
+See [[expand-b]], [[expand-c#Details]] and [[expand-b]] again.
+
+<!-- kbase: skipped lucene/expand-b.md (already included, linked from lucene/expand-a.md) -->
+
+<!-- kbase: lucene/expand-b.md (depth 1, linked from lucene/expand-a.md) -->
+# B
+
+Back to [[expand-a]], on to [[expand-c]].
+<!-- kbase: end lucene/expand-b.md -->
+
+<!-- kbase: lucene/expand-c.md#Details (depth 1, linked from lucene/expand-a.md) -->
+## Details
+
+The details.
+<!-- kbase: end lucene/expand-c.md#Details -->
+"
+    );
+}
+
+#[test]
+fn read_expand_links_depth_follows_links_and_detects_cycles() {
+    let tmp = setup_linked_notes();
+    let output = kbase(&tmp)
+        .args([
+            "read",
+            "lucene/expand-a.md",
+            "--expand-links",
+            "--depth",
+            "2",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(
+        "<!-- kbase: skipped lucene/expand-a.md (cycle, linked from lucene/expand-b.md) -->"
+    ));
+    assert!(
+        stdout.contains(
+            "<!-- kbase: lucene/expand-c.md (depth 2, linked from lucene/expand-b.md) -->"
+        )
+    );
+    assert!(stdout.contains("Not linked."));
+}
+
+#[test]
+fn read_expand_links_keeps_original_line_numbers() {
+    let tmp = setup_linked_notes();
+    let output = kbase(&tmp)
+        .args(["read", "lucene/expand-a.md", "--expand-links", "-n"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("     5\t## Details\n     6\t\n     7\tThe details.\n"));
+}
+
+#[test]
+fn read_expand_links_follows_aliases_and_reports_ambiguous_sections() {
+    let tmp = setup_vault();
+    let dir = tmp.path().join("lucene");
+    std::fs::write(
+        dir.join("expand-d.md"),
+        "---\naliases: [Dee]\n---\n# Part 1\n\n## Setup\n\nFirst.\n\n# Part 2\n\n## Setup\n\nSecond.\n",
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join("rust/expand-e.md"),
+        "# E\n\n[[Dee#Setup]] and [[Dee#Part 2#Setup]]\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    let output = kbase(&tmp)
+        .args(["read", "rust/expand-e.md", "--expand-links"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(
+        "<!-- kbase: skipped lucene/expand-d.md#Setup (section is ambiguous, linked from rust/expand-e.md) -->"
+    ));
+    assert!(stdout.contains("<!-- kbase: lucene/expand-d.md#Part 2#Setup (depth 1"));
+    assert!(stdout.contains("Second."));
+    assert!(!stdout.contains("First."));
+}
+
+#[test]
+fn read_expand_links_requires_link_index() {
+    let tmp = setup_vault();
+    let output = kbase(&tmp)
+        .args(["read", "lucene/search-flow.md", "--expand-links"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success(), "expected non-zero exit");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Link index not found"));
+}
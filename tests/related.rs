@@ -0,0 +1,103 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use serde_json::Value;
+use std::fs;
+use tempfile::TempDir;
+
+/// Two notes sharing tags, both linked from a hub note.
+fn setup_related() -> TempDir {
+    let tmp = setup_vault();
+    let dir = tmp.path().join("rust");
+    fs::write(
+        dir.join("ownership.md"),
+        "# Ownership\n\n#borrowing #memory\n\nMoves and borrows. See [[lifetimes]].\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("lifetimes.md"),
+        "# Lifetimes\n\n#borrowing #memory\n\nBorrows must not outlive their owner.\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("reading-list.md"),
+        "# Reading List\n\n- [[ownership]]\n- [[lifetimes]]\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+#[test]
+fn related_explains_each_signal() {
+    let tmp = setup_related();
+
+    kbase(&tmp)
+        .args(["related", "ownership"])
+        .assert()
+        .success()
+        .stdout(contains("Related to rust/ownership.md\n"))
+        .stdout(contains("rust/lifetimes.md  Lifetimes  (already linked)\n"))
+        .stdout(contains("      shared tags: #borrowing, #memory\n"))
+        .stdout(contains("      co-cited by: rust/reading-list.md\n"))
+        .stdout(contains("      similar text ("));
+}
+
+#[test]
+fn related_json_ranks_by_score() {
+    let tmp = setup_related();
+    let output = kbase(&tmp)
+        .args(["related", "rust/ownership.md", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["note"], "rust/ownership.md");
+
+    let related = json["related"].as_array().unwrap();
+    let top = &related[0];
+    assert_eq!(top["path"], "rust/lifetimes.md");
+    assert_eq!(top["linked"], true);
+    assert_eq!(top["signals"]["tags"], 1.0);
+    assert_eq!(top["signals"]["co_citation"], 1.0);
+    assert_eq!(top["co_cited_by"][0], "rust/reading-list.md");
+
+    let scores: Vec<f64> = related
+        .iter()
+        .map(|r| r["score"].as_f64().unwrap())
+        .collect();
+    assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+}
+
+#[test]
+fn related_unlinked_hides_linked_notes() {
+    let tmp = setup_related();
+    let output = kbase(&tmp)
+        .args(["related", "ownership", "--unlinked", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let paths: Vec<&str> = json["related"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["path"].as_str().unwrap())
+        .collect();
+    assert!(!paths.contains(&"rust/lifetimes.md"));
+    assert!(!paths.contains(&"rust/reading-list.md"));
+}
+
+#[test]
+fn related_requires_index() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["related", "lucene/search-flow.md"])
+        .assert()
+        .failure()
+        .stderr(contains("No tag or link index found"));
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use std::fs;
+use tempfile::TempDir;
+
+/// Add notes with section links to lucene/search-flow.md, then build the index.
+fn setup_section_links() -> TempDir {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("lucene/scoring.md"),
+        "# Scoring\n\n\
+         See [[search-flow#Phase 2: Scoring]] and [[search-flow#phase 2: scoring|BM25]].\n\
+         Unrelated: [[search-flow#Phase 1: IndexSearcher.search()]]\n",
+    )
+    .unwrap();
+
+    let search_flow = tmp.path().join("lucene/search-flow.md");
+    let mut content = fs::read_to_string(&search_flow).unwrap();
+    content.push_str("\nBack to [[#Phase 2: Scoring]].\n");
+    fs::write(&search_flow, content).unwrap();
+
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+#[test]
+fn rename_heading_updates_heading_and_section_links() {
+    let tmp = setup_section_links();
+
+    kbase(&tmp)
+        .args([
+            "rename-heading",
+            "lucene/search-flow.md",
+            "Phase 2: Scoring",
+            "Phase 2: Ranking",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Updated 3 links in 1 notes"));
+
+    let search_flow = fs::read_to_string(tmp.path().join("lucene/search-flow.md")).unwrap();
+    assert!(search_flow.contains("\n## Phase 2: Ranking\n"));
+    assert!(search_flow.contains("Back to [[#Phase 2: Ranking]]."));
+
+    let scoring = fs::read_to_string(tmp.path().join("lucene/scoring.md")).unwrap();
+    assert!(scoring.contains(
+        "See [[search-flow#Phase 2: Ranking]] and [[search-flow#Phase 2: Ranking|BM25]]."
+    ));
+    assert!(scoring.contains("Unrelated: [[search-flow#Phase 1: IndexSearcher.search()]]"));
+}
+
+#[test]
+fn rename_heading_dry_run_leaves_files_untouched() {
+    let tmp = setup_section_links();
+    let before = fs::read_to_string(tmp.path().join("lucene/scoring.md")).unwrap();
+
+    kbase(&tmp)
+        .args([
+            "rename-heading",
+            "lucene/search-flow.md",
+            "Phase 2: Scoring",
+            "Ranking",
+            "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("-## Phase 2: Scoring"))
+        .stdout(contains("+## Ranking"))
+        .stdout(contains("+++ b/lucene/scoring.md"));
+
+    let after = fs::read_to_string(tmp.path().join("lucene/scoring.md")).unwrap();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn rename_heading_unknown_heading() {
+    let tmp = setup_section_links();
+
+    kbase(&tmp)
+        .args([
+            "rename-heading",
+            "lucene/search-flow.md",
+            "Phase 3",
+            "Other",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "heading not found in lucene/search-flow.md: Phase 3",
+        ));
+}
+
+#[test]
+fn rename_heading_refuses_existing_heading() {
+    let tmp = setup_section_links();
+
+    kbase(&tmp)
+        .args([
+            "rename-heading",
+            "lucene/search-flow.md",
+            "Phase 2: Scoring",
+            "Step 2: BulkScorer",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("already exists"));
+}
+
+#[test]
+fn rename_heading_to_same_text_is_refused() {
+    let tmp = setup_section_links();
+
+    kbase(&tmp)
+        .args([
+            "rename-heading",
+            "lucene/search-flow.md",
+            "phase 2: scoring",
+            "Phase 2: Scoring",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("already named 'Phase 2: Scoring'"));
+
+    kbase(&tmp)
+        .args([
+            "rename-heading",
+            "lucene/search-flow.md",
+            "Phase 2: Scoring",
+            "Phase 2: scoring",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Updated 3 links in 1 notes"));
+
+    let search_flow = fs::read_to_string(tmp.path().join("lucene/search-flow.md")).unwrap();
+    assert!(search_flow.contains("\n## Phase 2: scoring\n"));
+}
+
+#[test]
+fn rename_heading_matches_case_and_follows_aliases() {
+    let tmp = setup_vault();
+    let search_flow = tmp.path().join("lucene/search-flow.md");
+    let content = fs::read_to_string(&search_flow).unwrap();
+    fs::write(
+        &search_flow,
+        format!("---\naliases: [Flow]\n---\n{}", content),
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("rust/notes.md"),
+        "# Notes\n\n[[Flow#Phase 2: Scoring]] and [[lucene/search-flow#Phase 2: Scoring#Phase 2: Scoring]]\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args([
+            "rename-heading",
+            "search-flow",
+            "phase 2: scoring",
+            "Ranking",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Updated 2 links in 1 notes"));
+
+    let search_flow = fs::read_to_string(&search_flow).unwrap();
+    assert!(search_flow.contains("\n## Ranking\n"));
+    let notes = fs::read_to_string(tmp.path().join("rust/notes.md")).unwrap();
+    assert!(notes.contains("[[Flow#Ranking]]"));
+    assert!(notes.contains("[[lucene/search-flow#Phase 2: Scoring#Ranking]]"));
+}
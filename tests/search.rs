@@ -0,0 +1,151 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use serde_json::Value;
+use std::fs;
+use tempfile::TempDir;
+
+fn search_paths(tmp: &TempDir, args: &[&str]) -> Vec<String> {
+    let output = kbase(tmp)
+        .arg("search")
+        .args(args)
+        .arg("--json")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["path"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn search_ranks_best_section_first() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["search", "bm25 scoring"])
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with(
+            "  7.08  lucene/search-flow.md:21  Phase 2: Scoring\n",
+        ));
+}
+
+#[test]
+fn search_no_match() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["search", "zebra"])
+        .assert()
+        .success()
+        .stdout("No results.\n");
+}
+
+#[test]
+fn search_semantic_matches_terms_used_in_similar_contexts() {
+    let tmp = setup_vault();
+    let dir = tmp.path().join("lucene");
+    fs::write(
+        dir.join("postings.md"),
+        "# Postings\n\nThe postings list maps each term dictionary entry to documents.\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("fst.md"),
+        "# FST\n\nThe terms index maps each term dictionary entry to a block.\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    assert!(tmp.path().join(".kbase/test-vault/search.json").exists());
+
+    // Keyword search only finds notes containing the word
+    let keyword = search_paths(&tmp, &["postings"]);
+    assert_eq!(keyword[0], "lucene/postings.md");
+    assert!(!keyword.contains(&"lucene/fst.md".to_string()));
+
+    // Semantic search also finds the note sharing its context
+    let semantic = search_paths(&tmp, &["postings", "--semantic"]);
+    assert_eq!(semantic[0], "lucene/postings.md");
+    assert!(semantic.contains(&"lucene/fst.md".to_string()));
+}
+
+#[test]
+fn search_blend_and_domain_filter() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    let paths = search_paths(
+        &tmp,
+        &[
+            "scoring",
+            "--semantic",
+            "--blend",
+            "0.5",
+            "--domain",
+            "lucene",
+        ],
+    );
+    assert_eq!(paths[0], "lucene/search-flow.md");
+    assert!(paths.iter().all(|p| p.starts_with("lucene/")));
+
+    kbase(&tmp)
+        .args(["search", "scoring", "--semantic", "--blend", "2"])
+        .assert()
+        .failure()
+        .stderr(contains("--blend must be between 0 and 1"));
+
+    // --blend only means something for semantic search
+    kbase(&tmp)
+        .args(["search", "scoring", "--blend", "0.3"])
+        .assert()
+        .failure()
+        .stderr(contains("--semantic"));
+}
+
+#[test]
+fn search_semantic_requires_index() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["search", "scoring", "--semantic"])
+        .assert()
+        .failure()
+        .stderr(contains("No search index found"));
+}
+
+#[test]
+fn search_logs_flag_includes_daily_logs() {
+    let tmp = setup_vault();
+    fs::create_dir_all(tmp.path().join("_logs")).unwrap();
+    fs::write(
+        tmp.path().join("_logs/2026-10.md"),
+        "# October\n\nRead about zebrafish genomes.\n",
+    )
+    .unwrap();
+
+    assert!(search_paths(&tmp, &["zebrafish"]).is_empty());
+    assert_eq!(
+        search_paths(&tmp, &["zebrafish", "--logs"]),
+        vec!["_logs/2026-10.md"]
+    );
+
+    // Semantic search has the logs indexed but only returns them with --logs
+    kbase(&tmp).arg("index").assert().success();
+    let log = "_logs/2026-10.md".to_string();
+    assert!(!search_paths(&tmp, &["zebrafish", "--semantic"]).contains(&log));
+    assert_eq!(
+        search_paths(&tmp, &["zebrafish", "--semantic", "--logs"]).first(),
+        Some(&log)
+    );
+}
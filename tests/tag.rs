@@ -0,0 +1,167 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::fs;
+
+const DRIFTED: &str = "---\ntags: [WIP, lang]\n---\n# Drifted\n\n#in-progress and #wip, #lang/rust\n\n`#WIP` stays\n\n```\n#WIP\n```\n";
+
+#[test]
+fn tag_merge_rewrites_body_and_frontmatter_but_not_code() {
+    let tmp = setup_vault();
+    fs::write(tmp.path().join("rust/drifted.md"), DRIFTED).unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["tag", "merge", "WIP", "in-progress", "--into", "wip"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Merged #WIP, #in-progress → #wip: 2 occurrences in 1 notes",
+        ));
+
+    let drifted = fs::read_to_string(tmp.path().join("rust/drifted.md")).unwrap();
+    assert_eq!(
+        drifted,
+        DRIFTED
+            .replacen("[WIP,", "[wip,", 1)
+            .replacen("#in-progress", "#wip", 1)
+    );
+
+    // The tag index was refreshed
+    let output = kbase(&tmp)
+        .args(["notes", "--tag", "in-progress"])
+        .output()
+        .unwrap();
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .contains("No notes")
+    );
+}
+
+#[test]
+fn tag_rename_rewrites_nested_tags() {
+    let tmp = setup_vault();
+    fs::write(tmp.path().join("rust/drifted.md"), DRIFTED).unwrap();
+
+    kbase(&tmp)
+        .args(["tag", "rename", "lang", "language"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "Renamed #lang → #language: 2 occurrences in 1 notes",
+        ));
+
+    let drifted = fs::read_to_string(tmp.path().join("rust/drifted.md")).unwrap();
+    assert!(drifted.contains("tags: [WIP, language]"));
+    assert!(drifted.contains("#language/rust"));
+}
+
+#[test]
+fn tag_rename_quoted_frontmatter_tag() {
+    let tmp = setup_vault();
+    let note = tmp.path().join("rust/drafts.md");
+    fs::write(
+        &note,
+        "---\ntags: [wip, \"#draft\"]\n---\n# Drafts\n\n#draft\n",
+    )
+    .unwrap();
+
+    kbase(&tmp)
+        .args(["tag", "rename", "draft", "final", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("+tags: [wip, \"#final\"]"));
+
+    kbase(&tmp)
+        .args(["tag", "rename", "draft", "final"])
+        .assert()
+        .success()
+        .stdout(contains("2 occurrences in 1 notes"));
+    assert_eq!(
+        fs::read_to_string(&note).unwrap(),
+        "---\ntags: [wip, \"#final\"]\n---\n# Drafts\n\n#final\n"
+    );
+}
+
+#[test]
+fn tag_rename_dry_run_prints_diff_without_changes() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["tag", "rename", "deep-dive", "deep", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("+++ b/lucene/search-flow.md"))
+        .stdout(contains("+This is a #deep into"));
+
+    let search_flow = fs::read_to_string(tmp.path().join("lucene/search-flow.md")).unwrap();
+    assert!(search_flow.contains("#deep-dive"));
+}
+
+#[test]
+fn tag_rename_unknown_tag_fails() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .args(["tag", "rename", "nonexistent", "other"])
+        .assert()
+        .failure()
+        .stderr(contains("No notes tagged #nonexistent"));
+}
+
+#[test]
+fn tag_lists_notes_with_nested_tags() {
+    let tmp = setup_vault();
+    fs::write(tmp.path().join("rust/drifted.md"), DRIFTED).unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["tag", "#lang"])
+        .assert()
+        .success()
+        .stdout("Notes tagged #lang (1):\n  rust/drifted.md\n");
+}
+
+#[test]
+fn tag_verbose_prints_matching_lines() {
+    let tmp = setup_vault();
+    fs::write(tmp.path().join("rust/drifted.md"), DRIFTED).unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    kbase(&tmp)
+        .args(["tag", "lang", "--verbose"])
+        .assert()
+        .success()
+        .stdout(
+            "Notes tagged #lang (1):\n\
+             \n\
+             rust/drifted.md\n  \
+             Line 2: tags: [WIP, lang]\n  \
+             Line 6: #in-progress and #wip, #lang/rust\n",
+        );
+}
+
+#[test]
+fn tag_verbose_flags_notes_edited_since_index() {
+    let tmp = setup_vault();
+    fs::write(tmp.path().join("rust/drifted.md"), DRIFTED).unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    fs::write(
+        tmp.path().join("rust/drifted.md"),
+        format!("New first line\n{}", DRIFTED),
+    )
+    .unwrap();
+
+    kbase(&tmp)
+        .args(["tag", "lang", "--verbose"])
+        .assert()
+        .success()
+        .stdout(contains(
+            "rust/drifted.md (changed since the last index; run `kbase index`)",
+        ))
+        .stdout(contains("Line").not());
+}
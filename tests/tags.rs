@@ -0,0 +1,228 @@
+mod common;
+
+use assert_cmd::Command;
+use common::{kbase, setup_vault};
+use std::fs;
+use std::io::Write;
+use tempfile::TempDir;
+
+/// Add notes with nested tags and build the index.
+fn setup_nested_tags() -> TempDir {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("rust/tokio.md"),
+        "# Tokio\n\n#lang/rust/async #lang/go\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("rust/traits.md"),
+        "# Traits\n\n#lang/rust\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+#[test]
+fn tags_tree_rolls_up_nested_counts() {
+    let tmp = setup_nested_tags();
+
+    let output = kbase(&tmp).args(["tags", "--tree"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("lang                 2\n  go                 1\n  rust               2\n    async            1\n"),
+        "{}",
+        stdout
+    );
+
+    let output = kbase(&tmp)
+        .args(["tags", "--tree", "--sort", "count"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("lang                 2\n  rust               2\n    async            1\n  go                 1\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn tags_flat_list_keeps_full_names() {
+    let tmp = setup_nested_tags();
+
+    let output = kbase(&tmp).arg("tags").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("lang/rust/async      1\n"), "{}", stdout);
+    assert!(!stdout.contains("\nlang "), "{}", stdout);
+}
+
+#[test]
+fn tags_domain_filter_counts_only_those_domains() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    let output = kbase(&tmp)
+        .args(["tags", "--domain", "rust", "--domain", "elasticsearch"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("performance          2\n"), "{}", stdout);
+    assert!(stdout.contains("wip                  1\n"), "{}", stdout);
+    assert!(!stdout.contains("lucene "), "{}", stdout);
+
+    kbase(&tmp)
+        .args(["tags", "--domain", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Domain 'nope' does not exist"));
+}
+
+#[test]
+fn tags_by_domain_matrix() {
+    let tmp = setup_vault();
+    kbase(&tmp).arg("index").assert().success();
+
+    let output = kbase(&tmp).args(["tags", "--by-domain"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("Tag                  elasticsearch  lucene  rust\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("performance          1              2       1\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("wip                  1              1       -\n"),
+        "{}",
+        stdout
+    );
+
+    let output = kbase(&tmp)
+        .args([
+            "tags",
+            "--by-domain",
+            "--domain",
+            "rust",
+            "--domain",
+            "lucene",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("Tag                  rust  lucene\n"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("performance          1     2\n"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn tags_cooccur_lists_tags_on_the_same_notes() {
+    let tmp = setup_nested_tags();
+    fs::write(
+        tmp.path().join("rust/channels.md"),
+        "# Channels\n\n#lang/rust/async #performance\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+
+    let output = kbase(&tmp)
+        .args(["tags", "--cooccur", "#lang/rust/async"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("Tag"), "got:\n{}", stdout);
+    assert!(lines[1].starts_with("lang/go "), "got:\n{}", stdout);
+    assert!(lines[2].starts_with("performance "), "got:\n{}", stdout);
+    assert_eq!(lines.len(), 3);
+
+    kbase(&tmp)
+        .args(["tags", "--cooccur", "missing"])
+        .assert()
+        .success()
+        .stdout("No notes with tag 'missing'.\n");
+}
+
+#[test]
+fn tags_cooccur_matrix_exports_csv() {
+    let tmp = setup_nested_tags();
+
+    let output = kbase(&tmp)
+        .args(["tags", "--cooccur-matrix", "--domain", "rust"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    // 3 tagged notes in rust/, so a pair on one note has lift 3
+    assert!(stdout.starts_with("tag,other,notes,lift,pmi\n"));
+    assert!(stdout.contains("\nlang/go,lang/rust/async,1,3.0000,1.5850\n"));
+    assert!(stdout.contains("\nperformance,rust,1,3.0000,1.5850\n"));
+    assert!(!stdout.contains("lucene"));
+}
+
+/// `kbase` with a `[vaults.test-vault.tags]` table that folds case and
+/// punctuation and aliases `in-progress` to `wip`. kbase() rewrites
+/// config.toml, so the table goes in after it.
+fn kbase_normalized(tmp: &TempDir) -> Command {
+    let cmd = kbase(tmp);
+    let mut config = fs::OpenOptions::new()
+        .append(true)
+        .open(tmp.path().join(".kbase/config.toml"))
+        .unwrap();
+    write!(
+        config,
+        "\n[vaults.test-vault.tags]\ncase_insensitive = true\nstrip_punctuation = true\n\
+         [vaults.test-vault.tags.aliases]\nwip = [\"in-progress\"]\n"
+    )
+    .unwrap();
+    cmd
+}
+
+#[test]
+fn tags_config_folds_spellings_into_canonical_tag() {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("rust/drift.md"),
+        "# Drift\n\n#WIP and #in-progress/review\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("rust/todo.md"), "# Todo\n\n#In-Progress\n").unwrap();
+    kbase_normalized(&tmp).arg("index").assert().success();
+
+    let output = kbase_normalized(&tmp).arg("tags").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Tag "), "got:\n{}", stdout);
+    assert!(stdout.contains("Spellings"), "got:\n{}", stdout);
+    let wip: Vec<String> = stdout
+        .lines()
+        .filter(|l| l.starts_with("wip"))
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    assert_eq!(
+        wip,
+        ["wip 4 In-Progress, WIP", "wip/review 1 in-progress/review"]
+    );
+
+    // Query-time tags are normalized the same way
+    kbase_normalized(&tmp)
+        .args([
+            "notes",
+            "--tag",
+            "IN-PROGRESS",
+            "--domain",
+            "rust",
+            "--files",
+        ])
+        .assert()
+        .success()
+        .stdout("rust/drift.md\nrust/todo.md\n");
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use std::fs;
+use tempfile::TempDir;
+
+const BOARD: &str = "# Task Board\r\n\r\n## This Week\r\n\r\n- [ ] Review codecs #wip\r\n  - [ ] Read BlockTree\r\n\r\n## Later\r\n\r\n## Done\r\n\r\n- [x] Search flow notes\r\n";
+
+fn setup_board() -> TempDir {
+    let tmp = setup_vault();
+    fs::write(tmp.path().join("lucene/03-task-board.md"), BOARD).unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+fn read_board(tmp: &TempDir) -> String {
+    fs::read_to_string(tmp.path().join("lucene/03-task-board.md")).unwrap()
+}
+
+#[test]
+fn task_done_rewrites_only_the_marker() {
+    let tmp = setup_board();
+
+    kbase(&tmp)
+        .args(["task", "done", "lucene/03-task-board.md:6"])
+        .assert()
+        .success()
+        .stdout(contains("Marked done: lucene/03-task-board.md:6"));
+
+    assert_eq!(
+        read_board(&tmp),
+        BOARD.replace("  - [ ] Read BlockTree", "  - [x] Read BlockTree")
+    );
+
+    // The index was updated, so the task no longer shows as open
+    kbase(&tmp)
+        .args(["tasks", "--total"])
+        .assert()
+        .success()
+        .stdout("1\n");
+}
+
+#[test]
+fn task_done_refuses_when_line_changed() {
+    let tmp = setup_board();
+    let path = tmp.path().join("lucene/03-task-board.md");
+    let edited = BOARD.replace("## This Week\r\n", "## This Week\r\nNew intro line\r\n");
+    fs::write(&path, &edited).unwrap();
+
+    kbase(&tmp)
+        .args(["task", "done", "lucene/03-task-board.md:5"])
+        .assert()
+        .failure()
+        .stderr(contains("changed since the last index"));
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), edited);
+}
+
+#[test]
+fn task_done_unknown_location() {
+    let tmp = setup_board();
+
+    kbase(&tmp)
+        .args(["task", "done", "lucene/03-task-board.md:3"])
+        .assert()
+        .failure()
+        .stderr(contains("No task at lucene/03-task-board.md:3"));
+
+    kbase(&tmp)
+        .args(["task", "done", "lucene/03-task-board.md"])
+        .assert()
+        .failure()
+        .stderr(contains("expected <note>:<line>"));
+}
+
+#[test]
+fn task_add_under_heading() {
+    let tmp = setup_board();
+
+    kbase(&tmp)
+        .args([
+            "task",
+            "add",
+            "lucene/03-task-board.md",
+            "--under",
+            "## This Week",
+            "Write norms notes",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Added task to lucene/03-task-board.md:7"));
+
+    assert_eq!(
+        read_board(&tmp),
+        BOARD.replace(
+            "  - [ ] Read BlockTree\r\n",
+            "  - [ ] Read BlockTree\r\n- [ ] Write norms notes\r\n"
+        )
+    );
+}
+
+#[test]
+fn task_add_to_empty_section_and_end_of_note() {
+    let tmp = setup_board();
+
+    kbase(&tmp)
+        .args([
+            "task",
+            "add",
+            "lucene/03-task-board.md",
+            "--under",
+            "later",
+            "Study FSTs",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("lucene/03-task-board.md:10"));
+
+    kbase(&tmp)
+        .args(["task", "add", "lucene/03-task-board.md", "Last one"])
+        .assert()
+        .success();
+
+    let board = read_board(&tmp);
+    assert!(board.contains("## Later\r\n\r\n- [ ] Study FSTs\r\n"));
+    assert!(board.ends_with("- [x] Search flow notes\r\n- [ ] Last one\r\n"));
+}
+
+#[test]
+fn task_add_unknown_heading() {
+    let tmp = setup_board();
+
+    kbase(&tmp)
+        .args([
+            "task",
+            "add",
+            "lucene/03-task-board.md",
+            "--under",
+            "## Someday",
+            "Nope",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("heading not found"));
+}
+
+#[test]
+fn task_commands_accept_short_note_names() {
+    let tmp = setup_board();
+
+    kbase(&tmp)
+        .args(["task", "done", "task-board:5"])
+        .assert()
+        .success()
+        .stdout(contains("Marked done: lucene/03-task-board.md:5"));
+
+    kbase(&tmp)
+        .args(["task", "add", "lucene/03-task-board", "Profile merges"])
+        .assert()
+        .success()
+        .stdout(contains("Added task to lucene/03-task-board.md"));
+}
+
+#[test]
+fn task_add_refreshes_tag_index() {
+    let tmp = setup_board();
+
+    kbase(&tmp)
+        .args(["task", "add", "task-board", "Benchmark merges #perf-review"])
+        .assert()
+        .success();
+
+    kbase(&tmp)
+        .args(["notes", "--tag", "perf-review"])
+        .assert()
+        .success()
+        .stdout(contains("lucene/03-task-board.md"));
+}
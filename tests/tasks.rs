@@ -0,0 +1,189 @@
+mod common;
+
+use common::{kbase, setup_vault};
+use predicates::str::contains;
+use std::fs;
+use tempfile::TempDir;
+
+/// Add a task board and a note with tasks, then build the index.
+fn setup_tasks() -> TempDir {
+    let tmp = setup_vault();
+    fs::write(
+        tmp.path().join("lucene/03-task-board.md"),
+        "# Task Board\n\n\
+         ## This Week\n\n\
+         - [ ] Review codecs #wip\n  \
+           - [x] Read BlockTree\n\
+         - [-] Skip FST\n\
+         - [?] Ask about norms\n\n\
+         ## Done\n\n\
+         - [x] Search flow notes\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("rust/todo.md"),
+        "# Todo\n\n- [ ] Learn lifetimes\n",
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+#[test]
+fn tasks_lists_open_tasks_by_default() {
+    let tmp = setup_tasks();
+
+    let output = kbase(&tmp).arg("tasks").output().unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("lucene/03-task-board.md:5"));
+    assert!(stdout.contains("[ ] Review codecs #wip"));
+    assert!(stdout.contains("[?] Ask about norms"));
+    assert!(stdout.contains("rust/todo.md:3"));
+    assert!(!stdout.contains("Read BlockTree"));
+    assert!(!stdout.contains("Skip FST"));
+}
+
+#[test]
+fn tasks_done_and_all() {
+    let tmp = setup_tasks();
+
+    let output = kbase(&tmp).args(["tasks", "--done"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[x] Read BlockTree"));
+    assert!(stdout.contains("[x] Search flow notes"));
+    assert!(!stdout.contains("Review codecs"));
+
+    kbase(&tmp)
+        .args(["tasks", "--all", "--total"])
+        .assert()
+        .success()
+        .stdout("6\n");
+}
+
+#[test]
+fn tasks_domain_and_file_filters() {
+    let tmp = setup_tasks();
+
+    kbase(&tmp)
+        .args(["tasks", "--domain", "rust", "--total"])
+        .assert()
+        .success()
+        .stdout("1\n");
+
+    kbase(&tmp)
+        .args(["tasks", "--file", "lucene/03-task-board.md", "--total"])
+        .assert()
+        .success()
+        .stdout("2\n");
+
+    kbase(&tmp)
+        .args(["tasks", "--domain", "nope"])
+        .assert()
+        .failure()
+        .stderr(contains("Domain 'nope' does not exist"));
+}
+
+#[test]
+fn tasks_verbose_groups_by_note_and_heading() {
+    let tmp = setup_tasks();
+
+    let output = kbase(&tmp)
+        .args([
+            "tasks",
+            "--all",
+            "--verbose",
+            "--file",
+            "lucene/03-task-board.md",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(stdout.starts_with("lucene/03-task-board.md\n  This Week\n"));
+    assert!(stdout.contains("     5  [ ] Review codecs #wip\n"));
+    assert!(stdout.contains("     6    [x] Read BlockTree\n"));
+    assert!(stdout.contains("  Done\n    12  [x] Search flow notes\n"));
+}
+
+#[test]
+fn tasks_requires_index() {
+    let tmp = setup_vault();
+
+    kbase(&tmp)
+        .arg("tasks")
+        .assert()
+        .failure()
+        .stderr(contains("No task index found"));
+}
+
+/// Write a note with Tasks plugin annotations relative to today.
+fn setup_dated_tasks() -> TempDir {
+    let tmp = setup_vault();
+    let today = chrono::Local::now().date_naive();
+    let day = |offset: i64| (today + chrono::Duration::days(offset)).format("%Y-%m-%d");
+
+    fs::write(
+        tmp.path().join("rust/dated.md"),
+        format!(
+            "# Dated\n\n\
+             - [ ] Later 📅 {}\n\
+             - [ ] Today ⏫ 📅 {}\n\
+             - [ ] Late 🔽 📅 {}\n\
+             - [ ] Weekly 🔁 every week 🔺\n",
+            day(30),
+            day(0),
+            day(-3)
+        ),
+    )
+    .unwrap();
+    kbase(&tmp).arg("index").assert().success();
+    tmp
+}
+
+#[test]
+fn tasks_due_filters() {
+    let tmp = setup_dated_tasks();
+
+    let output = kbase(&tmp)
+        .args(["tasks", "--due", "today"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("Today ⏫"));
+
+    let output = kbase(&tmp)
+        .args(["tasks", "--due", "overdue"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("Late 🔽"));
+}
+
+#[test]
+fn tasks_sort_by_due_and_priority_filter() {
+    let tmp = setup_dated_tasks();
+
+    let output = kbase(&tmp)
+        .args(["tasks", "--domain", "rust", "--sort", "due"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let order: Vec<&str> = stdout
+        .lines()
+        .map(|l| l.split("] ").nth(1).unwrap().split(' ').next().unwrap())
+        .collect();
+    assert_eq!(order, vec!["Late", "Today", "Later", "Weekly"]);
+
+    let output = kbase(&tmp)
+        .args(["tasks", "--priority", "high"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains("Today ⏫"));
+    assert!(stdout.contains("Weekly 🔁 every week 🔺"));
+}